@@ -4,11 +4,10 @@ use regex::{Regex, RegexBuilder};
 use sjmcl_types::error::SJMCLResult;
 use sjmcl_types::partial::{PartialError, PartialUpdate};
 use sjmcl_types::storage::{Storage, load_json_async, save_json_async};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_http::reqwest;
 use tokio;
@@ -16,53 +15,94 @@ use tokio::sync::Semaphore;
 use url::Url;
 use zip::read::ZipArchive;
 
+use crate::instance::helpers::client_jar::{load_protocol_version_from_jar, load_version_from_zip};
 use crate::instance::helpers::client_json::{
-  McClientInfo, remove_mod_loader_from_client_info, remove_optifine_from_client_info,
-  replace_native_libraries,
+  McClientInfo, libraries_to_info, remove_mod_loader_from_client_info,
+  remove_optifine_from_client_info, replace_native_libraries,
+};
+use crate::instance::helpers::client_patch::{
+  add_patch_to_client_info, load_client_json_from_source, remove_patch_from_client_info,
+  reorder_patches_in_client_info,
 };
 use crate::instance::helpers::game_version::{build_game_version_cmp_fn, compare_game_versions};
 use crate::instance::helpers::loader::common::{execute_processors, install_mod_loader};
 use crate::instance::helpers::loader::fabric::remove_fabric_api_mods;
 use crate::instance::helpers::loader::forge::InstallProfile;
+use crate::instance::helpers::loader::liteloader::install_liteloader_loader;
 use crate::instance::helpers::loader::optifine::{
-  download_optifine_installer, finish_optifine_install,
+  check_optifine_compatibility, download_optifine_as_mod, download_optifine_installer,
+  finish_optifine_install, remove_optifine_mod,
+};
+use crate::instance::helpers::local_cache::{
+  get_mod_info_from_jar_cached, load_resourcepack_from_zip_cached, save_local_resource_cache,
+};
+use crate::instance::helpers::lockfile::{
+  INSTANCE_LOCK_FILE_NAME, InstanceLock, InstanceLockSyncReport, apply_locked_renames,
+  build_instance_lock, collect_local_file_hashes, load_instance_lock, remove_unlocked_files,
 };
 use crate::instance::helpers::misc::{
   get_instance_game_config, get_instance_subdir_path_by_id, get_instance_subdir_paths,
   refresh_and_update_instances, unify_instance_name,
 };
+use crate::instance::helpers::mod_set::{apply_enabled_mods, get_enabled_mod_file_names};
 use crate::instance::helpers::modpack::export::{
   ExportModpackOptions, build_export_bundle, create_modpack_zip, list_files,
-  validate_export_options,
+  normalize_mod_loader_install_version, validate_export_options,
 };
 use crate::instance::helpers::modpack::import::{
-  ModpackMetaInfo, extract_overrides, get_download_params,
+  ModpackMetaInfo, extract_overrides, get_download_params, list_overrides,
+};
+use crate::instance::helpers::modpack::update::{
+  MODPACK_RECORD_FILE_NAME, ModpackRecord, ModpackUpdateReport, apply_modpack_update,
+  build_modpack_record, finish_modpack_update, plan_modpack_update, save_modpack_base_files,
+  save_modpack_record,
 };
 use crate::instance::helpers::mods::common::{
-  check_potential_incompatibility, compress_icon, get_mod_info_from_dir, get_mod_info_from_jar,
+  check_potential_incompatibility, compress_icon, get_local_mod_ids, get_mod_info_from_dir,
+  load_local_mod_infos,
+};
+use crate::instance::helpers::mods::dependency::check_local_mod_dependencies;
+use crate::instance::helpers::options_txt::{
+  GameOptions, OptionsTxt, get_minecraft_lang_tag, get_options_txt_path_by_instance_id,
 };
-use crate::instance::helpers::options_txt::get_minecraft_lang_tag;
 use crate::instance::helpers::resourcepack::{
-  load_resourcepack_from_dir, load_resourcepack_from_zip,
+  PackMeta, get_resource_pack_format, get_resource_pack_option_name, load_resourcepack_from_dir,
+  load_resourcepack_from_zip,
+};
+use crate::instance::helpers::screenshot::{
+  index_screenshots, list_screenshots, load_screenshots_with_index, sort_screenshots,
 };
 use crate::instance::helpers::server::{
-  GameServerInfo, get_servers_nbt_path_by_instance_id, load_servers_info_from_nbt,
-  query_servers_online, save_servers_to_nbt,
+  GameServerInfo, NbtServerInfo, check_servers_compatibility, get_servers_nbt_path_by_instance_id,
+  load_servers_from_nbt, load_servers_info_from_nbt, query_servers_online, reorder_servers,
+  save_servers_to_nbt,
+};
+use crate::instance::helpers::server_instance::{
+  SERVER_INSTANCES_DIR_NAME, copy_mods_to_server, get_paired_server_instance,
+  get_server_instance_by_id, install_server_instance, refresh_and_update_server_instances,
+  run_server_loader_installer,
+};
+use crate::instance::helpers::server_properties::{
+  ServerProperties, accept_eula, get_server_properties_path, is_eula_accepted,
 };
+use crate::instance::helpers::shaderpack::{get_active_shader_pack, set_active_shader_pack};
 use crate::instance::helpers::world::{load_level_data_from_nbt, load_world_info_from_dir};
 use crate::instance::models::misc::{
-  Instance, InstanceError, InstanceSubdirType, InstanceSummary, LocalModInfo, ModLoader,
-  ModLoaderStatus, ModLoaderType, ModpackFileList, OptiFine, ResourcePackInfo, SchematicInfo,
-  ScreenshotInfo, ShaderPackInfo,
+  ClientPatchInfo, Instance, InstanceError, InstanceSubdirType, InstanceSummary, LocalModInfo,
+  ModDependencyIssue, ModLoader, ModLoaderStatus, ModLoaderType, ModpackFileList, OptiFine,
+  ResourcePackInfo, SchematicInfo, ScreenshotInfo, ScreenshotPage, ScreenshotSortBy,
+  ServerInstance, ServerInstanceSummary, ShaderPackInfo,
 };
 use crate::instance::models::world::base::WorldInfo;
 use crate::instance::models::world::level::LevelData;
 use crate::launch::helpers::file_validator::{get_invalid_assets, get_invalid_library_files};
-use crate::launch::helpers::jre_selector::{get_minimum_java_version_by_game, select_java_runtime};
+use crate::launch::helpers::jre_selector::{get_recommended_java_version, select_java_runtime};
+use crate::launch::helpers::server_process::get_running_server_launching_id;
 use crate::launch::models::LaunchError;
 use crate::launcher_config::helpers::java::build_mojang_java_download_params;
 use crate::launcher_config::helpers::misc::get_global_game_config;
 use crate::launcher_config::models::{GameConfig, GameDirectory, LauncherConfig};
+use crate::resource::commands::fetch_game_version_specific;
 use crate::resource::helpers::misc::get_source_priority_list;
 use crate::resource::helpers::translation::{
   LOCAL_MOD_TRANSLATION_CACHE_EXPIRY_HOURS, LocalModTranslationEntry, LocalModTranslationsCache,
@@ -529,6 +569,38 @@ pub async fn retrieve_game_server_list(
   // query_online is true, amend query and return player count and online status
   if query_online {
     game_servers = query_servers_online(game_servers).await?;
+
+    // compare the reported version and required mods with the local instance
+    let instance = {
+      let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+      let state = binding.lock()?;
+      state
+        .get(&instance_id)
+        .ok_or(InstanceError::InstanceNotFoundByID)?
+        .clone()
+    };
+    let client_jar_path = instance.version_path.join(format!("{}.jar", instance.name));
+    let client_protocol = fs::File::open(client_jar_path)
+      .ok()
+      .and_then(|file| ZipArchive::new(file).ok())
+      .and_then(|mut jar| load_protocol_version_from_jar(&mut jar));
+
+    let mut local_mod_ids = HashSet::new();
+    if game_servers.iter().any(|server| server.mods.is_some())
+      && let Some(mods_dir) =
+        get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    {
+      let loader_type = (instance.mod_loader.loader_type != ModLoaderType::Unknown)
+        .then_some(instance.mod_loader.loader_type);
+      local_mod_ids = get_local_mod_ids(&mods_dir, loader_type).await;
+    }
+
+    check_servers_compatibility(
+      &mut game_servers,
+      &instance.version,
+      client_protocol,
+      &local_mod_ids,
+    );
   }
 
   Ok(game_servers)
@@ -544,7 +616,9 @@ pub async fn delete_game_server(
     Some(path) => path,
     None => return Err(InstanceError::InstanceNotFoundByID.into()),
   };
-  let mut existing_servers = load_servers_info_from_nbt(&nbt_path).await?;
+  let mut existing_servers = load_servers_from_nbt(&nbt_path)
+    .await
+    .map_err(|_| InstanceError::ServerNbtReadError)?;
 
   existing_servers.retain(|server| server.ip != server_addr);
   save_servers_to_nbt(&nbt_path, &existing_servers)
@@ -560,12 +634,15 @@ pub async fn add_game_server(
   instance_id: String,
   server_addr: String,
   server_name: String,
+  accept_textures: Option<bool>,
 ) -> SJMCLResult<()> {
   let nbt_path = match get_servers_nbt_path_by_instance_id(&app, &instance_id) {
     Some(path) => path,
     None => return Err(InstanceError::InstanceNotFoundByID.into()),
   };
-  let mut existing_servers = load_servers_info_from_nbt(&nbt_path).await?;
+  let mut existing_servers = load_servers_from_nbt(&nbt_path)
+    .await
+    .map_err(|_| InstanceError::ServerNbtReadError)?;
 
   if existing_servers
     .iter()
@@ -574,9 +651,10 @@ pub async fn add_game_server(
     return Err(InstanceError::DuplicateServer.into());
   }
 
-  existing_servers.push(GameServerInfo {
+  existing_servers.push(NbtServerInfo {
     ip: server_addr,
     name: server_name,
+    accept_textures,
     ..Default::default()
   });
   save_servers_to_nbt(&nbt_path, &existing_servers)
@@ -586,6 +664,368 @@ pub async fn add_game_server(
   Ok(())
 }
 
+#[tauri::command]
+pub async fn update_game_server(
+  app: AppHandle,
+  instance_id: String,
+  server_addr: String,
+  new_server_addr: String,
+  new_server_name: String,
+  accept_textures: Option<bool>,
+) -> SJMCLResult<()> {
+  let nbt_path = match get_servers_nbt_path_by_instance_id(&app, &instance_id) {
+    Some(path) => path,
+    None => return Err(InstanceError::InstanceNotFoundByID.into()),
+  };
+  let mut existing_servers = load_servers_from_nbt(&nbt_path)
+    .await
+    .map_err(|_| InstanceError::ServerNbtReadError)?;
+
+  if new_server_addr != server_addr
+    && existing_servers
+      .iter()
+      .any(|server| server.ip == new_server_addr)
+  {
+    return Err(InstanceError::DuplicateServer.into());
+  }
+
+  let server = existing_servers
+    .iter_mut()
+    .find(|server| server.ip == server_addr)
+    .ok_or(InstanceError::ServerNotFound)?;
+  // edit in place, the icon and other tags are preserved
+  server.ip = new_server_addr;
+  server.name = new_server_name;
+  server.accept_textures = accept_textures;
+
+  save_servers_to_nbt(&nbt_path, &existing_servers)
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn reorder_game_servers(
+  app: AppHandle,
+  instance_id: String,
+  server_addrs: Vec<String>,
+) -> SJMCLResult<()> {
+  let nbt_path = match get_servers_nbt_path_by_instance_id(&app, &instance_id) {
+    Some(path) => path,
+    None => return Err(InstanceError::InstanceNotFoundByID.into()),
+  };
+  let existing_servers = load_servers_from_nbt(&nbt_path)
+    .await
+    .map_err(|_| InstanceError::ServerNbtReadError)?;
+
+  save_servers_to_nbt(&nbt_path, &reorder_servers(existing_servers, &server_addrs))
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn sync_game_server_list(
+  app: AppHandle,
+  src_instance_id: String,
+  tgt_inst_ids: Vec<String>,
+  keep_other_servers: bool,
+) -> SJMCLResult<()> {
+  let src_path = match get_servers_nbt_path_by_instance_id(&app, &src_instance_id) {
+    Some(path) => path,
+    None => return Err(InstanceError::InstanceNotFoundByID.into()),
+  };
+  let src_servers = load_servers_from_nbt(&src_path)
+    .await
+    .map_err(|_| InstanceError::ServerNbtReadError)?;
+
+  for tgt_inst_id in tgt_inst_ids.iter().filter(|id| **id != src_instance_id) {
+    let tgt_path = match get_servers_nbt_path_by_instance_id(&app, tgt_inst_id) {
+      Some(path) => path,
+      None => return Err(InstanceError::InstanceNotFoundByID.into()),
+    };
+    // instances sharing the same game directory (no version isolation) share one servers.dat
+    if tgt_path == src_path {
+      continue;
+    }
+
+    // servers from the source list come first and follow its ordering
+    let mut servers = src_servers.clone();
+    if keep_other_servers {
+      let tgt_servers = load_servers_from_nbt(&tgt_path)
+        .await
+        .map_err(|_| InstanceError::ServerNbtReadError)?;
+      servers.extend(
+        tgt_servers
+          .into_iter()
+          .filter(|server| !src_servers.iter().any(|s| s.ip == server.ip)),
+      );
+    }
+    save_servers_to_nbt(&tgt_path, &servers)
+      .await
+      .map_err(|_| InstanceError::FileOperationError)?;
+  }
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn retrieve_game_options(
+  app: AppHandle,
+  instance_id: String,
+) -> SJMCLResult<GameOptions> {
+  let options_path = get_options_txt_path_by_instance_id(&app, &instance_id)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let options = OptionsTxt::load(&options_path)
+    .await
+    .map_err(|_| InstanceError::OptionsTxtReadError)?;
+
+  Ok(GameOptions::from(&options))
+}
+
+#[tauri::command]
+pub async fn update_game_options(
+  app: AppHandle,
+  instance_id: String,
+  game_options: GameOptions,
+) -> SJMCLResult<()> {
+  let options_path = get_options_txt_path_by_instance_id(&app, &instance_id)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let mut options = OptionsTxt::load(&options_path)
+    .await
+    .map_err(|_| InstanceError::OptionsTxtReadError)?;
+
+  game_options.apply_to(&mut options);
+  options
+    .save(&options_path)
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn copy_game_options(
+  app: AppHandle,
+  src_instance_id: String,
+  tgt_inst_ids: Vec<String>,
+  key_filters: Vec<String>,
+) -> SJMCLResult<()> {
+  let src_path = get_options_txt_path_by_instance_id(&app, &src_instance_id)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  if !src_path.exists() {
+    return Err(InstanceError::FileNotFoundError.into());
+  }
+  let src_options = OptionsTxt::load(&src_path)
+    .await
+    .map_err(|_| InstanceError::OptionsTxtReadError)?;
+
+  for tgt_inst_id in tgt_inst_ids.iter().filter(|id| **id != src_instance_id) {
+    let tgt_path = get_options_txt_path_by_instance_id(&app, tgt_inst_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    // instances sharing the same game directory (no version isolation) share one options.txt
+    if tgt_path == src_path {
+      continue;
+    }
+    let mut tgt_options = OptionsTxt::load(&tgt_path)
+      .await
+      .map_err(|_| InstanceError::OptionsTxtReadError)?;
+    tgt_options.merge_from(&src_options, &key_filters);
+    tgt_options
+      .save(&tgt_path)
+      .await
+      .map_err(|_| InstanceError::FileOperationError)?;
+  }
+
+  Ok(())
+}
+
+/// Generate `instance.lock` in the game root, recording the game, loaders and every
+/// mod, resource pack and shader pack file.
+#[tauri::command]
+pub async fn generate_instance_lock(
+  app: AppHandle,
+  instance_id: String,
+) -> SJMCLResult<InstanceLock> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let game_root = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Root)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+
+  let lock = build_instance_lock(&app, &instance).await?;
+  save_json_async(&lock, &game_root.join(INSTANCE_LOCK_FILE_NAME)).await?;
+  Ok(lock)
+}
+
+/// Add, remove or replace files so that the instance matches the lock.
+/// Uses the instance's own `instance.lock` if `lock_path` is None.
+/// If downloads are scheduled, call `finish_instance_lock_sync` after they succeed.
+#[tauri::command]
+pub async fn sync_instance_to_lock(
+  app: AppHandle,
+  instance_id: String,
+  lock_path: Option<PathBuf>,
+) -> SJMCLResult<InstanceLockSyncReport> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let game_root = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Root)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let lock_path = lock_path.unwrap_or_else(|| game_root.join(INSTANCE_LOCK_FILE_NAME));
+  let lock = load_instance_lock(&lock_path).await?;
+
+  // a different game or loader needs a new instance rather than a file sync
+  if lock.game_version != instance.version
+    || lock.mod_loader.loader_type != instance.mod_loader.loader_type
+  {
+    return Err(InstanceError::InstanceLockMismatch.into());
+  }
+
+  let mut local_files = collect_local_file_hashes(&app, &instance).await?;
+  let local_paths = local_files
+    .iter()
+    .map(|(_, path, _, _)| path.clone())
+    .collect::<HashSet<_>>();
+  let mut report = InstanceLockSyncReport::default();
+  let mut kept_paths = HashSet::new();
+  let mut renames = Vec::new();
+  let mut download_tasks = Vec::new();
+
+  // build the whole plan before touching any file
+  for locked_file in &lock.files {
+    let target = locked_file.local_path(&game_root);
+    kept_paths.insert(target.clone());
+
+    // reuse a local file with the same content, renaming it if needed
+    if let Some(index) = local_files
+      .iter()
+      .position(|(sha1, _, _, _)| sha1.eq_ignore_ascii_case(&locked_file.sha1))
+    {
+      let (_, _, _, full_path) = local_files.swap_remove(index);
+      if full_path != target {
+        renames.push((full_path, target));
+      }
+      continue;
+    }
+
+    let Some(src) = locked_file
+      .download_url
+      .as_deref()
+      .and_then(|download_url| url::Url::parse(download_url).ok())
+    else {
+      report.missing.push(locked_file.path.clone());
+      continue;
+    };
+    download_tasks.push(PTaskParam::Download(DownloadParam {
+      src,
+      dest: target,
+      filename: None,
+      sha1: (!locked_file.sha1.is_empty()).then(|| locked_file.sha1.clone()),
+    }));
+    if local_paths.contains(&locked_file.path) {
+      report.replaced.push(locked_file.path.clone());
+    } else {
+      report.added.push(locked_file.path.clone());
+    }
+  }
+
+  for (_, path, _, full_path) in local_files {
+    // files at a locked path are overwritten by the download
+    if !kept_paths.contains(&full_path) && !report.replaced.contains(&path) {
+      report.removed.push(path);
+    }
+  }
+
+  apply_locked_renames(&renames)?;
+
+  if download_tasks.is_empty() {
+    remove_unlocked_files(&app, &instance, &lock, &game_root)?;
+  } else {
+    // leftovers are removed by `finish_instance_lock_sync` once the downloads succeed
+    schedule_progressive_task_group(
+      app,
+      format!("instance-lock-sync?{}", instance.name),
+      download_tasks,
+      true,
+    )
+    .await?;
+  }
+
+  Ok(report)
+}
+
+/// Remove the files not listed in the lock after the downloads of `sync_instance_to_lock`
+/// succeeded, returns their paths relative to the game root.
+#[tauri::command]
+pub async fn finish_instance_lock_sync(
+  app: AppHandle,
+  instance_id: String,
+  lock_path: Option<PathBuf>,
+) -> SJMCLResult<Vec<String>> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let game_root = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Root)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let lock_path = lock_path.unwrap_or_else(|| game_root.join(INSTANCE_LOCK_FILE_NAME));
+  let lock = load_instance_lock(&lock_path).await?;
+
+  remove_unlocked_files(&app, &instance, &lock, &game_root)
+}
+
+#[tauri::command]
+pub async fn check_mod_dependencies(
+  app: AppHandle,
+  instance_id: String,
+) -> SJMCLResult<Vec<ModDependencyIssue>> {
+  let (mod_loader, game_version) = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    (instance.mod_loader.clone(), instance.version.clone())
+  };
+
+  let mods_dir = match get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+  {
+    Some(path) => path,
+    None => return Ok(Vec::new()),
+  };
+
+  let loader_type = mod_loader.loader_type;
+  let mod_infos = load_local_mod_infos(
+    &mods_dir,
+    (loader_type != ModLoaderType::Unknown).then_some(loader_type),
+  )
+  .await;
+
+  Ok(check_local_mod_dependencies(
+    &mod_infos,
+    &game_version,
+    loader_type,
+    &mod_loader.version,
+  ))
+}
+
 #[tauri::command]
 pub async fn retrieve_local_mod_list(
   app: AppHandle,
@@ -627,9 +1067,10 @@ pub async fn retrieve_local_mod_list(
       .acquire_owned()
       .await
       .map_err(|_| InstanceError::SemaphoreAcquireFailed)?;
+    let app = app.clone();
     let task = tokio::spawn(async move {
       log::debug!("Load mod info from jar: {}", path.display());
-      let info = get_mod_info_from_jar(&path, installed_loader_type)
+      let info = get_mod_info_from_jar_cached(&app, &path, installed_loader_type)
         .await
         .ok();
       drop(permit);
@@ -710,6 +1151,8 @@ pub async fn retrieve_local_mod_list(
     );
   }
   cache.save()?;
+  drop(cache);
+  save_local_resource_cache(&app)?;
 
   Ok(mod_infos)
 }
@@ -725,7 +1168,46 @@ pub async fn retrieve_resource_pack_list(
       Some(path) => path,
       None => return Ok(Vec::new()),
     };
+  let game_version = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance.version.clone()
+  };
+  let expected_format = get_resource_pack_format(&app, &game_version);
+  let has_file_prefix = compare_game_versions(&app, &game_version, "17w43a", false)
+    .await
+    .is_ge();
+
+  // enabled packs in options.txt are ordered from lowest to highest priority
+  let enabled_packs = match get_options_txt_path_by_instance_id(&app, &instance_id) {
+    Some(path) => OptionsTxt::load(&path)
+      .await
+      .map_err(|_| InstanceError::OptionsTxtReadError)?
+      .get_list("resourcePacks"),
+    None => Vec::new(),
+  };
+
   let mut info_list: Vec<ResourcePackInfo> = Vec::new();
+  let mut push_pack_info = |path: PathBuf, meta: PackMeta, icon_src: Option<ImageWrapper>| {
+    let name = match path.file_stem() {
+      Some(stem) => stem.to_string_lossy().to_string(),
+      None => String::new(),
+    };
+    let enabled = get_resource_pack_option_name(&path, has_file_prefix)
+      .is_some_and(|option_name| enabled_packs.contains(&option_name));
+    info_list.push(ResourcePackInfo {
+      name,
+      description: meta.description.clone(),
+      icon_src,
+      file_path: path,
+      enabled,
+      pack_format: meta.pack_format,
+      is_incompatible: expected_format.is_some_and(|format| !meta.supports_format(format)),
+    });
+  };
 
   let valid_extensions = RegexBuilder::new(r"\.zip$")
     .case_insensitive(true)
@@ -733,43 +1215,158 @@ pub async fn retrieve_resource_pack_list(
     .unwrap();
 
   for path in get_files_with_regex(&resource_packs_dir, &valid_extensions).unwrap_or(vec![]) {
-    if let Ok((description, icon_src)) = load_resourcepack_from_zip(&path) {
-      let name = match path.file_stem() {
-        Some(stem) => stem.to_string_lossy().to_string(),
-        None => String::new(),
-      };
-      info_list.push(ResourcePackInfo {
-        name,
-        description,
-        icon_src: icon_src.map(ImageWrapper::from).map(compress_icon),
-        file_path: path.clone(),
-      });
+    if let Ok((meta, icon_src)) = load_resourcepack_from_zip_cached(&app, &path).await {
+      push_pack_info(path, meta, icon_src);
     }
   }
+  save_local_resource_cache(&app)?;
 
   for path in get_subdirectories(&resource_packs_dir).unwrap_or(vec![]) {
-    if let Ok((description, icon_src)) = load_resourcepack_from_dir(&path).await {
-      let name = match path.file_stem() {
-        Some(stem) => stem.to_string_lossy().to_string(),
-        None => String::new(),
-      };
-      info_list.push(ResourcePackInfo {
-        name,
-        description,
-        icon_src: icon_src.map(ImageWrapper::from).map(compress_icon),
-        file_path: path.clone(),
-      });
+    if let Ok((meta, icon_src)) = load_resourcepack_from_dir(&path).await {
+      push_pack_info(
+        path,
+        meta,
+        icon_src.map(ImageWrapper::from).map(compress_icon),
+      );
     }
   }
+
+  // enabled packs first (highest priority on top, as in game), then the disabled ones
+  let priority = |info: &ResourcePackInfo| {
+    get_resource_pack_option_name(&info.file_path, has_file_prefix)
+      .and_then(|option_name| enabled_packs.iter().position(|p| *p == option_name))
+      .map(|idx| enabled_packs.len() - idx)
+      .unwrap_or(usize::MAX)
+  };
+  info_list.sort_by_key(priority);
+
   Ok(info_list)
 }
 
+/// Enable (at the highest priority) or disable a resource pack in options.txt.
 #[tauri::command]
-pub async fn retrieve_server_resource_pack_list(
+pub async fn toggle_resource_pack(
   app: AppHandle,
   instance_id: String,
-) -> SJMCLResult<Vec<ResourcePackInfo>> {
-  let resource_packs_dir = match get_instance_subdir_path_by_id(
+  file_path: PathBuf,
+  enable: bool,
+) -> SJMCLResult<()> {
+  let game_version = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance.version.clone()
+  };
+  let has_file_prefix = compare_game_versions(&app, &game_version, "17w43a", false)
+    .await
+    .is_ge();
+  let option_name = get_resource_pack_option_name(&file_path, has_file_prefix)
+    .ok_or(InstanceError::InvalidSourcePath)?;
+
+  let options_path = get_options_txt_path_by_instance_id(&app, &instance_id)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let mut options = OptionsTxt::load(&options_path)
+    .await
+    .map_err(|_| InstanceError::OptionsTxtReadError)?;
+
+  let mut enabled_packs = options.get_list("resourcePacks");
+  enabled_packs.retain(|p| *p != option_name);
+  if enable {
+    enabled_packs.push(option_name.clone());
+  }
+  options.set_list("resourcePacks", &enabled_packs);
+
+  // the game disables incompatible packs on load unless they are listed in `incompatibleResourcePacks`
+  let mut incompatible_packs = options.get_list("incompatibleResourcePacks");
+  incompatible_packs.retain(|p| *p != option_name);
+  if enable && let Some(expected_format) = get_resource_pack_format(&app, &game_version) {
+    let meta = if file_path.is_dir() {
+      load_resourcepack_from_dir(&file_path).await.map(|(m, _)| m)
+    } else {
+      load_resourcepack_from_zip(&file_path).map(|(m, _)| m)
+    };
+    if meta.is_ok_and(|meta| !meta.supports_format(expected_format)) {
+      incompatible_packs.push(option_name);
+    }
+  }
+  options.set_list("incompatibleResourcePacks", &incompatible_packs);
+
+  options
+    .save(&options_path)
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  Ok(())
+}
+
+/// Reorder the enabled resource packs, `file_paths` is ordered from the highest priority to the lowest.
+/// Built-in packs (e.g. `vanilla`, `fabric`) keep their original order below the packs from the folder.
+#[tauri::command]
+pub async fn reorder_resource_packs(
+  app: AppHandle,
+  instance_id: String,
+  file_paths: Vec<PathBuf>,
+) -> SJMCLResult<()> {
+  let game_version = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance.version.clone()
+  };
+  let has_file_prefix = compare_game_versions(&app, &game_version, "17w43a", false)
+    .await
+    .is_ge();
+  let ordered_names = file_paths
+    .iter()
+    .rev()
+    .filter_map(|path| get_resource_pack_option_name(path, has_file_prefix))
+    .collect::<Vec<_>>();
+
+  let options_path = get_options_txt_path_by_instance_id(&app, &instance_id)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let mut options = OptionsTxt::load(&options_path)
+    .await
+    .map_err(|_| InstanceError::OptionsTxtReadError)?;
+
+  // before 1.13, packs from the folder are not prefixed and no built-in pack is listed
+  let (mut enabled_packs, folder_packs): (Vec<_>, Vec<_>) = options
+    .get_list("resourcePacks")
+    .into_iter()
+    .partition(|p| has_file_prefix && !p.starts_with("file/"));
+  // enabled packs not given in `file_paths` keep their slots, the given ones fill the other
+  // slots in the new order, and the packs enabled only now are put last (highest priority)
+  let (reordered, newly_enabled): (Vec<_>, Vec<_>) = ordered_names
+    .into_iter()
+    .partition(|p| folder_packs.contains(p));
+  let mut reordered_iter = reordered.iter();
+  for pack in &folder_packs {
+    if reordered.contains(pack) {
+      enabled_packs.extend(reordered_iter.next().cloned());
+    } else {
+      enabled_packs.push(pack.clone());
+    }
+  }
+  enabled_packs.extend(newly_enabled);
+  options.set_list("resourcePacks", &enabled_packs);
+
+  options
+    .save(&options_path)
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn retrieve_server_resource_pack_list(
+  app: AppHandle,
+  instance_id: String,
+) -> SJMCLResult<Vec<ResourcePackInfo>> {
+  let resource_packs_dir = match get_instance_subdir_path_by_id(
     &app,
     &instance_id,
     &InstanceSubdirType::ServerResourcePacks,
@@ -785,22 +1382,26 @@ pub async fn retrieve_server_resource_pack_list(
     .unwrap();
 
   for path in get_files_with_regex(&resource_packs_dir, &valid_extensions).unwrap_or(vec![]) {
-    if let Ok((description, icon_src)) = load_resourcepack_from_zip(&path) {
+    if let Ok((meta, icon_src)) = load_resourcepack_from_zip(&path) {
       let name = match path.file_stem() {
         Some(stem) => stem.to_string_lossy().to_string(),
         None => String::new(),
       };
       info_list.push(ResourcePackInfo {
         name,
-        description,
+        description: meta.description,
         icon_src: icon_src.map(ImageWrapper::from).map(compress_icon),
         file_path: path.clone(),
+        // server resource packs are managed by the game itself
+        enabled: false,
+        pack_format: meta.pack_format,
+        is_incompatible: false,
       });
     }
   }
 
   for path in get_subdirectories(&resource_packs_dir).unwrap_or(vec![]) {
-    if let Ok((description, icon_src)) = load_resourcepack_from_dir(&path).await {
+    if let Ok((meta, icon_src)) = load_resourcepack_from_dir(&path).await {
       let name = match path.file_stem() {
         Some(stem) => stem.to_string_lossy().to_string(),
         None => String::new(),
@@ -808,9 +1409,13 @@ pub async fn retrieve_server_resource_pack_list(
 
       info_list.push(ResourcePackInfo {
         name,
-        description,
+        description: meta.description,
         icon_src: icon_src.map(ImageWrapper::from).map(compress_icon),
         file_path: path.clone(),
+        // server resource packs are managed by the game itself
+        enabled: false,
+        pack_format: meta.pack_format,
+        is_incompatible: false,
       });
     }
   }
@@ -861,20 +1466,35 @@ pub fn retrieve_schematic_list(
 }
 
 #[tauri::command]
-pub fn retrieve_shader_pack_list(
+pub async fn retrieve_shader_pack_list(
   app: AppHandle,
   instance_id: String,
 ) -> SJMCLResult<Vec<ShaderPackInfo>> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
   // Get the shaderpacks directory based on the instance
-  let shaderpacks_dir =
-    match get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::ShaderPacks) {
-      Some(path) => path,
-      None => return Ok(Vec::new()),
-    };
+  let subdirs = get_instance_subdir_paths(
+    &app,
+    &instance,
+    &[&InstanceSubdirType::Root, &InstanceSubdirType::ShaderPacks],
+  )
+  .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let [game_root, shaderpacks_dir] = subdirs.as_slice() else {
+    return Err(InstanceError::InstanceNotFoundByID.into());
+  };
 
   if !shaderpacks_dir.exists() {
     return Ok(Vec::new());
   }
+  let active_shader_pack = get_active_shader_pack(game_root, &instance)
+    .await
+    .unwrap_or_default();
 
   let valid_extensions = RegexBuilder::new(r"\.zip$")
     .case_insensitive(true)
@@ -882,17 +1502,64 @@ pub fn retrieve_shader_pack_list(
     .unwrap();
   let mut shaderpack_list = Vec::new();
   for path in get_files_with_regex(shaderpacks_dir, &valid_extensions)? {
+    let enabled = path
+      .file_name()
+      .is_some_and(|name| active_shader_pack.as_deref() == Some(&*name.to_string_lossy()));
     shaderpack_list.push(ShaderPackInfo {
       file_name: path.file_stem().unwrap().to_string_lossy().to_string(),
       file_path: path,
+      enabled,
     });
   }
 
   Ok(shaderpack_list)
 }
 
+/// Choose the active shader pack (by its file path), or turn shaders off if `file_path` is None.
+/// Writes to the OptiFine or Iris/Oculus config depending on the shader loader of the instance.
+#[tauri::command]
+pub async fn select_shader_pack(
+  app: AppHandle,
+  instance_id: String,
+  file_path: Option<PathBuf>,
+) -> SJMCLResult<()> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let game_root = get_instance_subdir_paths(&app, &instance, &[&InstanceSubdirType::Root])
+    .ok_or(InstanceError::InstanceNotFoundByID)?[0]
+    .clone();
+
+  let file_name = match &file_path {
+    Some(path) => {
+      if !path.exists() {
+        return Err(InstanceError::FileNotFoundError.into());
+      }
+      Some(
+        path
+          .file_name()
+          .ok_or(InstanceError::InvalidSourcePath)?
+          .to_string_lossy()
+          .to_string(),
+      )
+    }
+    None => None,
+  };
+
+  set_active_shader_pack(&game_root, &instance, file_name.as_deref())
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  Ok(())
+}
+
 #[tauri::command]
-pub fn retrieve_screenshot_list(
+pub async fn retrieve_screenshot_list(
   app: AppHandle,
   instance_id: String,
 ) -> SJMCLResult<Vec<ScreenshotInfo>> {
@@ -902,148 +1569,423 @@ pub fn retrieve_screenshot_list(
       None => return Ok(Vec::new()),
     };
 
-  if !screenshots_dir.exists() {
-    return Ok(Vec::new());
-  }
+  let mut screenshot_list =
+    load_screenshots_with_index(&app, &screenshots_dir, &instance_id).await?;
+  sort_screenshots(&mut screenshot_list, ScreenshotSortBy::TimeDesc);
 
-  // The default screenshot format in Minecraft is PNG. For broader compatibility, JPG and JPEG formats are also included here.
-  let valid_extensions = RegexBuilder::new(r"\.(jpg|jpeg|png)$")
-    .case_insensitive(true)
-    .build()
-    .unwrap();
+  Ok(screenshot_list)
+}
+
+/// Retrieve a page of screenshots of one instance, or of all instances if `instance_id` is None.
+#[tauri::command]
+pub async fn retrieve_screenshot_page(
+  app: AppHandle,
+  instance_id: Option<String>,
+  sort_by: ScreenshotSortBy,
+  page: usize,
+  page_size: usize,
+) -> SJMCLResult<ScreenshotPage> {
+  let instance_ids = match instance_id {
+    Some(id) => vec![id],
+    None => {
+      let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+      let state = binding.lock()?;
+      let mut ids = state.keys().cloned().collect::<Vec<_>>();
+      ids.sort();
+      ids
+    }
+  };
+
+  let mut visited_dirs = HashSet::new();
   let mut screenshot_list = Vec::new();
-  for path in get_files_with_regex(screenshots_dir, &valid_extensions)? {
-    let metadata = path.metadata().unwrap();
-    let modified_time = metadata.modified().unwrap();
-    let timestamp = modified_time
-      .duration_since(SystemTime::UNIX_EPOCH)
-      .unwrap()
-      .as_secs();
-    screenshot_list.push(ScreenshotInfo {
-      file_name: path.file_stem().unwrap().to_string_lossy().to_string(),
-      file_path: path,
-      time: timestamp,
-    });
+  for id in instance_ids {
+    let Some(screenshots_dir) =
+      get_instance_subdir_path_by_id(&app, &id, &InstanceSubdirType::Screenshots)
+    else {
+      continue;
+    };
+    // instances without version isolation share the same screenshots folder
+    if !visited_dirs.insert(screenshots_dir.clone()) {
+      continue;
+    }
+    screenshot_list.extend(list_screenshots(&screenshots_dir, &id)?);
   }
+  // sort by file metadata first, then only decode the requested page
+  sort_screenshots(&mut screenshot_list, sort_by);
 
-  Ok(screenshot_list)
+  let total = screenshot_list.len();
+  let mut items = screenshot_list
+    .into_iter()
+    .skip(page.saturating_mul(page_size))
+    .take(page_size)
+    .collect::<Vec<_>>();
+  for screenshots_dir in visited_dirs {
+    let positions = (0..items.len())
+      .filter(|&i| items[i].file_path.parent() == Some(screenshots_dir.as_path()))
+      .collect::<Vec<_>>();
+    if positions.is_empty() {
+      continue;
+    }
+    let mut dir_items = positions
+      .iter()
+      .map(|&i| items[i].clone())
+      .collect::<Vec<_>>();
+    index_screenshots(&app, &screenshots_dir, &mut dir_items).await?;
+    for (i, item) in positions.into_iter().zip(dir_items) {
+      items[i] = item;
+    }
+  }
+
+  Ok(ScreenshotPage { items, total })
 }
 
 lazy_static! {
-  static ref RENAME_LOCK: Mutex<()> = Mutex::new(());
-  static ref RENAME_REGEX: Regex = RegexBuilder::new(r"^(.*?)(\.disabled)*$")
+  static ref SCREENSHOT_EXT_REGEX: Regex = RegexBuilder::new(r"\.(jpg|jpeg|png)$")
     .case_insensitive(true)
     .build()
     .unwrap();
 }
 
 #[tauri::command]
-pub fn toggle_mod_by_extension(file_path: PathBuf, enable: bool) -> SJMCLResult<()> {
-  let _lock = RENAME_LOCK.lock().expect("Failed to acquire lock");
-  if !file_path.is_file() {
-    return Err(InstanceError::FileNotFoundError.into());
-  }
-
-  let file_name = file_path
-    .file_name()
-    .unwrap_or_default()
-    .to_str()
-    .unwrap_or_default();
-
-  let new_name = if enable {
-    if let Some(captures) = RENAME_REGEX.captures(file_name) {
-      captures
-        .get(1)
-        .map(|m| m.as_str())
-        .unwrap_or(file_name)
-        .to_string()
-    } else {
-      file_name.to_string()
+pub fn delete_screenshots(
+  app: AppHandle,
+  instance_id: String,
+  file_paths: Vec<PathBuf>,
+) -> SJMCLResult<()> {
+  let screenshots_dir =
+    get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Screenshots)
+      .and_then(|dir| fs::canonicalize(dir).ok())
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+  // only screenshots of the instance can be deleted
+  let mut canonical_paths = Vec::new();
+  for path in &file_paths {
+    let path = fs::canonicalize(path).map_err(|_| InstanceError::InvalidSourcePath)?;
+    if !path.is_file()
+      || !path.starts_with(&screenshots_dir)
+      || !SCREENSHOT_EXT_REGEX.is_match(&path.to_string_lossy())
+    {
+      return Err(InstanceError::InvalidSourcePath.into());
     }
-  } else if RENAME_REGEX.is_match(file_name) {
-    format!("{}.disabled", file_name)
-  } else {
-    file_name.to_string()
-  };
-  let new_path = file_path.with_file_name(new_name);
-
-  if new_path != file_path {
-    fs::rename(&file_path, &new_path)?;
+    canonical_paths.push(path);
+  }
+  // cached thumbnails are dropped on the next retrieval
+  for path in canonical_paths {
+    fs::remove_file(&path).map_err(|_| InstanceError::FileOperationError)?;
   }
 
   Ok(())
 }
 
 #[tauri::command]
-pub async fn retrieve_world_details(
-  app: AppHandle,
-  instance_id: String,
-  world_name: String,
-) -> SJMCLResult<LevelData> {
-  let worlds_dir =
-    match get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Saves) {
-      Some(path) => path,
-      None => return Err(InstanceError::WorldNotExistError.into()),
-    };
-  let level_path = worlds_dir.join(world_name).join("level.dat");
-  if tokio::fs::metadata(&level_path).await.is_err() {
-    return Err(InstanceError::LevelNotExistError.into());
+pub fn copy_screenshots(file_paths: Vec<PathBuf>, target_dir: PathBuf) -> SJMCLResult<()> {
+  if !target_dir.exists() {
+    fs::create_dir_all(&target_dir).map_err(|_| InstanceError::FolderCreationFailed)?;
   }
-  if let Ok(level_data) = load_level_data_from_nbt(&level_path).await {
-    Ok(level_data)
-  } else {
-    Err(InstanceError::LevelParseError.into())
+  for path in file_paths {
+    let file_name = path
+      .file_name()
+      .filter(|_| path.is_file())
+      .ok_or(InstanceError::InvalidSourcePath)?;
+    let dest_path = generate_unique_filename(&target_dir, file_name);
+    fs::copy(&path, &dest_path).map_err(|_| InstanceError::FileCopyFailed)?;
   }
+
+  Ok(())
+}
+
+lazy_static! {
+  static ref RENAME_LOCK: Mutex<()> = Mutex::new(());
+  static ref RENAME_REGEX: Regex = RegexBuilder::new(r"^(.*?)(\.disabled)*$")
+    .case_insensitive(true)
+    .build()
+    .unwrap();
 }
 
 #[tauri::command]
-pub fn create_launch_desktop_shortcut(
-  app: AppHandle,
-  instance_id: String,
-  icon_src: String,
-) -> SJMCLResult<()> {
+pub fn retrieve_mod_sets(app: AppHandle, instance_id: String) -> SJMCLResult<Vec<ModSet>> {
   let binding = app.state::<Mutex<HashMap<String, Instance>>>();
-  let state = binding
-    .lock()
-    .map_err(|_| InstanceError::InstanceNotFoundByID)?;
+  let state = binding.lock()?;
   let instance = state
     .get(&instance_id)
     .ok_or(InstanceError::InstanceNotFoundByID)?;
+  Ok(instance.mod_sets.clone())
+}
 
-  let name = instance.name.clone();
-  let encoded_id = url::form_urlencoded::Serializer::new(String::new())
-    .append_pair("id", &instance.id)
-    .finish()
-    .replace("+", "%20");
-  let url = format!("sjmcl://launch?{}", encoded_id);
-
-  #[cfg(any(target_os = "windows", target_os = "linux"))]
-  let icon_path = {
-    use crate::instance::helpers::misc::create_instance_shortcut_icon;
-    create_instance_shortcut_icon(&app, instance, &icon_src).ok()
-  };
-  #[cfg(target_os = "macos")]
-  let icon_path = {
-    let _ = icon_src; // explicitly consume to avoid warning
-    None
+/// Save the currently enabled mods as a named set, overwriting the set with the same name.
+#[tauri::command]
+pub async fn save_mod_set(
+  app: AppHandle,
+  instance_id: String,
+  name: String,
+) -> SJMCLResult<ModSet> {
+  let mods_dir = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let mod_set = ModSet {
+    name,
+    enabled_mods: get_enabled_mod_file_names(&mods_dir),
   };
 
-  create_url_shortcut(&app, name, url, icon_path)
-    .map_err(|_| InstanceError::ShortcutCreationFailed)?;
-
-  Ok(())
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let mut state = binding.lock()?;
+    let instance = state
+      .get_mut(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    match instance
+      .mod_sets
+      .iter_mut()
+      .find(|s| s.name == mod_set.name)
+    {
+      Some(existing) => *existing = mod_set.clone(),
+      None => instance.mod_sets.push(mod_set.clone()),
+    }
+    instance.clone()
+  };
+  instance.save_json_cfg().await?;
+  Ok(mod_set)
 }
 
 #[tauri::command]
-pub async fn create_instance(
-  app: AppHandle,
-  directory: GameDirectory,
-  name: String,
-  description: String,
+pub async fn delete_mod_set(app: AppHandle, instance_id: String, name: String) -> SJMCLResult<()> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let mut state = binding.lock()?;
+    let instance = state
+      .get_mut(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance.mod_sets.retain(|s| s.name != name);
+    instance.clone()
+  };
+  instance.save_json_cfg().await?;
+  Ok(())
+}
+
+#[tauri::command]
+pub fn apply_mod_set(app: AppHandle, instance_id: String, name: String) -> SJMCLResult<()> {
+  let mod_set = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance
+      .mod_sets
+      .iter()
+      .find(|s| s.name == name)
+      .cloned()
+      .ok_or(InstanceError::ModSetNotFound)?
+  };
+  let mods_dir = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+
+  let _lock = RENAME_LOCK.lock().expect("Failed to acquire lock");
+  apply_enabled_mods(&mods_dir, &mod_set.enabled_mods.into_iter().collect())
+}
+
+/// Start bisecting the enabled mods to find the one crashing the game.
+/// After each test launch, report the result by `report_mod_bisect`.
+#[tauri::command]
+pub async fn start_mod_bisect(app: AppHandle, instance_id: String) -> SJMCLResult<ModBisectState> {
+  let mods_dir = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let bisect = ModBisectState::new(get_enabled_mod_file_names(&mods_dir));
+  update_mod_bisect(&app, &instance_id, &mods_dir, Some(bisect.clone())).await?;
+  Ok(bisect)
+}
+
+#[tauri::command]
+pub async fn report_mod_bisect(
+  app: AppHandle,
+  instance_id: String,
+  crashed: bool,
+) -> SJMCLResult<ModBisectState> {
+  let mut bisect = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance
+      .mod_bisect
+      .clone()
+      .ok_or(InstanceError::ModBisectNotStarted)?
+  };
+  let mods_dir = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+
+  bisect.report(crashed);
+  update_mod_bisect(&app, &instance_id, &mods_dir, Some(bisect.clone())).await?;
+  Ok(bisect)
+}
+
+/// Abort the bisect and restore the mods enabled before it started.
+#[tauri::command]
+pub async fn stop_mod_bisect(app: AppHandle, instance_id: String) -> SJMCLResult<()> {
+  let mods_dir = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  update_mod_bisect(&app, &instance_id, &mods_dir, None).await
+}
+
+// apply the mods to test (or restore the original ones on stop), then persist the bisect state.
+async fn update_mod_bisect(
+  app: &AppHandle,
+  instance_id: &str,
+  mods_dir: &Path,
+  bisect: Option<ModBisectState>,
+) -> SJMCLResult<()> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let mut state = binding.lock()?;
+    let instance = state
+      .get_mut(instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    let enabled_mods = match (&bisect, &instance.mod_bisect) {
+      (Some(bisect), _) => bisect.testing.clone(),
+      (None, Some(previous)) => previous.original_enabled.clone(),
+      (None, None) => return Ok(()),
+    };
+    {
+      let _lock = RENAME_LOCK.lock().expect("Failed to acquire lock");
+      apply_enabled_mods(mods_dir, &enabled_mods.into_iter().collect())?;
+    }
+    instance.mod_bisect = bisect.filter(|b| !b.finished);
+    instance.clone()
+  };
+  instance.save_json_cfg().await?;
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn toggle_mod_pin(
+  app: AppHandle,
+  instance_id: String,
+  file_name: String,
+  pinned: bool,
+) -> SJMCLResult<()> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let mut state = binding.lock()?;
+    let instance = state
+      .get_mut(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance.pinned_mods.retain(|name| name != &file_name);
+    if pinned {
+      instance.pinned_mods.push(file_name);
+    }
+    instance.clone()
+  };
+  instance.save_json_cfg().await?;
+  Ok(())
+}
+
+#[tauri::command]
+pub fn toggle_mod_by_extension(file_path: PathBuf, enable: bool) -> SJMCLResult<()> {
+  let _lock = RENAME_LOCK.lock().expect("Failed to acquire lock");
+  if !file_path.is_file() {
+    return Err(InstanceError::FileNotFoundError.into());
+  }
+
+  let file_name = file_path
+    .file_name()
+    .unwrap_or_default()
+    .to_str()
+    .unwrap_or_default();
+
+  let new_name = if enable {
+    if let Some(captures) = RENAME_REGEX.captures(file_name) {
+      captures
+        .get(1)
+        .map(|m| m.as_str())
+        .unwrap_or(file_name)
+        .to_string()
+    } else {
+      file_name.to_string()
+    }
+  } else if RENAME_REGEX.is_match(file_name) {
+    format!("{}.disabled", file_name)
+  } else {
+    file_name.to_string()
+  };
+  let new_path = file_path.with_file_name(new_name);
+
+  if new_path != file_path {
+    fs::rename(&file_path, &new_path)?;
+  }
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn retrieve_world_details(
+  app: AppHandle,
+  instance_id: String,
+  world_name: String,
+) -> SJMCLResult<LevelData> {
+  let worlds_dir =
+    match get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Saves) {
+      Some(path) => path,
+      None => return Err(InstanceError::WorldNotExistError.into()),
+    };
+  let level_path = worlds_dir.join(world_name).join("level.dat");
+  if tokio::fs::metadata(&level_path).await.is_err() {
+    return Err(InstanceError::LevelNotExistError.into());
+  }
+  if let Ok(level_data) = load_level_data_from_nbt(&level_path).await {
+    Ok(level_data)
+  } else {
+    Err(InstanceError::LevelParseError.into())
+  }
+}
+
+#[tauri::command]
+pub fn create_launch_desktop_shortcut(
+  app: AppHandle,
+  instance_id: String,
+  icon_src: String,
+) -> SJMCLResult<()> {
+  let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+  let state = binding
+    .lock()
+    .map_err(|_| InstanceError::InstanceNotFoundByID)?;
+  let instance = state
+    .get(&instance_id)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+
+  let name = instance.name.clone();
+  let encoded_id = url::form_urlencoded::Serializer::new(String::new())
+    .append_pair("id", &instance.id)
+    .finish()
+    .replace("+", "%20");
+  let url = format!("sjmcl://launch?{}", encoded_id);
+
+  #[cfg(any(target_os = "windows", target_os = "linux"))]
+  let icon_path = {
+    use crate::instance::helpers::misc::create_instance_shortcut_icon;
+    create_instance_shortcut_icon(&app, instance, &icon_src).ok()
+  };
+  #[cfg(target_os = "macos")]
+  let icon_path = {
+    let _ = icon_src; // explicitly consume to avoid warning
+    None
+  };
+
+  create_url_shortcut(&app, name, url, icon_path)
+    .map_err(|_| InstanceError::ShortcutCreationFailed)?;
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn create_instance(
+  app: AppHandle,
+  directory: GameDirectory,
+  name: String,
+  description: String,
   icon_src: String,
   game: GameClientResourceInfo,
   mod_loader: ModLoaderResourceInfo,
   optifine: Option<OptiFineResourceInfo>,
+  liteloader: Option<ModLoaderResourceInfo>, // LiteLoader stacked on Forge
   modpack_path: Option<String>,
   mut is_install_fabric_api: Option<bool>,
   mut is_install_qf_api: Option<bool>,
@@ -1069,10 +2011,25 @@ pub async fn create_instance(
   // Guard removes version_path on any early return (errors), fix #1105 #1310
   let dir_guard = RemoveDirGuard::new(version_path.clone());
 
+  if let Some(info) = optifine.as_ref() {
+    check_optifine_compatibility(
+      &ModLoader {
+        loader_type: mod_loader.loader_type,
+        version: mod_loader.version.clone(),
+        ..Default::default()
+      },
+      info,
+    )?;
+  }
   let optifine_info = optifine.as_ref().map(|info| OptiFine {
     filename: info.filename.clone(),
     version: format!("{}_{}", info.r#type, info.patch),
-    status: ModLoaderStatus::NotDownloaded,
+    // loaded from the mods folder by OptiFabric, nothing left to install
+    status: if mod_loader.loader_type == ModLoaderType::Fabric {
+      ModLoaderStatus::Installed
+    } else {
+      ModLoaderStatus::NotDownloaded
+    },
   });
 
   // Create instance config
@@ -1085,7 +2042,10 @@ pub async fn create_instance(
       loader_type: mod_loader.loader_type,
       status: if matches!(
         mod_loader.loader_type,
-        ModLoaderType::Unknown | ModLoaderType::Fabric | ModLoaderType::Quilt
+        ModLoaderType::Unknown
+          | ModLoaderType::Fabric
+          | ModLoaderType::Quilt
+          | ModLoaderType::LiteLoader
       ) {
         ModLoaderStatus::Installed
       } else {
@@ -1103,6 +2063,9 @@ pub async fn create_instance(
     use_spec_game_config: false,
     spec_game_config: None,
     modpack_version: modpack_version.clone(),
+    pinned_mods: Vec::new(),
+    mod_sets: Vec::new(),
+    mod_bisect: None,
   };
 
   // Download version info
@@ -1138,11 +2101,12 @@ pub async fn create_instance(
 
     if let Err(err) = select_java_runtime(&app, None, &instance, client_java_version).await
       && err.0 == LaunchError::NoSuitableJava.to_string()
+      && let Some(recommended_java_version) =
+        get_recommended_java_version(&app, &instance, client_java_version).await
     {
-      let minimum_java_version = get_minimum_java_version_by_game(&app, &instance, false).await;
-      let minimum_java_version = minimum_java_version.to_string();
-      task_params.extend(build_mojang_java_download_params(&app, &minimum_java_version).await?);
-      java_version_to_download = Some(minimum_java_version);
+      let recommended_java_version = recommended_java_version.to_string();
+      task_params.extend(build_mojang_java_download_params(&app, &recommended_java_version).await?);
+      java_version_to_download = Some(recommended_java_version);
     }
   }
 
@@ -1210,22 +2174,67 @@ pub async fn create_instance(
     .await?;
   }
 
-  if let Some(info) = optifine.as_ref() {
-    download_optifine_installer(
+  if let Some(info) = liteloader.as_ref() {
+    if !matches!(
+      instance.mod_loader.loader_type,
+      ModLoaderType::Forge | ModLoaderType::LegacyForge
+    ) {
+      return Err(InstanceError::UnsupportedModLoader.into());
+    }
+    let liteloader = ModLoader {
+      loader_type: ModLoaderType::LiteLoader,
+      version: info.version.clone(),
+      status: ModLoaderStatus::Installed,
+      branch: None,
+    };
+    // the Forge patch is merged later, when its libraries are downloaded
+    install_liteloader_loader(
+      &app,
+      &priority_list,
       &instance.version,
-      info,
+      &liteloader,
       libraries_dir.to_path_buf(),
+      &mut version_info,
       &mut task_params,
     )
     .await?;
   }
 
+  if let Some(info) = optifine.as_ref() {
+    if instance.mod_loader.loader_type == ModLoaderType::Fabric {
+      download_optifine_as_mod(
+        &app,
+        &instance.version,
+        info,
+        mods_dir.to_path_buf(),
+        &mut task_params,
+      )
+      .await?;
+    } else {
+      download_optifine_installer(
+        &instance.version,
+        info,
+        libraries_dir.to_path_buf(),
+        &mut task_params,
+      )
+      .await?;
+    }
+  }
+
   // If modpack path is provided, install it
   if let Some(modpack_path) = modpack_path {
     let path = PathBuf::from(modpack_path);
     let file = fs::File::open(&path).map_err(|_| InstanceError::FileNotFoundError)?;
-    task_params.extend(get_download_params(&app, &file, &version_path).await?);
+    let modpack_params = get_download_params(&app, &file, &version_path).await?;
     extract_overrides(&file, &version_path)?;
+    save_modpack_record(
+      &file,
+      &version_path,
+      &modpack_params,
+      modpack_version.clone(),
+    )
+    .await?;
+    task_params.extend(modpack_params);
   }
 
   schedule_progressive_task_group(
@@ -1477,7 +2486,10 @@ pub async fn change_mod_loader(
     version: new_mod_loader.version.clone(),
     status: if matches!(
       new_mod_loader.loader_type,
-      ModLoaderType::Unknown | ModLoaderType::Fabric | ModLoaderType::Quilt
+      ModLoaderType::Unknown
+        | ModLoaderType::Fabric
+        | ModLoaderType::Quilt
+        | ModLoaderType::LiteLoader
     ) {
       ModLoaderStatus::Installed
     } else {
@@ -1587,29 +2599,53 @@ pub async fn change_optifine(
       .ok_or(InstanceError::InstanceNotFoundByID)?
       .clone()
   };
-  let subdirs = get_instance_subdir_paths(&app, &instance, &[&InstanceSubdirType::Libraries])
-    .ok_or(InstanceError::InstanceNotFoundByID)?;
-  let [libraries_dir] = subdirs.as_slice() else {
+  check_optifine_compatibility(&instance.mod_loader, &new_optifine)?;
+  let subdirs = get_instance_subdir_paths(
+    &app,
+    &instance,
+    &[&InstanceSubdirType::Libraries, &InstanceSubdirType::Mods],
+  )
+  .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let [libraries_dir, mods_dir] = subdirs.as_slice() else {
     return Err(InstanceError::InstanceNotFoundByID.into());
   };
+  let is_optifabric = instance.mod_loader.loader_type == ModLoaderType::Fabric;
+
+  let mut optifine_task_params: Vec<PTaskParam> = Vec::new();
+  if is_optifabric {
+    if let Some(old_optifine) = &instance.optifine {
+      remove_optifine_mod(mods_dir, old_optifine)?;
+    }
+    download_optifine_as_mod(
+      &app,
+      &instance.version,
+      &new_optifine,
+      mods_dir.to_path_buf(),
+      &mut optifine_task_params,
+    )
+    .await?;
+  } else {
+    download_optifine_installer(
+      &instance.version,
+      &new_optifine,
+      libraries_dir.to_path_buf(),
+      &mut optifine_task_params,
+    )
+    .await?;
+  }
 
   let optifine_info = OptiFine {
     filename: new_optifine.filename.clone(),
     version: format!("{}_{}", new_optifine.r#type, new_optifine.patch),
-    status: ModLoaderStatus::NotDownloaded,
+    status: if is_optifabric {
+      ModLoaderStatus::Installed
+    } else {
+      ModLoaderStatus::NotDownloaded
+    },
   };
 
   instance.optifine = Some(optifine_info);
 
-  let mut optifine_task_params: Vec<PTaskParam> = Vec::new();
-  download_optifine_installer(
-    &instance.version,
-    &new_optifine,
-    libraries_dir.to_path_buf(),
-    &mut optifine_task_params,
-  )
-  .await?;
-
   if !optifine_task_params.is_empty() {
     schedule_progressive_task_group(
       app.clone(),
@@ -1644,6 +2680,15 @@ pub async fn remove_optifine(app: AppHandle, instance_id: String) -> SJMCLResult
   let mut version_info: McClientInfo = load_json_async(&json_path).await?;
 
   remove_optifine_from_client_info(&mut version_info);
+  if instance.mod_loader.loader_type == ModLoaderType::Fabric
+    && let Some(optifine) = &instance.optifine
+  {
+    let subdirs = get_instance_subdir_paths(&app, &instance, &[&InstanceSubdirType::Mods])
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    if let Some(mods_dir) = subdirs.first() {
+      remove_optifine_mod(mods_dir, optifine)?;
+    }
+  }
   instance.optifine = None;
 
   save_json_async(&version_info, &json_path).await?;
@@ -1656,13 +2701,705 @@ pub async fn remove_optifine(app: AppHandle, instance_id: String) -> SJMCLResult
 }
 
 #[tauri::command]
-pub async fn retrieve_modpack_meta_info(
+pub async fn retrieve_client_patch_list(
   app: AppHandle,
-  path: String,
-) -> SJMCLResult<ModpackMetaInfo> {
-  let path = PathBuf::from(path);
-  let file = fs::File::open(&path).map_err(|_| InstanceError::FileNotFoundError)?;
-  ModpackMetaInfo::from_archive(&app, &file).await
+  instance_id: String,
+) -> SJMCLResult<Vec<ClientPatchInfo>> {
+  let version_info = retrieve_merged_client_info(app, instance_id).await?;
+  Ok(
+    version_info
+      .patches
+      .iter()
+      .map(ClientPatchInfo::from)
+      .collect(),
+  )
+}
+
+#[tauri::command]
+pub async fn retrieve_merged_client_info(
+  app: AppHandle,
+  instance_id: String,
+) -> SJMCLResult<McClientInfo> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let json_path = instance
+    .version_path
+    .join(format!("{}.json", instance.name));
+  load_json_async(&json_path).await
+}
+
+// download the libraries introduced by user patches
+async fn download_client_patch_libraries(
+  app: &AppHandle,
+  instance: &Instance,
+  version_info: &McClientInfo,
+) -> SJMCLResult<()> {
+  let priority_list = {
+    let launcher_config_state = app.state::<Mutex<LauncherConfig>>();
+    let launcher_config = launcher_config_state.lock()?;
+    get_source_priority_list(&launcher_config)
+  };
+  let subdirs = get_instance_subdir_paths(app, instance, &[&InstanceSubdirType::Libraries])
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let [libraries_dir] = subdirs.as_slice() else {
+    return Err(InstanceError::InstanceNotFoundByID.into());
+  };
+  let task_params =
+    get_invalid_library_files(priority_list[0], libraries_dir, version_info, false).await?;
+  if !task_params.is_empty() {
+    schedule_progressive_task_group(
+      app.clone(),
+      format!("client-patch-libraries?{}", instance.id),
+      task_params,
+      true,
+    )
+    .await?;
+  }
+  Ok(())
+}
+
+async fn update_client_patches<F>(app: &AppHandle, instance_id: &str, update: F) -> SJMCLResult<()>
+where
+  F: FnOnce(&mut McClientInfo) -> SJMCLResult<()>,
+{
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let json_path = instance
+    .version_path
+    .join(format!("{}.json", instance.name));
+  let mut version_info: McClientInfo = load_json_async(&json_path).await?;
+  update(&mut version_info)?;
+  save_json_async(&version_info, &json_path).await?;
+  download_client_patch_libraries(app, &instance, &version_info).await
+}
+
+/// Add a user version JSON patch (custom libraries, LWJGL replacements, main class, arguments) from a file or URL.
+#[tauri::command]
+pub async fn add_client_patch(
+  app: AppHandle,
+  instance_id: String,
+  source: String,
+) -> SJMCLResult<()> {
+  let patch = load_client_json_from_source(&app, &source).await?;
+  update_client_patches(&app, &instance_id, |version_info| {
+    add_patch_to_client_info(version_info, patch)
+  })
+  .await
+}
+
+#[tauri::command]
+pub async fn reorder_client_patches(
+  app: AppHandle,
+  instance_id: String,
+  patch_ids: Vec<String>,
+) -> SJMCLResult<()> {
+  update_client_patches(&app, &instance_id, |version_info| {
+    reorder_patches_in_client_info(version_info, &patch_ids)
+  })
+  .await
+}
+
+#[tauri::command]
+pub async fn remove_client_patch(
+  app: AppHandle,
+  instance_id: String,
+  patch_id: String,
+) -> SJMCLResult<()> {
+  update_client_patches(&app, &instance_id, |version_info| {
+    remove_patch_from_client_info(version_info, &patch_id)
+  })
+  .await
+}
+
+/// Install a third-party version JSON that `inheritsFrom` a vanilla version, from a file or URL.
+/// The vanilla version is installed as usual and the JSON is applied on top as a patch.
+#[tauri::command]
+pub async fn install_custom_version(
+  app: AppHandle,
+  directory: GameDirectory,
+  name: String,
+  source: String,
+) -> SJMCLResult<()> {
+  let custom_info = load_client_json_from_source(&app, &source).await?;
+  let parent_version = custom_info
+    .inherits_from
+    .clone()
+    .ok_or(InstanceError::ClientJsonParseError)?;
+  let game = fetch_game_version_specific(app.clone(), parent_version).await?;
+  let (_, _, loader_type, _) = libraries_to_info(&custom_info).await;
+
+  create_instance(
+    app.clone(),
+    directory.clone(),
+    name.clone(),
+    String::new(),
+    loader_type.to_icon_path().to_string(),
+    game,
+    ModLoaderResourceInfo::default(),
+    None,
+    None,
+    None,
+    Some(false),
+    Some(false),
+    None,
+  )
+  .await?;
+
+  let version_path = directory.dir.join("versions").join(&name);
+  let json_path = version_path.join(format!("{}.json", name));
+  let mut version_info: McClientInfo = load_json_async(&json_path).await?;
+  add_patch_to_client_info(&mut version_info, custom_info)?;
+  save_json_async(&version_info, &json_path).await?;
+  // list the new instance with the patch applied
+  refresh_and_update_instances(&app, false).await;
+
+  let instance = Instance {
+    id: format!("{}:{}", directory.name, name),
+    name,
+    version_path,
+    ..Default::default()
+  };
+  download_client_patch_libraries(&app, &instance, &version_info).await
+}
+
+// the name is used as the version folder and the cached zip file name
+fn check_version_zip_name(name: &str) -> SJMCLResult<()> {
+  if name.is_empty() || !sanitize_filename::is_sanitized(name) {
+    return Err(InstanceError::InvalidNameError.into());
+  }
+  Ok(())
+}
+
+fn get_version_zip_cache_path(app: &AppHandle, name: &str) -> SJMCLResult<PathBuf> {
+  let launcher_config_state = app.state::<Mutex<LauncherConfig>>();
+  let launcher_config = launcher_config_state.lock()?;
+  Ok(
+    launcher_config
+      .download
+      .cache
+      .directory
+      .join(format!("{}.zip", name)),
+  )
+}
+
+/// Install an experimental version distributed as a zip with the client JAR and a full version JSON,
+/// from a file or URL (e.g. entries of the extra version manifests).
+/// Returns `true` if the zip download was scheduled, then call `finish_version_zip_install` once it succeeds.
+#[tauri::command]
+pub async fn install_version_from_zip(
+  app: AppHandle,
+  directory: GameDirectory,
+  name: String,
+  source: String,
+) -> SJMCLResult<bool> {
+  check_version_zip_name(&name)?;
+  let version_path = directory.dir.join("versions").join(&name);
+  if version_path.exists() {
+    return Err(InstanceError::ConflictNameError.into());
+  }
+
+  match Url::parse(&source) {
+    Ok(url) if matches!(url.scheme(), "http" | "https") => {
+      let zip_path = get_version_zip_cache_path(&app, &name)?;
+      schedule_progressive_task_group(
+        app,
+        format!("version-zip?{}", name),
+        vec![PTaskParam::Download(DownloadParam {
+          src: url,
+          dest: zip_path,
+          filename: None,
+          sha1: None,
+        })],
+        true,
+      )
+      .await?;
+      Ok(true)
+    }
+    _ => {
+      install_version_from_zip_file(app, directory, name, PathBuf::from(source)).await?;
+      Ok(false)
+    }
+  }
+}
+
+/// Install the version zip downloaded by `install_version_from_zip`, then remove it from the cache.
+#[tauri::command]
+pub async fn finish_version_zip_install(
+  app: AppHandle,
+  directory: GameDirectory,
+  name: String,
+) -> SJMCLResult<()> {
+  check_version_zip_name(&name)?;
+  let zip_path = get_version_zip_cache_path(&app, &name)?;
+  let result = install_version_from_zip_file(app, directory, name, zip_path.clone()).await;
+  let _ = fs::remove_file(&zip_path);
+  result
+}
+
+async fn install_version_from_zip_file(
+  app: AppHandle,
+  directory: GameDirectory,
+  name: String,
+  zip_path: PathBuf,
+) -> SJMCLResult<()> {
+  let version_path = directory.dir.join("versions").join(&name);
+  if version_path.exists() {
+    return Err(InstanceError::ConflictNameError.into());
+  }
+
+  let zip_file = fs::File::open(&zip_path).map_err(|_| InstanceError::FileNotFoundError)?;
+  let mut archive = ZipArchive::new(zip_file).map_err(|_| InstanceError::ZipFileProcessFailed)?;
+  let (mut version_info, jar_bytes, jar_game_version) = load_version_from_zip(&mut archive)?;
+  // a zip is expected to carry a complete version, use `install_custom_version` for patches
+  if version_info.inherits_from.is_some() {
+    return Err(InstanceError::ClientJsonParseError.into());
+  }
+  let game_version = jar_game_version.unwrap_or_else(|| version_info.id.clone());
+
+  let dir_guard = RemoveDirGuard::new(version_path.clone());
+  fs::create_dir_all(&version_path).map_err(|_| InstanceError::FolderCreationFailed)?;
+  fs::write(version_path.join(format!("{}.jar", name)), jar_bytes)
+    .map_err(|_| InstanceError::FileCreationFailed)?;
+
+  version_info.id = name.clone();
+  version_info.jar = Some(name.clone());
+  let mut game_patch = version_info.clone();
+  game_patch.id = "game".to_string();
+  game_patch.version = Some(game_version.clone());
+  game_patch.priority = Some(0);
+  game_patch.patches.clear();
+  version_info.patches = vec![game_patch];
+
+  let instance = Instance {
+    id: format!("{}:{}", directory.name, name),
+    name: name.clone(),
+    version: game_version,
+    version_path: version_path.clone(),
+    ..Default::default()
+  };
+
+  let priority_list = {
+    let launcher_config_state = app.state::<Mutex<LauncherConfig>>();
+    let launcher_config = launcher_config_state.lock()?;
+    get_source_priority_list(&launcher_config)
+  };
+  let subdirs = get_instance_subdir_paths(
+    &app,
+    &instance,
+    &[&InstanceSubdirType::Libraries, &InstanceSubdirType::Assets],
+  )
+  .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let [libraries_dir, assets_dir] = subdirs.as_slice() else {
+    return Err(InstanceError::InstanceNotFoundByID.into());
+  };
+
+  replace_native_libraries(&app, &mut version_info, &instance)
+    .await
+    .map_err(|_| InstanceError::ClientJsonParseError)?;
+
+  let mut task_params =
+    get_invalid_library_files(priority_list[0], libraries_dir, &version_info, false).await?;
+  task_params
+    .extend(get_invalid_assets(&app, &version_info, priority_list[0], assets_dir, false).await?);
+  schedule_progressive_task_group(
+    app.clone(),
+    format!("game-client?{}", name),
+    task_params,
+    true,
+  )
+  .await?;
+
+  save_json_async(&version_info, &version_path.join(format!("{}.json", name))).await?;
+  instance
+    .save_json_cfg()
+    .await
+    .map_err(|_| InstanceError::FileCreationFailed)?;
+
+  dir_guard.commit();
+  refresh_and_update_instances(&app, false).await;
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn retrieve_server_instance_list(
+  app: AppHandle,
+) -> SJMCLResult<Vec<ServerInstanceSummary>> {
+  refresh_and_update_server_instances(&app).await; // firstly refresh and update
+  let servers = {
+    let binding = app.state::<Mutex<HashMap<String, ServerInstance>>>();
+    binding.lock()?.clone()
+  };
+
+  let mut summary_list = Vec::new();
+  for server in servers.into_values() {
+    summary_list.push(ServerInstanceSummary {
+      eula_accepted: is_eula_accepted(&server.server_path).await,
+      launching_id: get_running_server_launching_id(&app, &server.id),
+      id: server.id,
+      name: server.name,
+      description: server.description,
+      version: server.version,
+      server_path: server.server_path,
+      mod_loader: server.mod_loader,
+    });
+  }
+  summary_list.sort_by_key(|server| server.name.to_lowercase());
+  Ok(summary_list)
+}
+
+#[tauri::command]
+pub async fn create_server_instance(
+  app: AppHandle,
+  directory: GameDirectory,
+  name: String,
+  description: String,
+  game: GameClientResourceInfo,
+  mod_loader: ModLoaderResourceInfo,
+) -> SJMCLResult<()> {
+  let version_info = {
+    let client = app.state::<reqwest::Client>();
+    client
+      .get(&game.url)
+      .send()
+      .await
+      .map_err(|_| InstanceError::NetworkError)?
+      .json::<McClientInfo>()
+      .await
+      .map_err(|_| InstanceError::ClientJsonParseError)?
+  };
+
+  install_server_instance(
+    &app,
+    &directory,
+    &name,
+    description,
+    ModLoader {
+      loader_type: mod_loader.loader_type,
+      version: mod_loader.version,
+      branch: mod_loader.branch,
+      ..Default::default()
+    },
+    &version_info,
+    &game.id,
+    None,
+  )
+  .await?;
+  Ok(())
+}
+
+/// Run the Forge or NeoForge installer once its download finished.
+#[tauri::command]
+pub async fn finish_server_instance_install(app: AppHandle, server_id: String) -> SJMCLResult<()> {
+  let mut server = get_server_instance_by_id(&app, &server_id)?;
+  match server.mod_loader.status {
+    ModLoaderStatus::Installing => return Err(InstanceError::InstallationDuplicated.into()),
+    ModLoaderStatus::Downloading => {}
+    _ => return Ok(()),
+  }
+
+  let update_status = |status: ModLoaderStatus| -> SJMCLResult<()> {
+    let binding = app.state::<Mutex<HashMap<String, ServerInstance>>>();
+    let mut state = binding.lock()?;
+    if let Some(server) = state.get_mut(&server_id) {
+      server.mod_loader.status = status;
+    }
+    Ok(())
+  };
+  update_status(ModLoaderStatus::Installing)?;
+
+  match run_server_loader_installer(&app, &server).await {
+    Ok(launch_args) => {
+      server.launch_args = launch_args;
+      server.mod_loader.status = ModLoaderStatus::Installed;
+    }
+    Err(e) => {
+      server.mod_loader.status = ModLoaderStatus::DownloadFailed;
+      server.save_json_cfg().await?;
+      update_status(ModLoaderStatus::DownloadFailed)?;
+      return Err(e);
+    }
+  }
+  server.save_json_cfg().await?;
+
+  let binding = app.state::<Mutex<HashMap<String, ServerInstance>>>();
+  let mut state = binding.lock()?;
+  state.insert(server_id, server);
+  Ok(())
+}
+
+#[tauri::command]
+pub fn delete_server_instance(app: AppHandle, server_id: String) -> SJMCLResult<()> {
+  if get_running_server_launching_id(&app, &server_id).is_some() {
+    return Err(InstanceError::ServerAlreadyRunning.into());
+  }
+  let server = get_server_instance_by_id(&app, &server_id)?;
+  if server.server_path.exists() {
+    fs::remove_dir_all(&server.server_path)?;
+  }
+
+  let binding = app.state::<Mutex<HashMap<String, ServerInstance>>>();
+  let mut state = binding.lock()?;
+  state.remove(&server_id);
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn accept_server_eula(app: AppHandle, server_id: String) -> SJMCLResult<()> {
+  let server = get_server_instance_by_id(&app, &server_id)?;
+  accept_eula(&server.server_path)
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn retrieve_server_properties(
+  app: AppHandle,
+  server_id: String,
+) -> SJMCLResult<ServerProperties> {
+  let server = get_server_instance_by_id(&app, &server_id)?;
+  let properties = OptionsTxt::load_properties(&get_server_properties_path(&server.server_path))
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  Ok(ServerProperties::from(&properties))
+}
+
+#[tauri::command]
+pub async fn update_server_properties(
+  app: AppHandle,
+  server_id: String,
+  server_properties: ServerProperties,
+) -> SJMCLResult<()> {
+  let server = get_server_instance_by_id(&app, &server_id)?;
+  let properties_path = get_server_properties_path(&server.server_path);
+  let mut properties = OptionsTxt::load_properties(&properties_path)
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  server_properties.apply_to(&mut properties);
+  properties
+    .save(&properties_path)
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  Ok(())
+}
+
+/// Copy the mods of a client instance to a server instance, returns the skipped client-only mods.
+#[tauri::command]
+pub async fn copy_mods_to_server_instance(
+  app: AppHandle,
+  instance_id: String,
+  server_id: String,
+) -> SJMCLResult<Vec<String>> {
+  let server = get_server_instance_by_id(&app, &server_id)?;
+  let mods_dir = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+
+  copy_mods_to_server(&app, &mods_dir, &server.server_path.join("mods")).await
+}
+
+/// Create a local test server with the same game version and mod loader as a client instance,
+/// returns the server id. An existing paired server is reused.
+#[tauri::command]
+pub async fn create_lan_test_server(app: AppHandle, instance_id: String) -> SJMCLResult<String> {
+  refresh_and_update_server_instances(&app).await;
+  if let Some(server) = get_paired_server_instance(&app, &instance_id) {
+    return Ok(server.id);
+  }
+
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let directory = {
+    let binding = app.state::<Mutex<LauncherConfig>>();
+    let config = binding.lock()?;
+    config
+      .local_game_directories
+      .iter()
+      .find(|dir| instance.version_path.starts_with(&dir.dir))
+      .cloned()
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+  };
+  let client_info_dir = instance
+    .version_path
+    .join(format!("{}.json", instance.name));
+  let client_info = load_json_async::<McClientInfo>(&client_info_dir).await?;
+
+  let mut name = format!("{}-server", instance.name);
+  let mut index = 1;
+  while directory
+    .dir
+    .join(SERVER_INSTANCES_DIR_NAME)
+    .join(&name)
+    .exists()
+  {
+    index += 1;
+    name = format!("{}-server-{}", instance.name, index);
+  }
+
+  // installed Forge and NeoForge record the patch id, e.g. "1.20.1-forge-47.2.0"
+  let loader_version =
+    normalize_mod_loader_install_version(&instance.mod_loader, &instance.version);
+
+  let server = install_server_instance(
+    &app,
+    &directory,
+    &name,
+    instance.description.clone(),
+    ModLoader {
+      loader_type: instance.mod_loader.loader_type,
+      version: loader_version,
+      branch: instance.mod_loader.branch.clone(),
+      ..Default::default()
+    },
+    &client_info,
+    &instance.version,
+    Some(instance_id),
+  )
+  .await?;
+  Ok(server.id)
+}
+
+#[tauri::command]
+pub async fn retrieve_modpack_meta_info(
+  app: AppHandle,
+  path: String,
+) -> SJMCLResult<ModpackMetaInfo> {
+  let path = PathBuf::from(path);
+  let file = fs::File::open(&path).map_err(|_| InstanceError::FileNotFoundError)?;
+  ModpackMetaInfo::from_archive(&app, &file).await
+}
+
+/// Update the modpack of an instance in place from a newer version of the same pack,
+/// keeping the files added or changed by the user. `old_path` is the installed version of the
+/// pack, used for instances installed before their files were recorded.
+/// If downloads are scheduled, call `finish_instance_modpack_update` after they succeed.
+#[tauri::command]
+pub async fn update_instance_modpack(
+  app: AppHandle,
+  instance_id: String,
+  path: String,
+  old_path: Option<String>,
+) -> SJMCLResult<ModpackUpdateReport> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let file = fs::File::open(PathBuf::from(path)).map_err(|_| InstanceError::FileNotFoundError)?;
+  let meta_info = ModpackMetaInfo::from_archive(&app, &file).await?;
+
+  // a different game version or loader needs a new instance
+  let loader_type = meta_info
+    .mod_loader
+    .as_ref()
+    .map_or(ModLoaderType::Unknown, |loader| loader.loader_type);
+  if meta_info.client_version != instance.version || loader_type != instance.mod_loader.loader_type
+  {
+    return Err(InstanceError::ModpackUpdateMismatch.into());
+  }
+
+  let version_path = instance.version_path.clone();
+  let mut old_record =
+    load_json_async::<ModpackRecord>(&version_path.join(MODPACK_RECORD_FILE_NAME))
+      .await
+      .ok();
+  if old_record.is_none()
+    && let Some(old_path) = old_path
+  {
+    let old_file =
+      fs::File::open(PathBuf::from(old_path)).map_err(|_| InstanceError::FileNotFoundError)?;
+    let old_params = get_download_params(&app, &old_file, &version_path).await?;
+    old_record = Some(build_modpack_record(
+      &old_file,
+      &version_path,
+      &old_params,
+      instance.modpack_version.clone(),
+    )?);
+    save_modpack_base_files(&old_file, &version_path, &list_overrides(&old_file)?)?;
+  }
+
+  let download_params = get_download_params(&app, &file, &version_path).await?;
+  let mut plan = plan_modpack_update(
+    &file,
+    &version_path,
+    old_record.as_ref(),
+    download_params,
+    meta_info.version.clone(),
+  )?;
+  apply_modpack_update(&file, &version_path, &plan).await?;
+
+  if plan.downloads.is_empty() {
+    finish_instance_modpack_update(app.clone(), instance_id).await?;
+  } else {
+    // dropped files are removed by `finish_instance_modpack_update` once the downloads succeed
+    schedule_progressive_task_group(
+      app.clone(),
+      format!("modpack-update?{}", instance.name),
+      std::mem::take(&mut plan.downloads),
+      true,
+    )
+    .await?;
+  }
+
+  plan.report.new_mod_loader_version = meta_info
+    .mod_loader
+    .map(|loader| loader.version)
+    .filter(|version| *version != instance.mod_loader.version);
+
+  Ok(plan.report)
+}
+
+/// Remove the files dropped by the new pack and record the update.
+#[tauri::command]
+pub async fn finish_instance_modpack_update(
+  app: AppHandle,
+  instance_id: String,
+) -> SJMCLResult<()> {
+  let version_path = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .version_path
+      .clone()
+  };
+  let modpack_version = finish_modpack_update(&version_path).await?;
+
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let mut state = binding.lock()?;
+    let instance = state
+      .get_mut(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance.modpack_version = modpack_version;
+    instance.clone()
+  };
+  instance.save_json_cfg().await?;
+  Ok(())
 }
 
 #[tauri::command]
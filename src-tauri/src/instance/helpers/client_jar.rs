@@ -3,9 +3,13 @@
 use cafebabe::constant_pool::{ConstantPoolItem, LiteralConstant};
 use cafebabe::parse_class;
 use serde_json::Value;
-use std::io::{Read, Seek};
+use sjmcl_types::error::SJMCLResult;
+use std::io::{Cursor, Read, Seek};
 use zip::ZipArchive;
 
+use crate::instance::helpers::client_json::McClientInfo;
+use crate::instance::models::misc::InstanceError;
+
 // ref: HMCL org.jackhuang.hmcl.game.GameVersion
 
 fn from_json<R: Read + Seek>(jar: &mut ZipArchive<R>) -> Option<String> {
@@ -76,6 +80,69 @@ pub fn load_game_version_from_jar<R: Read + Seek>(jar: &mut ZipArchive<R>) -> Op
   None
 }
 
+/// Read the network protocol version from `version.json` (only exists since 18w47b).
+pub fn load_protocol_version_from_jar<R: Read + Seek>(jar: &mut ZipArchive<R>) -> Option<i64> {
+  let file = jar.by_name("version.json").ok()?;
+  let json_value: Value = serde_json::from_reader(file).ok()?;
+  json_value.get("protocol_version").and_then(Value::as_i64)
+}
+
+/// Read a version zip (e.g. Combat Test builds) holding a client JAR and its version JSON,
+/// returns the JSON, the JAR content and the game version identified from the JAR.
+pub fn load_version_from_zip<R: Read + Seek>(
+  zip: &mut ZipArchive<R>,
+) -> SJMCLResult<(McClientInfo, Vec<u8>, Option<String>)> {
+  let file_names = zip
+    .file_names()
+    .filter(|name| !name.starts_with("__MACOSX/"))
+    .map(String::from)
+    .collect::<Vec<_>>();
+  let jar_names = file_names
+    .iter()
+    .filter(|name| name.ends_with(".jar"))
+    .collect::<Vec<_>>();
+
+  // the client JAR is the one the version JSON names (`jar`, or else its `id`),
+  // zips may carry other JARs such as bundled mods or libraries
+  let mut matched = None;
+  for json_name in file_names.iter().filter(|name| name.ends_with(".json")) {
+    let Ok(file) = zip.by_name(json_name) else {
+      continue;
+    };
+    let Ok(client_info) = serde_json::from_reader::<_, McClientInfo>(file) else {
+      continue;
+    };
+    if client_info.id.is_empty() || client_info.main_class.is_none() {
+      continue;
+    }
+    let jar_file_name = format!(
+      "{}.jar",
+      client_info.jar.as_ref().unwrap_or(&client_info.id)
+    );
+    let jar_name = jar_names
+      .iter()
+      .find(|name| name.rsplit('/').next() == Some(jar_file_name.as_str()))
+      // a lone JAR is taken even if it was renamed
+      .or(match jar_names.as_slice() {
+        [jar_name] => Some(jar_name),
+        _ => None,
+      });
+    if let Some(jar_name) = jar_name {
+      matched = Some((client_info, (*jar_name).clone()));
+      break;
+    }
+  }
+  let (client_info, jar_name) = matched.ok_or(InstanceError::ClientJsonParseError)?;
+
+  let mut jar_bytes = Vec::new();
+  zip.by_name(&jar_name)?.read_to_end(&mut jar_bytes)?;
+
+  let game_version = ZipArchive::new(Cursor::new(&jar_bytes))
+    .ok()
+    .and_then(|mut jar| load_game_version_from_jar(&mut jar));
+  Ok((client_info, jar_bytes, game_version))
+}
+
 // pub fn load_image_from_jar<R: Read + Seek>(jar: &mut ZipArchive<R>) -> SJMCLResult<String> {
 //   if let Ok(mut file) = jar.by_name("pack.png") {
 //     let mut buffer = Vec::new();
@@ -322,8 +322,12 @@ pub fn patches_to_info(
     if game_version.is_none() && patch.id == "game" {
       game_version = patch.version.clone();
     }
-    if loader_type == ModLoaderType::Unknown
-      && let Ok(found_loader_type) = ModLoaderType::from_str(&patch.id)
+    // LiteLoader stacked on Forge is reported as Forge
+    if matches!(
+      loader_type,
+      ModLoaderType::Unknown | ModLoaderType::LiteLoader
+    ) && let Ok(found_loader_type) = ModLoaderType::from_str(&patch.id)
+      && found_loader_type != ModLoaderType::Unknown
     {
       loader_type = found_loader_type;
       loader_version = patch.version.clone();
@@ -333,6 +337,7 @@ pub fn patches_to_info(
         patch: "".to_string(),
         filename: "".to_string(),
         r#type: patch.version.clone().unwrap_or_default(),
+        forge: None,
       });
     }
   }
@@ -384,13 +389,28 @@ pub fn reset_fields_from_patches(client_info: &mut McClientInfo) {
 
   client_info.libraries.clear();
   let mut library_names = HashSet::new();
-  for patch in &patches {
+  for patch in patches.iter().filter(|patch| is_builtin_patch(&patch.id)) {
     for library in &patch.libraries {
       if library_names.insert(library.name.clone()) {
         client_info.libraries.push(library.clone());
       }
     }
   }
+  // user patches replace libraries of the same artifact, e.g. LWJGL replacements
+  let mut custom_patches = patches
+    .iter()
+    .filter(|patch| !is_builtin_patch(&patch.id))
+    .collect::<Vec<_>>();
+  custom_patches.sort_by_key(|patch| patch.priority.unwrap_or(i64::MIN));
+  for patch in custom_patches {
+    for library in &patch.libraries {
+      let key = get_library_override_key(&library.name);
+      client_info
+        .libraries
+        .retain(|existing| get_library_override_key(&existing.name) != key);
+      client_info.libraries.push(library.clone());
+    }
+  }
 
   client_info.main_class = base.main_class.clone();
   client_info.arguments = base.arguments.clone();
@@ -399,11 +419,11 @@ pub fn reset_fields_from_patches(client_info: &mut McClientInfo) {
   let mut patch_refs = patches.iter().skip(1).collect::<Vec<_>>();
   patch_refs.sort_by_key(|patch| patch.priority.unwrap_or(i64::MIN));
 
-  // Non-OptiFine `minecraftArguments` replace the inherited template; OptiFine arguments are incremental (ref: #1897).
+  // Loader `minecraftArguments` replace the inherited template; OptiFine and LiteLoader tweakers are incremental (ref: #1897).
   if let Some(minecraft_arguments) = patch_refs
     .iter()
     .rev()
-    .filter(|patch| patch.id != "optifine")
+    .filter(|patch| !is_incremental_patch(&patch.id))
     .filter_map(|patch| patch.minecraft_arguments.as_ref())
     .find(|arguments| !arguments.is_empty())
   {
@@ -444,7 +464,7 @@ pub fn reset_fields_from_patches(client_info: &mut McClientInfo) {
         continue;
       }
 
-      if patch.id != "optifine" {
+      if !is_incremental_patch(&patch.id) {
         continue;
       }
 
@@ -462,6 +482,30 @@ pub fn reset_fields_from_patches(client_info: &mut McClientInfo) {
   }
 }
 
+/// Patches created by the launcher: the vanilla game, mod loaders and OptiFine.
+pub fn is_builtin_patch(patch_id: &str) -> bool {
+  matches!(patch_id, "game" | "optifine") || ModLoaderType::from_str(patch_id).is_ok()
+}
+
+// group, artifact, classifier and extension, without the version
+fn get_library_override_key(name: &str) -> (String, String, Option<String>, Option<String>) {
+  let (coord, extension) = match name.split_once('@') {
+    Some((coord, extension)) => (coord, Some(extension.to_string())),
+    None => (name, None),
+  };
+  let parts = coord.split(':').collect::<Vec<_>>();
+  (
+    parts.first().unwrap_or(&"").to_string(),
+    parts.get(1).unwrap_or(&"").to_string(),
+    parts.get(3).map(|classifier| classifier.to_string()),
+    extension,
+  )
+}
+
+fn is_incremental_patch(patch_id: &str) -> bool {
+  matches!(patch_id, "optifine" | "liteloader")
+}
+
 fn normalize_optifine_tweaker(arguments: &mut LaunchArgumentTemplate, has_forge_patch: bool) {
   let tweaker = if has_forge_patch {
     "optifine.OptiFineForgeTweaker"
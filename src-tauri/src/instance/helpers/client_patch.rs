@@ -0,0 +1,144 @@
+use sjmcl_types::error::SJMCLResult;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use url::Url;
+
+use crate::instance::helpers::client_json::{
+  McClientInfo, is_builtin_patch, reset_fields_from_patches,
+};
+use crate::instance::models::misc::{ClientPatchInfo, InstanceError};
+
+impl From<&McClientInfo> for ClientPatchInfo {
+  fn from(patch: &McClientInfo) -> Self {
+    Self {
+      id: patch.id.clone(),
+      version: patch.version.clone(),
+      priority: patch.priority,
+      main_class: patch.main_class.clone(),
+      libraries: patch
+        .libraries
+        .iter()
+        .map(|library| library.name.clone())
+        .collect(),
+      is_builtin: is_builtin_patch(&patch.id),
+    }
+  }
+}
+
+/// Load a version JSON from a local path or an http(s) URL.
+pub async fn load_client_json_from_source(
+  app: &AppHandle,
+  source: &str,
+) -> SJMCLResult<McClientInfo> {
+  if let Ok(url) = Url::parse(source)
+    && matches!(url.scheme(), "http" | "https")
+  {
+    let client = app.state::<reqwest::Client>();
+    return client
+      .get(url)
+      .send()
+      .await
+      .map_err(|_| InstanceError::NetworkError)?
+      .json::<McClientInfo>()
+      .await
+      .map_err(|_| InstanceError::ClientJsonParseError.into());
+  }
+  let content = tokio::fs::read_to_string(Path::new(source))
+    .await
+    .map_err(|_| InstanceError::FileNotFoundError)?;
+  serde_json::from_str(&content).map_err(|_| InstanceError::ClientJsonParseError.into())
+}
+
+/// Append a user patch, by default it is applied after all existing patches.
+pub fn add_patch_to_client_info(
+  client_info: &mut McClientInfo,
+  mut patch: McClientInfo,
+) -> SJMCLResult<()> {
+  if patch.id.is_empty() || is_builtin_patch(&patch.id) {
+    return Err(InstanceError::ClientPatchNotEditable.into());
+  }
+  if client_info.patches.is_empty() {
+    return Err(InstanceError::NotSupportChangeModLoader.into());
+  }
+  if client_info.patches.iter().any(|p| p.id == patch.id) {
+    return Err(InstanceError::ClientPatchConflict.into());
+  }
+  if patch.priority.is_none() {
+    let max_priority = client_info
+      .patches
+      .iter()
+      .filter_map(|p| p.priority)
+      .max()
+      .unwrap_or(0);
+    patch.priority = Some(max_priority + 1);
+  }
+  // a patch never inherits on its own, the base game is the first patch
+  patch.inherits_from = None;
+  patch.patches.clear();
+  client_info.patches.push(patch);
+  reset_fields_from_patches(client_info);
+  Ok(())
+}
+
+pub fn remove_patch_from_client_info(
+  client_info: &mut McClientInfo,
+  patch_id: &str,
+) -> SJMCLResult<()> {
+  if is_builtin_patch(patch_id) {
+    return Err(InstanceError::ClientPatchNotEditable.into());
+  }
+  let patch_count = client_info.patches.len();
+  client_info.patches.retain(|patch| patch.id != patch_id);
+  if client_info.patches.len() == patch_count {
+    return Err(InstanceError::ClientPatchNotFound.into());
+  }
+  reset_fields_from_patches(client_info);
+  Ok(())
+}
+
+/// Reorder the user patches, the existing priorities of user patches are reassigned in the new order.
+/// Builtin patches (game, loaders, OptiFine) keep their place.
+pub fn reorder_patches_in_client_info(
+  client_info: &mut McClientInfo,
+  patch_ids: &[String],
+) -> SJMCLResult<()> {
+  let mut current_ids = client_info
+    .patches
+    .iter()
+    .filter(|patch| !is_builtin_patch(&patch.id))
+    .map(|patch| patch.id.clone())
+    .collect::<Vec<_>>();
+  let mut requested_ids = patch_ids.to_vec();
+  current_ids.sort();
+  requested_ids.sort();
+  if current_ids != requested_ids {
+    return Err(InstanceError::ClientPatchNotFound.into());
+  }
+
+  let mut priorities = client_info
+    .patches
+    .iter()
+    .filter(|patch| !is_builtin_patch(&patch.id))
+    .map(|patch| patch.priority.unwrap_or(0))
+    .collect::<Vec<_>>();
+  priorities.sort();
+  // patches sharing a priority would be applied in an unspecified order
+  for index in 1..priorities.len() {
+    if priorities[index] <= priorities[index - 1] {
+      priorities[index] = priorities[index - 1] + 1;
+    }
+  }
+
+  for patch in client_info
+    .patches
+    .iter_mut()
+    .filter(|patch| !is_builtin_patch(&patch.id))
+  {
+    if let Some(position) = patch_ids.iter().position(|id| id == &patch.id) {
+      patch.priority = Some(priorities[position]);
+    }
+  }
+  reset_fields_from_patches(client_info);
+  Ok(())
+}
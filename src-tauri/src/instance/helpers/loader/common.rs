@@ -9,6 +9,7 @@ use zip::ZipArchive;
 use crate::instance::helpers::client_json::{LibrariesValue, McClientInfo};
 use crate::instance::helpers::loader::fabric::install_fabric_loader;
 use crate::instance::helpers::loader::forge::{InstallProfile, install_forge_loader};
+use crate::instance::helpers::loader::liteloader::install_liteloader_loader;
 use crate::instance::helpers::loader::neoforge::install_neoforge_loader;
 use crate::instance::helpers::loader::quilt::install_quilt_loader;
 use crate::instance::helpers::misc::get_instance_game_config;
@@ -78,6 +79,18 @@ pub async fn install_mod_loader(
     ModLoaderType::NeoForge => {
       install_neoforge_loader(priority, loader, lib_dir, task_params).await
     }
+    ModLoaderType::LiteLoader => {
+      install_liteloader_loader(
+        &app,
+        priority,
+        game_version,
+        loader,
+        lib_dir,
+        client_info,
+        task_params,
+      )
+      .await
+    }
     _ => Err(InstanceError::UnsupportedModLoader.into()),
   }
 }
@@ -0,0 +1,102 @@
+use sjmcl_types::error::SJMCLResult;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use url::Url;
+
+use crate::instance::helpers::client_json::{ArgumentsItem, LaunchArgumentTemplate, McClientInfo};
+use crate::instance::helpers::loader::common::add_library_entry;
+use crate::instance::models::misc::ModLoader;
+use crate::launch::helpers::file_validator::convert_library_name_to_path;
+use crate::resource::helpers::loader_meta::liteloader::get_liteloader_remote_info;
+use crate::resource::helpers::misc::{convert_url_to_target_source, get_download_api};
+use crate::resource::models::{ResourceType, SourceType};
+use crate::tasks::PTaskParam;
+use crate::tasks::download::DownloadParam;
+
+const LAUNCHWRAPPER_MAIN_CLASS: &str = "net.minecraft.launchwrapper.Launch";
+
+/// Install LiteLoader standalone or on top of (legacy) Forge, LiteLoader needs no processors.
+/// ref: https://github.com/HMCL-dev/HMCL/blob/main/HMCLCore/src/main/java/org/jackhuang/hmcl/download/liteloader/LiteLoaderInstallTask.java
+pub async fn install_liteloader_loader(
+  app: &AppHandle,
+  priority: &[SourceType],
+  game_version: &str,
+  loader: &ModLoader,
+  lib_dir: PathBuf,
+  client_info: &mut McClientInfo,
+  task_params: &mut Vec<PTaskParam>,
+) -> SJMCLResult<()> {
+  let remote = get_liteloader_remote_info(app, priority, game_version, &loader.version).await?;
+
+  let liteloader_coord = format!("com.mumfrey:liteloader:{}", remote.version);
+  let mut new_patch = McClientInfo {
+    id: "liteloader".to_string(),
+    version: Some(remote.version.clone()),
+    priority: Some(60000),
+    main_class: Some(LAUNCHWRAPPER_MAIN_CLASS.to_string()),
+    ..Default::default()
+  };
+
+  add_library_entry(&mut client_info.libraries, &liteloader_coord, None)?;
+  add_library_entry(&mut new_patch.libraries, &liteloader_coord, None)?;
+  task_params.push(PTaskParam::Download(DownloadParam {
+    src: remote.jar_url.clone(),
+    dest: lib_dir.join(convert_library_name_to_path(&liteloader_coord, None)?),
+    filename: None,
+    sha1: None,
+  }));
+
+  for lib in remote.libraries.iter() {
+    add_library_entry(&mut client_info.libraries, &lib.name, None)?;
+    add_library_entry(&mut new_patch.libraries, &lib.name, None)?;
+
+    let url_root = match &lib.url {
+      Some(url) => Url::parse(url)?,
+      None => get_download_api(priority[0], ResourceType::Libraries)?,
+    };
+    let rel = convert_library_name_to_path(&lib.name, None)?;
+    task_params.push(PTaskParam::Download(DownloadParam {
+      src: convert_url_to_target_source(
+        &url_root.join(&rel)?,
+        &[ResourceType::Libraries],
+        &priority[0],
+      )?,
+      dest: lib_dir.join(&rel),
+      filename: None,
+      sha1: None,
+    }));
+  }
+
+  // the tweaker is appended to the existing arguments, like OptiFine
+  if let Some(arguments) = &mut client_info.arguments {
+    let tweak_arguments = vec![
+      ArgumentsItem {
+        value: vec!["--tweakClass".to_string()],
+        rules: vec![],
+      },
+      ArgumentsItem {
+        value: vec![remote.tweak_class.clone()],
+        rules: vec![],
+      },
+    ];
+    arguments.game.extend(tweak_arguments.clone());
+    new_patch.arguments = Some(LaunchArgumentTemplate {
+      game: tweak_arguments,
+      jvm: vec![],
+    });
+  } else {
+    let tweak_arguments = format!("--tweakClass {}", remote.tweak_class);
+    let mut minecraft_arguments = client_info.minecraft_arguments.clone().unwrap_or_default();
+    if !minecraft_arguments.is_empty() && !minecraft_arguments.ends_with(' ') {
+      minecraft_arguments.push(' ');
+    }
+    minecraft_arguments.push_str(&tweak_arguments);
+    client_info.minecraft_arguments = Some(minecraft_arguments);
+    new_patch.minecraft_arguments = Some(tweak_arguments);
+  }
+
+  client_info.main_class = Some(LAUNCHWRAPPER_MAIN_CLASS.to_string());
+  client_info.patches.push(new_patch);
+
+  Ok(())
+}
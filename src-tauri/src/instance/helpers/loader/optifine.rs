@@ -1,24 +1,39 @@
+use regex::Regex;
 use sjmcl_types::error::SJMCLResult;
 use std::fs;
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::LazyLock;
 use tauri::AppHandle;
+use url::Url;
 use zip::{ZipArchive, ZipWriter, write::FileOptions};
 
 use crate::instance::helpers::client_json::{ArgumentsItem, LaunchArgumentTemplate};
 use crate::instance::helpers::client_json::{LibrariesValue, McClientInfo};
 use crate::instance::helpers::loader::common::add_library_entry;
 use crate::instance::helpers::misc::{get_instance_game_config, get_instance_subdir_paths};
-use crate::instance::models::misc::{Instance, InstanceError, InstanceSubdirType, ModLoaderType};
+use crate::instance::helpers::modpack::export::normalize_mod_loader_version;
+use crate::instance::models::misc::{
+  Instance, InstanceError, InstanceSubdirType, ModLoader, ModLoaderType, OptiFine,
+};
 use crate::launch::helpers::file_validator::convert_library_name_to_path;
 use crate::launch::helpers::jre_selector::select_java_runtime;
+use crate::resource::helpers::curseforge::fetch_resource_version_packs_curseforge;
 use crate::resource::helpers::misc::{convert_url_to_target_source, get_download_api};
-use crate::resource::models::{OptiFineResourceInfo, ResourceType, SourceType};
+use crate::resource::models::{
+  OptiFineResourceInfo, OtherResourceVersionPackQuery, ResourceType, SourceType,
+};
 use crate::tasks::PTaskParam;
 use crate::tasks::commands::schedule_progressive_task_group;
 use crate::tasks::download::DownloadParam;
 
+const OPTIFABRIC_MOD_ID_CURSEFORGE: &str = "322385";
+
+// legacy Forge ids embed the game version, e.g. "1.7.10-Forge10.13.4.1614-1.7.10"
+static LEGACY_FORGE_BUILD_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"\d+\.\d+\.\d+\.\d+").expect("Invalid regex"));
+
 pub async fn download_optifine_installer(
   game_version: &str,
   optifine: &OptiFineResourceInfo,
@@ -49,6 +64,122 @@ pub async fn download_optifine_installer(
   Ok(())
 }
 
+/// OptiFine only declares its Forge compatibility, e.g. `Forge 14.23.5.2847`, `Forge #2847` or `Forge N/A`,
+/// the declared build is taken as the minimum. On Fabric it needs OptiFabric, which only supports HD U editions.
+pub fn check_optifine_compatibility(
+  mod_loader: &ModLoader,
+  optifine: &OptiFineResourceInfo,
+) -> SJMCLResult<()> {
+  match mod_loader.loader_type {
+    ModLoaderType::Unknown | ModLoaderType::LiteLoader => Ok(()),
+    ModLoaderType::Forge | ModLoaderType::LegacyForge => {
+      let Some(required) = optifine
+        .forge
+        .as_deref()
+        .map(|forge| forge.trim_start_matches("Forge").trim())
+        .filter(|forge| !forge.is_empty())
+      else {
+        return Ok(());
+      };
+      if required.eq_ignore_ascii_case("N/A") {
+        return Err(InstanceError::OptiFineForgeIncompatible.into());
+      }
+      let parse_numbers = |version: &str| {
+        version
+          .split(|c: char| !c.is_ascii_digit())
+          .filter(|part| !part.is_empty())
+          .filter_map(|part| part.parse::<u32>().ok())
+          .collect::<Vec<_>>()
+      };
+      // installed instances record the patch id, e.g. "1.12.2-forge-14.23.5.2860"
+      let loader_version = normalize_mod_loader_version(&mod_loader.version);
+      let installed = match LEGACY_FORGE_BUILD_REGEX.find(&loader_version) {
+        Some(build) => parse_numbers(build.as_str()),
+        None => parse_numbers(&loader_version),
+      };
+      let compatible = match required.strip_prefix('#') {
+        // legacy declarations only give the Forge build number
+        Some(build) => installed.last() >= parse_numbers(build).first(),
+        None => installed >= parse_numbers(required),
+      };
+      if compatible {
+        Ok(())
+      } else {
+        Err(InstanceError::OptiFineForgeIncompatible.into())
+      }
+    }
+    ModLoaderType::Fabric => {
+      if optifine.r#type == "HD_U" {
+        Ok(())
+      } else {
+        Err(InstanceError::OptiFineEditionUnsupported.into())
+      }
+    }
+    _ => Err(InstanceError::UnsupportedModLoader.into()),
+  }
+}
+
+fn get_optifine_mod_path(mods_dir: &Path, filename: &str) -> PathBuf {
+  mods_dir.join(format!("{}.jar", filename))
+}
+
+/// On Fabric, OptiFine is put into the mods folder and loaded by OptiFabric instead of LaunchWrapper.
+/// OptiFabric files only declare the game version, not the OptiFine editions they support,
+/// so the newest one for the game version is taken and may lag behind a brand-new edition.
+pub async fn download_optifine_as_mod(
+  app: &AppHandle,
+  game_version: &str,
+  optifine: &OptiFineResourceInfo,
+  mods_dir: PathBuf,
+  task_params: &mut Vec<PTaskParam>,
+) -> SJMCLResult<()> {
+  // only have BMCLAPI source
+  let root = get_download_api(SourceType::BMCLAPIMirror, ResourceType::OptiFine)?;
+  task_params.push(PTaskParam::Download(DownloadParam {
+    src: root.join(&format!(
+      "{}/{}/{}",
+      game_version, optifine.r#type, optifine.patch
+    ))?,
+    dest: get_optifine_mod_path(&mods_dir, &optifine.filename),
+    filename: None,
+    sha1: None,
+  }));
+
+  let query = OtherResourceVersionPackQuery {
+    resource_id: OPTIFABRIC_MOD_ID_CURSEFORGE.to_string(),
+    mod_loader: ModLoaderType::Fabric.to_string(),
+    game_versions: vec![game_version.to_string()],
+    resource_type: String::new(),
+  };
+  let version_packs = fetch_resource_version_packs_curseforge(app, &query)
+    .await
+    .map_err(|_| InstanceError::OptiFabricNotFound)?;
+  let optifabric_file = version_packs
+    .iter()
+    .flat_map(|pack| pack.items.iter())
+    .filter(|file| matches!(file.release_type.as_str(), "beta" | "release"))
+    .max_by(|a, b| a.file_date.cmp(&b.file_date))
+    .ok_or(InstanceError::OptiFabricNotFound)?;
+
+  task_params.push(PTaskParam::Download(DownloadParam {
+    src: Url::parse(&optifabric_file.download_url)
+      .map_err(|_| InstanceError::OptiFabricNotFound)?,
+    dest: mods_dir.join(&optifabric_file.file_name),
+    filename: Some(optifabric_file.file_name.clone()),
+    sha1: Some(optifabric_file.sha1.clone()),
+  }));
+
+  Ok(())
+}
+
+pub fn remove_optifine_mod(mods_dir: &Path, optifine: &OptiFine) -> SJMCLResult<()> {
+  let optifine_path = get_optifine_mod_path(mods_dir, &optifine.filename);
+  if optifine_path.exists() {
+    fs::remove_file(optifine_path)?;
+  }
+  Ok(())
+}
+
 pub async fn download_optifine_libraries(
   app: &AppHandle,
   priority: &[SourceType],
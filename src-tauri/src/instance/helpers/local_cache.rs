@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sjmcl_types::error::SJMCLResult;
+use sjmcl_types::storage::Storage;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::APP_DATA_DIR;
+use crate::instance::helpers::mods::common::{
+  compress_icon, get_mod_info_from_jar, set_mod_file_fields,
+};
+use crate::instance::helpers::resourcepack::{PackMeta, load_resourcepack_from_zip};
+use crate::instance::models::misc::{LocalModInfo, ModLoaderType};
+use crate::resource::helpers::curseforge::fetch_remote_resource_by_local_curseforge;
+use crate::resource::helpers::modrinth::fetch_remote_resource_by_local_modrinth;
+use crate::resource::models::{OtherResourceFileInfo, OtherResourceSource, ResourceError};
+use crate::utils::image::ImageWrapper;
+
+const LOCAL_RESOURCE_CACHE_FILE_NAME: &str = "local_resource_cache.json";
+// bump when the cached metadata changes, e.g. nested mods, so that stale entries are dropped
+const LOCAL_RESOURCE_CACHE_FORMAT_VERSION: u32 = 1;
+// remote matches older than this are looked up again, as the project may get new files or urls
+const REMOTE_MATCH_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Parsed metadata of local mods, resource packs and shader packs, shared by all instances.
+/// Files are identified by sha1, which is only recomputed when the size or mtime of a path changes.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LocalResourceCache {
+  pub format_version: u32,
+  pub files: HashMap<String, LocalFileStamp>, // file path -> stamp
+  pub entries: HashMap<String, CachedLocalResource>, // sha1 -> metadata
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LocalFileStamp {
+  pub size: u64,
+  pub mtime: u64,
+  pub sha1: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CachedLocalResource {
+  // parsed with the given prior loader type, path dependent fields are refilled on use
+  pub mod_info: Option<(Option<ModLoaderType>, LocalModInfo)>,
+  pub pack_meta: Option<PackMeta>,
+  pub pack_icon: Option<ImageWrapper>, // compressed
+  pub remote_matches: Vec<CachedRemoteMatch>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CachedRemoteMatch {
+  pub source: OtherResourceSource,
+  pub file_info: OtherResourceFileInfo,
+  pub fetched_at: u64, // unix seconds
+}
+
+impl CachedRemoteMatch {
+  fn is_fresh(&self) -> bool {
+    get_unix_secs().saturating_sub(self.fetched_at) < REMOTE_MATCH_TTL_SECS
+  }
+}
+
+impl Storage for LocalResourceCache {
+  fn file_path() -> PathBuf {
+    APP_DATA_DIR
+      .get()
+      .unwrap()
+      .join(LOCAL_RESOURCE_CACHE_FILE_NAME)
+  }
+}
+
+fn get_unix_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |d| d.as_secs())
+}
+
+fn get_file_size_mtime(path: &Path) -> Option<(u64, u64)> {
+  let metadata = std::fs::metadata(path).ok()?;
+  let mtime = metadata
+    .modified()
+    .ok()?
+    .duration_since(UNIX_EPOCH)
+    .ok()?
+    .as_secs();
+  Some((metadata.len(), mtime))
+}
+
+impl LocalResourceCache {
+  /// Load the saved cache, starting over if it was written in another format.
+  pub fn load_or_default() -> Self {
+    match Self::load() {
+      Ok(cache) if cache.format_version == LOCAL_RESOURCE_CACHE_FORMAT_VERSION => cache,
+      _ => Self {
+        format_version: LOCAL_RESOURCE_CACHE_FORMAT_VERSION,
+        ..Default::default()
+      },
+    }
+  }
+
+  /// Drop the stamps of removed files and the entries no stamp refers to.
+  pub fn prune(&mut self) {
+    self.files.retain(|path, _| Path::new(path).exists());
+    let referenced = self
+      .files
+      .values()
+      .map(|stamp| stamp.sha1.clone())
+      .collect::<HashSet<_>>();
+    self.entries.retain(|sha1, _| referenced.contains(sha1));
+  }
+}
+
+/// Get the sha1 of a local file, hashing it only if it is new or changed since last time.
+pub async fn get_local_resource_sha1(app: &AppHandle, path: &Path) -> SJMCLResult<String> {
+  let key = path.to_string_lossy().to_string();
+  let size_mtime = get_file_size_mtime(path);
+  {
+    let cache_state = app.state::<Mutex<LocalResourceCache>>();
+    let cache = cache_state.lock()?;
+    if let (Some((size, mtime)), Some(stamp)) = (size_mtime, cache.files.get(&key))
+      && stamp.size == size
+      && stamp.mtime == mtime
+    {
+      return Ok(stamp.sha1.clone());
+    }
+  }
+
+  let mut hasher = Sha1::new();
+  hasher.update(tokio::fs::read(path).await?);
+  let sha1 = hex::encode(hasher.finalize());
+  if let Some((size, mtime)) = size_mtime {
+    let cache_state = app.state::<Mutex<LocalResourceCache>>();
+    let mut cache = cache_state.lock()?;
+    cache.files.insert(
+      key,
+      LocalFileStamp {
+        size,
+        mtime,
+        sha1: sha1.clone(),
+      },
+    );
+  }
+  Ok(sha1)
+}
+
+fn get_cached_entry(app: &AppHandle, sha1: &str) -> SJMCLResult<Option<CachedLocalResource>> {
+  let cache_state = app.state::<Mutex<LocalResourceCache>>();
+  let cache = cache_state.lock()?;
+  Ok(cache.entries.get(sha1).cloned())
+}
+
+fn update_cached_entry(
+  app: &AppHandle,
+  sha1: &str,
+  update: impl FnOnce(&mut CachedLocalResource),
+) -> SJMCLResult<()> {
+  let cache_state = app.state::<Mutex<LocalResourceCache>>();
+  let mut cache = cache_state.lock()?;
+  update(cache.entries.entry(sha1.to_string()).or_default());
+  Ok(())
+}
+
+/// Same as `get_mod_info_from_jar`, parsing the jar only if it is not cached.
+pub async fn get_mod_info_from_jar_cached(
+  app: &AppHandle,
+  path: &PathBuf,
+  prior_loader_type: Option<ModLoaderType>,
+) -> SJMCLResult<LocalModInfo> {
+  let sha1 = get_local_resource_sha1(app, path).await?;
+  if let Some((loader_type, mut mod_info)) =
+    get_cached_entry(app, &sha1)?.and_then(|entry| entry.mod_info)
+    && loader_type == prior_loader_type
+  {
+    set_mod_file_fields(&mut mod_info, path);
+    return Ok(mod_info);
+  }
+
+  let mod_info = get_mod_info_from_jar(path, prior_loader_type).await?;
+  update_cached_entry(app, &sha1, |entry| {
+    entry.mod_info = Some((prior_loader_type, mod_info.clone()));
+  })?;
+  Ok(mod_info)
+}
+
+/// Same as `load_resourcepack_from_zip` with a compressed icon, parsing the zip only if it is not cached.
+pub async fn load_resourcepack_from_zip_cached(
+  app: &AppHandle,
+  path: &PathBuf,
+) -> SJMCLResult<(PackMeta, Option<ImageWrapper>)> {
+  let sha1 = get_local_resource_sha1(app, path).await?;
+  if let Some(entry) = get_cached_entry(app, &sha1)?
+    && let Some(meta) = entry.pack_meta
+  {
+    return Ok((meta, entry.pack_icon));
+  }
+
+  let (meta, icon) = load_resourcepack_from_zip(path)?;
+  let icon = icon.map(ImageWrapper::from).map(compress_icon);
+  update_cached_entry(app, &sha1, |entry| {
+    entry.pack_meta = Some(meta.clone());
+    entry.pack_icon = icon.clone();
+  })?;
+  Ok((meta, icon))
+}
+
+/// Look up a local mod, resource pack or shader pack on a platform by its hash,
+/// reusing the previous match of the same file unless it is stale.
+pub async fn fetch_remote_resource_by_local_cached(
+  app: &AppHandle,
+  file_path: &str,
+  download_source: &OtherResourceSource,
+) -> SJMCLResult<OtherResourceFileInfo> {
+  let sha1 = get_local_resource_sha1(app, Path::new(file_path)).await?;
+  if let Some(entry) = get_cached_entry(app, &sha1)?
+    && let Some(remote_match) = entry
+      .remote_matches
+      .into_iter()
+      .find(|m| &m.source == download_source && m.is_fresh())
+  {
+    return Ok(remote_match.file_info);
+  }
+
+  let file_info = match download_source {
+    OtherResourceSource::CurseForge => {
+      fetch_remote_resource_by_local_curseforge(app, file_path).await?
+    }
+    OtherResourceSource::Modrinth => {
+      fetch_remote_resource_by_local_modrinth(app, file_path).await?
+    }
+    _ => return Err(ResourceError::NoDownloadApi.into()),
+  };
+  update_cached_entry(app, &sha1, |entry| {
+    entry
+      .remote_matches
+      .retain(|m| &m.source != download_source);
+    entry.remote_matches.push(CachedRemoteMatch {
+      source: download_source.clone(),
+      file_info: file_info.clone(),
+      fetched_at: get_unix_secs(),
+    });
+  })?;
+  Ok(file_info)
+}
+
+/// Remote project ids of the local files already matched on the platform, without new lookups.
+/// Stale matches are used too, the project of a file does not change.
+pub async fn get_cached_remote_project_ids(
+  app: &AppHandle,
+  paths: &[PathBuf],
+  download_source: &OtherResourceSource,
+) -> HashSet<String> {
+  let mut project_ids = HashSet::new();
+  for path in paths {
+    let Ok(sha1) = get_local_resource_sha1(app, path).await else {
+      continue;
+    };
+    if let Ok(Some(entry)) = get_cached_entry(app, &sha1) {
+      project_ids.extend(
+        entry
+          .remote_matches
+          .into_iter()
+          .filter(|m| &m.source == download_source)
+          .map(|m| m.file_info.resource_id),
+      );
+    }
+  }
+  project_ids
+}
+
+/// Persist the cache, called after listing the local resources of an instance.
+pub fn save_local_resource_cache(app: &AppHandle) -> SJMCLResult<()> {
+  let cache_state = app.state::<Mutex<LocalResourceCache>>();
+  let mut cache = cache_state.lock()?;
+  cache.prune();
+  cache.save()?;
+  Ok(())
+}
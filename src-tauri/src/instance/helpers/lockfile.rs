@@ -0,0 +1,288 @@
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use sha1::Digest;
+use sjmcl_types::error::SJMCLResult;
+use sjmcl_types::storage::load_json_async;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::Semaphore;
+
+use crate::instance::helpers::local_cache::fetch_remote_resource_by_local_cached;
+use crate::instance::helpers::misc::get_instance_subdir_paths;
+use crate::instance::models::misc::{
+  Instance, InstanceError, InstanceSubdirType, ModLoader, OptiFine,
+};
+use crate::resource::models::OtherResourceSource;
+use crate::utils::fs::get_files_with_regex;
+
+pub const INSTANCE_LOCK_FILE_NAME: &str = "instance.lock";
+const INSTANCE_LOCK_FORMAT_VERSION: u32 = 1;
+const DISABLED_SUFFIX: &str = ".disabled";
+const SYNC_TEMP_SUFFIX: &str = ".sjmcl-sync";
+
+// folders (relative to the game root) whose files are recorded in the lock
+const LOCKED_SUBDIRS: [(&InstanceSubdirType, &str); 3] = [
+  (&InstanceSubdirType::Mods, "mods"),
+  (&InstanceSubdirType::ResourcePacks, "resourcepacks"),
+  (&InstanceSubdirType::ShaderPacks, "shaderpacks"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InstanceLock {
+  pub format_version: u32,
+  pub game_version: String,
+  pub mod_loader: ModLoader,
+  pub optifine: Option<OptiFine>,
+  pub files: Vec<LockedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LockedFile {
+  pub path: String, // relative to the game root, without the `.disabled` suffix
+  pub enabled: bool,
+  pub sha1: String,
+  pub sha512: String,
+  pub size: u64,
+  pub source: OtherResourceSource, // Unknown if not found on any platform
+  pub project_id: Option<String>,
+  pub file_id: Option<String>,
+  pub download_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceLockSyncReport {
+  pub added: Vec<String>,
+  pub replaced: Vec<String>,
+  pub removed: Vec<String>,
+  pub missing: Vec<String>, // locked files without a known download url
+}
+
+impl LockedFile {
+  // the path comes from a possibly imported lock, it must name a file directly in a locked folder
+  fn has_valid_path(&self) -> bool {
+    let components = Path::new(&self.path).components().collect::<Vec<_>>();
+    match components.as_slice() {
+      [Component::Normal(dir), Component::Normal(_)] => LOCKED_SUBDIRS
+        .iter()
+        .any(|(_, dir_name)| dir.to_str() == Some(*dir_name)),
+      _ => false,
+    }
+  }
+
+  pub fn local_path(&self, game_root: &Path) -> PathBuf {
+    match self.enabled {
+      true => game_root.join(&self.path),
+      false => game_root.join(format!("{}{}", self.path, DISABLED_SUFFIX)),
+    }
+  }
+}
+
+// (path relative to the game root without `.disabled`, enabled, full path)
+pub fn list_locked_candidates(
+  app: &AppHandle,
+  instance: &Instance,
+) -> SJMCLResult<Vec<(String, bool, PathBuf)>> {
+  let subdir_types = LOCKED_SUBDIRS.map(|(t, _)| t);
+  let subdirs = get_instance_subdir_paths(app, instance, &subdir_types)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let valid_extensions = RegexBuilder::new(r"\.(jar|zip)(\.disabled)?$")
+    .case_insensitive(true)
+    .build()
+    .unwrap();
+
+  let mut candidates = Vec::new();
+  for (dir, (_, dir_name)) in subdirs.iter().zip(LOCKED_SUBDIRS) {
+    for path in get_files_with_regex(dir, &valid_extensions).unwrap_or_default() {
+      let Some(file_name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+        continue;
+      };
+      let (file_name, enabled) = match file_name.strip_suffix(DISABLED_SUFFIX) {
+        Some(stripped) => (stripped.to_string(), false),
+        None => (file_name, true),
+      };
+      candidates.push((format!("{}/{}", dir_name, file_name), enabled, path));
+    }
+  }
+  candidates.sort_by(|a, b| a.0.cmp(&b.0));
+  Ok(candidates)
+}
+
+pub async fn hash_file(path: &Path) -> SJMCLResult<(String, String, u64)> {
+  let file_content = tokio::fs::read(path).await?;
+  let mut sha1_hasher = sha1::Sha1::new();
+  let mut sha512_hasher = sha2::Sha512::new();
+  sha1_hasher.update(&file_content);
+  sha512_hasher.update(&file_content);
+  Ok((
+    hex::encode(sha1_hasher.finalize()),
+    hex::encode(sha512_hasher.finalize()),
+    file_content.len() as u64,
+  ))
+}
+
+// files that cannot be read are still recorded, without hashes, so that a sync keeps them
+async fn build_locked_file(
+  app: &AppHandle,
+  path: String,
+  enabled: bool,
+  full_path: &Path,
+) -> LockedFile {
+  let Ok((sha1, sha512, size)) = hash_file(full_path).await else {
+    return LockedFile {
+      path,
+      enabled,
+      ..Default::default()
+    };
+  };
+  let full_path_str = full_path.to_string_lossy().to_string();
+
+  let mut remote = None;
+  for source in [
+    OtherResourceSource::Modrinth,
+    OtherResourceSource::CurseForge,
+  ] {
+    if let Ok(file_info) = fetch_remote_resource_by_local_cached(app, &full_path_str, &source).await
+    {
+      remote = Some((source, file_info));
+      break;
+    }
+  }
+  let (source, project_id, file_id, download_url) = match remote {
+    Some((source, remote)) => (
+      source,
+      Some(remote.resource_id),
+      (!remote.file_id.is_empty()).then_some(remote.file_id),
+      // CurseForge gives no url for mods that disallow third-party downloads
+      (!remote.download_url.is_empty()).then_some(remote.download_url),
+    ),
+    None => (OtherResourceSource::Unknown, None, None, None),
+  };
+
+  LockedFile {
+    path,
+    enabled,
+    sha1,
+    sha512,
+    size,
+    source,
+    project_id,
+    file_id,
+    download_url,
+  }
+}
+
+/// Build the lock of the instance, looking up every mod, resource pack and shader pack remotely.
+pub async fn build_instance_lock(
+  app: &AppHandle,
+  instance: &Instance,
+) -> SJMCLResult<InstanceLock> {
+  let semaphore = Arc::new(Semaphore::new(
+    std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get),
+  ));
+  let mut tasks = Vec::new();
+  for (path, enabled, full_path) in list_locked_candidates(app, instance)? {
+    let permit = semaphore
+      .clone()
+      .acquire_owned()
+      .await
+      .map_err(|_| InstanceError::SemaphoreAcquireFailed)?;
+    let app = app.clone();
+    tasks.push(tokio::spawn(async move {
+      let locked_file = build_locked_file(&app, path, enabled, &full_path).await;
+      drop(permit);
+      locked_file
+    }));
+  }
+
+  let mut files = Vec::new();
+  for task in tasks {
+    files.push(task.await?);
+  }
+
+  Ok(InstanceLock {
+    format_version: INSTANCE_LOCK_FORMAT_VERSION,
+    game_version: instance.version.clone(),
+    mod_loader: instance.mod_loader.clone(),
+    optifine: instance.optifine.clone(),
+    files,
+  })
+}
+
+/// Load a lock file, rejecting locked paths outside the mod, resource pack and shader pack folders.
+pub async fn load_instance_lock(lock_path: &Path) -> SJMCLResult<InstanceLock> {
+  let lock = load_json_async::<InstanceLock>(lock_path)
+    .await
+    .map_err(|_| InstanceError::InstanceLockParseError)?;
+  if !lock.files.iter().all(LockedFile::has_valid_path) {
+    return Err(InstanceError::InstanceLockParseError.into());
+  }
+  Ok(lock)
+}
+
+/// Hash the local files covered by the lock, as (sha1, path, enabled, full path).
+pub async fn collect_local_file_hashes(
+  app: &AppHandle,
+  instance: &Instance,
+) -> SJMCLResult<Vec<(String, String, bool, PathBuf)>> {
+  let mut hashes = Vec::new();
+  for (path, enabled, full_path) in list_locked_candidates(app, instance)? {
+    if let Ok((sha1, _, _)) = hash_file(&full_path).await {
+      hashes.push((sha1, path, enabled, full_path));
+    }
+  }
+  Ok(hashes)
+}
+
+/// Move local files to their locked paths. Every source is first moved to a temporary name,
+/// so that swapped or chained renames do not overwrite a file before it is moved.
+pub fn apply_locked_renames(renames: &[(PathBuf, PathBuf)]) -> SJMCLResult<()> {
+  let temp_path = |path: &Path| {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(SYNC_TEMP_SUFFIX);
+    PathBuf::from(temp)
+  };
+
+  for (index, (from, _)) in renames.iter().enumerate() {
+    if fs::rename(from, temp_path(from)).is_err() {
+      // put back the files staged so far
+      for (staged, _) in &renames[..index] {
+        let _ = fs::rename(temp_path(staged), staged);
+      }
+      return Err(InstanceError::FileMoveFailed.into());
+    }
+  }
+  for (from, to) in renames {
+    fs::rename(temp_path(from), to).map_err(|_| InstanceError::FileMoveFailed)?;
+  }
+  Ok(())
+}
+
+/// Remove the local files not listed in the lock, returns their paths relative to the game root.
+pub fn remove_unlocked_files(
+  app: &AppHandle,
+  instance: &Instance,
+  lock: &InstanceLock,
+  game_root: &Path,
+) -> SJMCLResult<Vec<String>> {
+  let locked_paths = lock
+    .files
+    .iter()
+    .map(|file| file.local_path(game_root))
+    .collect::<HashSet<_>>();
+
+  let mut removed = Vec::new();
+  for (path, _, full_path) in list_locked_candidates(app, instance)? {
+    if locked_paths.contains(&full_path) {
+      continue;
+    }
+    fs::remove_file(&full_path).map_err(|_| InstanceError::FileOperationError)?;
+    removed.push(path);
+  }
+  Ok(removed)
+}
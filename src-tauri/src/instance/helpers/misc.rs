@@ -265,12 +265,16 @@ async fn refresh_instance(
     }
   }
 
-  let (mut game_version, loader_version, loader_type, optifine_info) =
+  let (mut game_version, mut loader_version, mut loader_type, optifine_info) =
     if !client_data.patches.is_empty() {
       patches_to_info(&client_data.patches)
     } else {
       libraries_to_info(&client_data).await
     };
+  // loaders brought in by a user version JSON patch have no dedicated patch
+  if loader_type == ModLoaderType::Unknown && !client_data.patches.is_empty() {
+    (_, loader_version, loader_type, _) = libraries_to_info(&client_data).await;
+  }
   // TODO: patches related logic
   if game_version.is_none() {
     let file = Cursor::new(tokio::fs::read(jar_path).await?);
@@ -288,6 +292,8 @@ async fn refresh_instance(
     .optifine
     .as_ref()
     .is_some_and(|o| o.status == ModLoaderStatus::Installed);
+  // OptiFine on Fabric lives in the mods folder without a patch, keep the saved config
+  let optifine_in_patches = optifine_info.is_some();
   let optifine_filename = optifine_info.as_ref().map(|info| info.filename.clone());
   let optifine_version = optifine_info.map(|info| info.r#type.to_string());
   let instance = Instance {
@@ -305,7 +311,7 @@ async fn refresh_instance(
         branch: None,
       }
     },
-    optifine: if !optifine_installed {
+    optifine: if !optifine_installed || !optifine_in_patches {
       cfg_read.optifine.clone()
     } else {
       Some(OptiFine {
@@ -1,12 +1,20 @@
 pub mod asset_index;
 pub mod client_jar;
 pub mod client_json;
+pub mod client_patch;
 pub mod game_version;
 pub mod loader;
+pub mod local_cache;
+pub mod lockfile;
 pub mod misc;
+pub mod mod_set;
 pub mod modpack;
 pub mod mods;
 pub mod options_txt;
 pub mod resourcepack;
+pub mod screenshot;
 pub mod server;
+pub mod server_instance;
+pub mod server_properties;
+pub mod shaderpack;
 pub mod world;
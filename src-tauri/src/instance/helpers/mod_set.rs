@@ -0,0 +1,109 @@
+use regex::RegexBuilder;
+use sjmcl_types::error::SJMCLResult;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::instance::models::misc::{InstanceError, ModBisectState};
+use crate::utils::fs::get_files_with_regex;
+
+const DISABLED_SUFFIX: &str = ".disabled";
+
+// (enabled file name, current path), e.g. ("sodium.jar", ".../sodium.jar.disabled")
+fn list_mod_files(mods_dir: &Path) -> Vec<(String, PathBuf)> {
+  let valid_extensions = RegexBuilder::new(r"\.(jar|zip)(\.disabled)*$")
+    .case_insensitive(true)
+    .build()
+    .unwrap();
+  get_files_with_regex(mods_dir, &valid_extensions)
+    .unwrap_or_default()
+    .into_iter()
+    .filter_map(|path| {
+      let file_name = path.file_name()?.to_string_lossy().to_string();
+      let mut base_name = file_name.as_str();
+      while let Some(stripped) = base_name.strip_suffix(DISABLED_SUFFIX) {
+        base_name = stripped;
+      }
+      Some((base_name.to_string(), path))
+    })
+    .collect()
+}
+
+/// File names (without `.disabled`) of the enabled mods, sorted.
+pub fn get_enabled_mod_file_names(mods_dir: &Path) -> Vec<String> {
+  let mut names = list_mod_files(mods_dir)
+    .into_iter()
+    .filter(|(name, path)| {
+      path
+        .file_name()
+        .is_some_and(|f| f.to_string_lossy() == *name)
+    })
+    .map(|(name, _)| name)
+    .collect::<Vec<_>>();
+  names.sort();
+  names
+}
+
+/// Enable exactly the mods in `enabled_mods` and disable all others.
+/// Renames already done are reverted if any of them fails, so the folder is never left half-applied.
+pub fn apply_enabled_mods(mods_dir: &Path, enabled_mods: &HashSet<String>) -> SJMCLResult<()> {
+  let renames = list_mod_files(mods_dir)
+    .into_iter()
+    .filter_map(|(name, path)| {
+      let target = match enabled_mods.contains(&name) {
+        true => mods_dir.join(&name),
+        false => mods_dir.join(format!("{}{}", name, DISABLED_SUFFIX)),
+      };
+      (target != path).then_some((path, target))
+    })
+    .collect::<Vec<_>>();
+
+  let mut done = Vec::new();
+  for (from, to) in renames {
+    if to.exists() || fs::rename(&from, &to).is_err() {
+      for (from, to) in done.into_iter().rev() {
+        let _ = fs::rename(&to, &from);
+      }
+      return Err(InstanceError::FileOperationError.into());
+    }
+    done.push((from, to));
+  }
+  Ok(())
+}
+
+impl ModBisectState {
+  pub fn new(enabled_mods: Vec<String>) -> Self {
+    let mut state = Self {
+      original_enabled: enabled_mods.clone(),
+      suspects: enabled_mods,
+      ..Default::default()
+    };
+    state.next_step();
+    state
+  }
+
+  /// Test the first half of the remaining suspects next, or finish if at most one is left.
+  fn next_step(&mut self) {
+    if self.suspects.len() <= 1 {
+      self.finished = true;
+      self.culprit = self.suspects.first().cloned();
+      self.testing = self.original_enabled.clone();
+      return;
+    }
+    self.step += 1;
+    self.testing = self.suspects[..self.suspects.len() / 2].to_vec();
+  }
+
+  /// Narrow the suspects down by the result of the last test launch.
+  pub fn report(&mut self, crashed: bool) {
+    if self.finished {
+      return;
+    }
+    if crashed {
+      self.suspects = self.testing.clone();
+    } else {
+      self.suspects.retain(|name| !self.testing.contains(name));
+    }
+    self.next_step();
+  }
+}
@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sjmcl_types::error::SJMCLResult;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path};
+use std::str::FromStr;
+use tauri::AppHandle;
+use zip::ZipArchive;
+
+use crate::instance::helpers::modpack::import::{ModpackManifest, ModpackMetaInfo};
+use crate::instance::models::misc::{InstanceError, ModLoader, ModLoaderType};
+use crate::resource::models::OtherResourceSource;
+use crate::tasks::PTaskParam;
+use crate::tasks::download::DownloadParam;
+
+// ATLauncher instance exports: the instance folder zipped with its `instance.json`.
+
+structstruck::strike! {
+#[strikethrough[derive(Deserialize, Serialize, Debug, Clone, Default)]]
+#[strikethrough[serde(rename_all = "camelCase", default)]]
+  pub struct ATLauncherInstance {
+    pub id: String, // game version
+    pub launcher: struct ATLauncherInstanceLauncher {
+      pub name: String,
+      pub pack: String,
+      pub version: String,
+      pub description: Option<String>,
+      pub loader_version: Option<struct ATLauncherLoaderVersion {
+        pub version: String,
+        #[serde(rename = "type")]
+        pub loader_type: String,
+      }>,
+      pub mods: Vec<struct ATLauncherMod {
+        pub name: String,
+        pub file: String,
+        #[serde(rename = "type")]
+        pub mod_type: String,
+        pub url: Option<String>,
+        pub disabled: bool,
+        pub curse_forge_file_id: Option<u64>,
+      }>,
+    },
+    #[serde(skip)]
+    pub bundled_files: HashSet<String>,
+  }
+}
+
+impl ATLauncherMod {
+  fn get_dir(&self) -> Option<&'static str> {
+    match self.mod_type.as_str() {
+      "mods" | "forge" | "fabric" => Some("mods"),
+      "resourcepack" => Some("resourcepacks"),
+      "shaderpack" => Some("shaderpacks"),
+      _ => None, // jar mods, natives, etc. are not supported
+    }
+  }
+
+  fn get_download_url(&self) -> Option<String> {
+    self.url.clone().or_else(|| {
+      self.curse_forge_file_id.map(|file_id| {
+        format!(
+          "https://edge.forgecdn.net/files/{}/{}/{}",
+          file_id / 1000,
+          file_id % 1000,
+          urlencoding::encode(&self.file)
+        )
+      })
+    })
+  }
+}
+
+#[async_trait]
+impl ModpackManifest for ATLauncherInstance {
+  fn from_archive(file: &File) -> SJMCLResult<Self> {
+    let mut archive = ZipArchive::new(file)?;
+    let mut manifest: ATLauncherInstance;
+    {
+      let mut manifest_file = archive.by_name("instance.json")?;
+      let mut manifest_content = String::new();
+      manifest_file.read_to_string(&mut manifest_content)?;
+      manifest = serde_json::from_str(&manifest_content)?;
+    }
+    if manifest.id.is_empty() || manifest.launcher.name.is_empty() {
+      return Err(InstanceError::ModpackManifestParseError.into());
+    }
+    manifest.bundled_files = archive.file_names().map(str::to_string).collect();
+
+    Ok(manifest)
+  }
+
+  async fn get_meta_info(&self, app: &AppHandle) -> SJMCLResult<ModpackMetaInfo> {
+    let client_version = self.get_client_version()?;
+    let mod_loader = if let Ok((loader_type, version)) = self.get_mod_loader_type_version() {
+      Some(
+        ModLoader {
+          loader_type,
+          version,
+          ..Default::default()
+        }
+        .with_branch(app, client_version.clone())
+        .await?,
+      )
+    } else {
+      None
+    };
+    Ok(ModpackMetaInfo {
+      name: self.launcher.name.clone(),
+      version: Some(self.launcher.version.clone()).filter(|v| !v.is_empty()),
+      description: self.launcher.description.clone(),
+      author: None,
+      modpack_source: OtherResourceSource::ATLauncher,
+      client_version,
+      mod_loader,
+    })
+  }
+
+  fn get_client_version(&self) -> SJMCLResult<String> {
+    Ok(self.id.clone())
+  }
+
+  fn get_mod_loader_type_version(&self) -> SJMCLResult<(ModLoaderType, String)> {
+    let loader = self
+      .launcher
+      .loader_version
+      .as_ref()
+      .ok_or(InstanceError::ModLoaderVersionParseError)?;
+    Ok((
+      ModLoaderType::from_str(&loader.loader_type)
+        .ok()
+        .ok_or(InstanceError::ModLoaderVersionParseError)?,
+      loader.version.clone(),
+    ))
+  }
+
+  async fn get_download_params(
+    &self,
+    _app: &AppHandle,
+    instance_path: &Path,
+  ) -> SJMCLResult<Vec<PTaskParam>> {
+    let mut task_params = Vec::new();
+    for atl_mod in &self.launcher.mods {
+      if atl_mod.disabled {
+        continue;
+      }
+      let Some(dir) = atl_mod.get_dir() else {
+        continue;
+      };
+      // files included in the export are extracted as overrides
+      if self
+        .bundled_files
+        .contains(&format!("{}/{}", dir, atl_mod.file))
+      {
+        continue;
+      }
+      let Some(download_url) = atl_mod.get_download_url() else {
+        continue;
+      };
+      // the file name comes from the pack, keep it inside the target folder
+      let is_plain_file = Path::new(&atl_mod.file)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)));
+      if atl_mod.file.is_empty() || !is_plain_file {
+        return Err(InstanceError::InvalidSourcePath.into());
+      }
+      task_params.push(PTaskParam::Download(DownloadParam {
+        src: url::Url::parse(&download_url).map_err(|_| InstanceError::InvalidSourcePath)?,
+        dest: instance_path.join(dir).join(&atl_mod.file),
+        filename: Some(atl_mod.file.clone()),
+        sha1: None,
+      }));
+    }
+    Ok(task_params)
+  }
+
+  fn get_overrides_path(&self) -> String {
+    String::new()
+  }
+
+  fn is_override_excluded(&self, relative_path: &Path) -> bool {
+    relative_path == Path::new("instance.json")
+  }
+}
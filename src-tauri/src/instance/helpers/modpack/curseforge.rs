@@ -3,14 +3,21 @@ use serde::{Deserialize, Serialize};
 use sjmcl_types::error::{SJMCLError, SJMCLResult};
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_http::reqwest;
+use tokio::sync::Semaphore;
 use zip::ZipArchive;
 
+use crate::instance::helpers::local_cache::fetch_remote_resource_by_local_cached;
+use crate::instance::helpers::modpack::export::{
+  ExportModpackOptions, ModpackExportBundle, normalize_mod_loader_version,
+};
 use crate::instance::helpers::modpack::import::{ModpackManifest, ModpackMetaInfo};
-use crate::instance::models::misc::{InstanceError, ModLoader, ModLoaderType};
+use crate::instance::models::misc::{Instance, InstanceError, ModLoader, ModLoaderType};
+use crate::resource::helpers::curseforge::fetch_remote_resource_by_id_curseforge;
 use crate::resource::helpers::curseforge::misc::{CURSEFORGE_API_KEY, CurseForgeProject};
 use crate::resource::models::OtherResourceSource;
 use crate::tasks::PTaskParam;
@@ -37,6 +44,10 @@ structstruck::strike! {
 #[strikethrough[derive(Deserialize, Serialize, Debug, Clone)]]
 #[strikethrough[serde(rename_all = "camelCase")]]
   pub struct CurseForgeManifest {
+    #[serde(default)]
+    pub manifest_type: String,
+    #[serde(default)]
+    pub manifest_version: u32,
     pub name: String,
     pub version: Option<String>,
     pub author: String,
@@ -224,3 +235,194 @@ impl ModpackManifest for CurseForgeManifest {
     self.overrides.clone()
   }
 }
+
+pub async fn build_curseforge_export_bundle(
+  app: &AppHandle,
+  instance: &Instance,
+  options: &ExportModpackOptions,
+  selected_files: &[(String, PathBuf)],
+) -> SJMCLResult<ModpackExportBundle> {
+  let mut manifest = generate_curseforge_manifest(instance, options);
+  let (remote_files, override_files) = collect_curseforge_files(app, selected_files).await?;
+
+  let mut modlist_items = Vec::new();
+  for (file, name, website_url, author) in remote_files {
+    modlist_items.push(match author {
+      Some(author) => format!(
+        "<li><a href=\"{}\">{} (by {})</a></li>",
+        escape_html(&website_url),
+        escape_html(&name),
+        escape_html(&author)
+      ),
+      None => format!(
+        "<li><a href=\"{}\">{}</a></li>",
+        escape_html(&website_url),
+        escape_html(&name)
+      ),
+    });
+    manifest.files.push(file);
+  }
+  let json = serde_json::to_string_pretty(&manifest)
+    .map_err(|_| InstanceError::ModpackManifestParseError)?;
+
+  Ok(ModpackExportBundle {
+    overrides_prefix: manifest.overrides.clone(),
+    overrides_files: override_files,
+    extra_files: vec![
+      ("manifest.json".to_string(), json),
+      (
+        "modlist.html".to_string(),
+        format!("<ul>\n{}\n</ul>\n", modlist_items.join("\n")),
+      ),
+    ],
+  })
+}
+
+fn generate_curseforge_manifest(
+  instance: &Instance,
+  options: &ExportModpackOptions,
+) -> CurseForgeManifest {
+  let mut mod_loaders = Vec::new();
+  let loader_key = match instance.mod_loader.loader_type {
+    ModLoaderType::Forge | ModLoaderType::LegacyForge => "forge",
+    ModLoaderType::NeoForge => "neoforge",
+    ModLoaderType::Fabric => "fabric",
+    ModLoaderType::Quilt => "quilt",
+    _ => "",
+  };
+  if !loader_key.is_empty() {
+    mod_loaders.push(CurseForgeModLoader {
+      id: format!(
+        "{}-{}",
+        loader_key,
+        normalize_mod_loader_version(&instance.mod_loader.version)
+      ),
+      primary: true,
+    });
+  }
+
+  CurseForgeManifest {
+    manifest_type: "minecraftModpack".to_string(),
+    manifest_version: 1,
+    name: options.name.clone(),
+    version: Some(options.version.clone()),
+    author: options.author.clone().unwrap_or_default(),
+    overrides: "overrides".to_string(),
+    minecraft: Minecraft {
+      version: instance.version.clone(),
+      mod_loaders,
+    },
+    files: Vec::new(),
+  }
+}
+
+// (manifest entry, project name, project url, author) of a file found on CurseForge by fingerprint
+async fn build_curseforge_remote_file(
+  app: &AppHandle,
+  full: &Path,
+) -> SJMCLResult<(CurseForgeFiles, String, String, Option<String>)> {
+  let remote = fetch_remote_resource_by_local_cached(
+    app,
+    full.to_string_lossy().as_ref(),
+    &OtherResourceSource::CurseForge,
+  )
+  .await?;
+  let project_id = remote
+    .resource_id
+    .parse::<u64>()
+    .map_err(|_| InstanceError::ModpackManifestParseError)?;
+  let file_id = remote
+    .file_id
+    .parse::<u64>()
+    .map_err(|_| InstanceError::ModpackManifestParseError)?;
+
+  // project info is only used by modlist.html, fall back to the file name
+  let (name, website_url, author) =
+    match fetch_remote_resource_by_id_curseforge(app, &remote.resource_id).await {
+      Ok(project) => (project.name, project.website_url, project.author),
+      Err(_) => (
+        remote.file_name.clone(),
+        format!("https://www.curseforge.com/projects/{}", project_id),
+        None,
+      ),
+    };
+
+  Ok((
+    CurseForgeFiles {
+      project_id,
+      file_id,
+      required: true,
+    },
+    name,
+    website_url,
+    author,
+  ))
+}
+
+#[allow(clippy::type_complexity)]
+async fn collect_curseforge_files(
+  app: &AppHandle,
+  selected_files: &[(String, PathBuf)],
+) -> SJMCLResult<(
+  Vec<(CurseForgeFiles, String, String, Option<String>)>,
+  Vec<(String, PathBuf)>,
+)> {
+  // disabled files are kept in overrides to preserve their state
+  let is_remote_candidate = |rel: &str| {
+    (rel.starts_with("mods/")
+      || rel.starts_with("resourcepacks/")
+      || rel.starts_with("shaderpacks/"))
+      && !rel.ends_with(".disabled")
+  };
+
+  let mut tasks = Vec::new();
+  let semaphore = Arc::new(Semaphore::new(
+    std::thread::available_parallelism().unwrap().into(),
+  ));
+
+  for (rel, full) in selected_files {
+    if !is_remote_candidate(rel) {
+      tasks.push((rel.clone(), full.clone(), None));
+      continue;
+    }
+    let app = app.clone();
+    let permit = semaphore
+      .clone()
+      .acquire_owned()
+      .await
+      .map_err(|_| InstanceError::SemaphoreAcquireFailed)?;
+
+    let task = tokio::spawn({
+      let full = full.clone();
+      async move {
+        let result = build_curseforge_remote_file(&app, &full).await.ok();
+        drop(permit);
+        result
+      }
+    });
+    tasks.push((rel.clone(), full.clone(), Some(task)));
+  }
+
+  let mut remote_files = Vec::new();
+  let mut override_files = Vec::new();
+
+  for (rel, full, task) in tasks {
+    let result = match task {
+      Some(task) => task.await.ok().flatten(),
+      None => None,
+    };
+    match result {
+      Some(remote_file) => remote_files.push(remote_file),
+      None => override_files.push((rel, full)),
+    }
+  }
+
+  Ok((remote_files, override_files))
+}
+
+fn escape_html(s: &str) -> String {
+  s.replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
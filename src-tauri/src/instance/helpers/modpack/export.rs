@@ -11,12 +11,16 @@ use zip::write::{ExtendedFileOptions, FileOptions};
 use zip::{CompressionMethod, ZipWriter};
 
 use crate::instance::helpers::modpack::{
-  modrinth::build_modrinth_export_bundle, multimc::build_multimc_export_bundle,
+  curseforge::build_curseforge_export_bundle, modrinth::build_modrinth_export_bundle,
+  multimc::build_multimc_export_bundle,
+};
+use crate::instance::models::misc::{
+  Instance, InstanceError, ModLoader, ModLoaderType, ModpackFileList,
 };
-use crate::instance::models::misc::{Instance, InstanceError, ModpackFileList};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum ExportFormat {
+  CurseForge,
   Modrinth,
   MultiMC,
 }
@@ -125,6 +129,8 @@ static BLACKLIST: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
     // SJMCL
     "install_profile.json",
     "sjmclcfg.json",
+    "sjmclmodpack.json",
+    ".sjmclmodpack",
     // Curse
     "manifest.json",
     "minecraftinstance.json",
@@ -296,6 +302,22 @@ pub(crate) fn normalize_mod_loader_version(raw: &str) -> String {
   raw.to_string()
 }
 
+/// Same as `normalize_mod_loader_version`, in the form the loader installers take:
+/// NeoForge for 1.20.1 was published as "1.20.1-47.1.106" under the old Forge artifact.
+pub(crate) fn normalize_mod_loader_install_version(
+  mod_loader: &ModLoader,
+  game_version: &str,
+) -> String {
+  let version = normalize_mod_loader_version(&mod_loader.version);
+  if mod_loader.loader_type == ModLoaderType::NeoForge
+    && game_version == "1.20.1"
+    && !version.starts_with("1.20.1-")
+  {
+    return format!("1.20.1-{}", version);
+  }
+  version
+}
+
 #[derive(Debug, Clone)]
 pub struct ModpackExportBundle {
   pub overrides_prefix: String,
@@ -310,6 +332,9 @@ pub async fn build_export_bundle(
   selected_files: &[(String, PathBuf)],
 ) -> SJMCLResult<ModpackExportBundle> {
   match options.format {
+    ExportFormat::CurseForge => {
+      build_curseforge_export_bundle(app, instance, options, selected_files).await
+    }
     ExportFormat::Modrinth => {
       build_modrinth_export_bundle(app, instance, options, selected_files).await
     }
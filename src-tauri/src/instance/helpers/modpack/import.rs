@@ -3,13 +3,16 @@ use serde::{Deserialize, Serialize};
 use sjmcl_types::error::SJMCLResult;
 use std::fs;
 use std::fs::File;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 use zip::ZipArchive;
 
+use crate::instance::helpers::modpack::atlauncher::ATLauncherInstance;
 use crate::instance::helpers::modpack::curseforge::CurseForgeManifest;
 use crate::instance::helpers::modpack::modrinth::ModrinthManifest;
 use crate::instance::helpers::modpack::multimc::MultiMcManifest;
+use crate::instance::helpers::modpack::server_pack::ServerPackManifest;
+use crate::instance::helpers::modpack::technic::TechnicManifest;
 use crate::instance::models::misc::{InstanceError, ModLoader, ModLoaderType};
 use crate::resource::commands::fetch_mod_loader_version_list;
 use crate::resource::models::OtherResourceSource;
@@ -29,6 +32,10 @@ pub trait ModpackManifest {
     instance_path: &Path,
   ) -> SJMCLResult<Vec<PTaskParam>>;
   fn get_overrides_path(&self) -> String;
+  /// Files under the overrides path that must not be extracted, e.g. the manifest itself.
+  fn is_override_excluded(&self, _relative_path: &Path) -> bool {
+    false
+  }
 }
 
 type ManifestBox = Box<dyn ModpackManifest + Send + Sync>;
@@ -54,6 +61,25 @@ fn get_parsers() -> Vec<Parser> {
         b
       })
     }),
+    Box::new(|f| {
+      TechnicManifest::from_archive(f).map(|m| {
+        let b: ManifestBox = Box::new(m);
+        b
+      })
+    }),
+    Box::new(|f| {
+      ATLauncherInstance::from_archive(f).map(|m| {
+        let b: ManifestBox = Box::new(m);
+        b
+      })
+    }),
+    // heuristic fallback for archives without any manifest, keep it last
+    Box::new(|f| {
+      ServerPackManifest::from_archive(f).map(|m| {
+        let b: ManifestBox = Box::new(m);
+        b
+      })
+    }),
   ]
 }
 
@@ -109,40 +135,61 @@ pub async fn get_download_params(
   Err(InstanceError::ModpackManifestParseError.into())
 }
 
-pub fn extract_overrides(file: &File, instance_path: &Path) -> SJMCLResult<()> {
-  let get_overrides_path = |file| {
+/// List the override entries of the archive, as (archive index, path relative to the instance).
+pub fn list_overrides(file: &File) -> SJMCLResult<Vec<(usize, PathBuf)>> {
+  let get_manifest = |file| {
     for parser in get_parsers() {
       if let Ok(manifest) = parser(file) {
-        return Some(manifest.get_overrides_path());
+        return Some(manifest);
       }
     }
     None
   };
-  let overrides_path = get_overrides_path(file).ok_or(InstanceError::ModpackManifestParseError)?;
+  let manifest = get_manifest(file).ok_or(InstanceError::ModpackManifestParseError)?;
+  let overrides_path = manifest.get_overrides_path();
   let mut archive = ZipArchive::new(file)?;
+  let mut overrides = Vec::new();
   for i in 0..archive.len() {
-    let mut file = archive.by_index(i)?;
-    let path = file.mangled_name();
-    let outpath = if path.starts_with(format!("{}/", overrides_path)) {
-      // Remove "{overrides}/" prefix and join with instance path
-      let relative_path = path.strip_prefix(format!("{}/", overrides_path)).unwrap();
-      instance_path.join(relative_path)
-    } else {
+    let file = archive.by_index(i)?;
+    if !file.is_file() {
       continue;
-    };
+    }
+    let path = file.mangled_name();
+    // Remove "{overrides}/" prefix (none if empty)
+    if let Ok(relative_path) = path.strip_prefix(&overrides_path)
+      && !manifest.is_override_excluded(relative_path)
+    {
+      overrides.push((i, relative_path.to_path_buf()));
+    }
+  }
+  Ok(overrides)
+}
 
-    if file.is_file() {
-      // Create parent directories if they don't exist
-      if let Some(p) = outpath.parent()
-        && !p.exists()
-      {
-        fs::create_dir_all(p)?;
-      }
+/// Extract the given override entries (from `list_overrides`) into the instance.
+pub fn extract_override_entries(
+  file: &File,
+  instance_path: &Path,
+  entries: &[(usize, PathBuf)],
+) -> SJMCLResult<()> {
+  let mut archive = ZipArchive::new(file)?;
+  for (index, relative_path) in entries {
+    let mut file = archive.by_index(*index)?;
+    let outpath = instance_path.join(relative_path);
 
-      // Extract file
-      let mut outfile = File::create(&outpath)?;
-      std::io::copy(&mut file, &mut outfile)?;
+    // Create parent directories if they don't exist
+    if let Some(p) = outpath.parent()
+      && !p.exists()
+    {
+      fs::create_dir_all(p)?;
     }
+
+    // Extract file
+    let mut outfile = File::create(&outpath)?;
+    std::io::copy(&mut file, &mut outfile)?;
   }
   Ok(())
 }
+
+pub fn extract_overrides(file: &File, instance_path: &Path) -> SJMCLResult<()> {
+  extract_override_entries(file, instance_path, &list_overrides(file)?)
+}
@@ -1,5 +1,9 @@
+pub mod atlauncher;
 pub mod curseforge;
 pub mod export;
 pub mod import;
 pub mod modrinth;
 pub mod multimc;
+pub mod server_pack;
+pub mod technic;
+pub mod update;
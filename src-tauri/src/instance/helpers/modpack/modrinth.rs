@@ -13,15 +13,12 @@ use tauri::AppHandle;
 use tokio::sync::Semaphore;
 use zip::ZipArchive;
 
+use crate::instance::helpers::local_cache::fetch_remote_resource_by_local_cached;
 use crate::instance::helpers::modpack::export::{
   ExportModpackOptions, ModpackExportBundle, normalize_mod_loader_version,
 };
 use crate::instance::helpers::modpack::import::{ModpackManifest, ModpackMetaInfo};
 use crate::instance::models::misc::{Instance, InstanceError, ModLoader, ModLoaderType};
-use crate::resource::helpers::{
-  curseforge::fetch_remote_resource_by_local_curseforge,
-  modrinth::fetch_remote_resource_by_local_modrinth,
-};
 use crate::resource::models::OtherResourceSource;
 use crate::tasks::PTaskParam;
 use crate::tasks::download::DownloadParam;
@@ -220,15 +217,23 @@ async fn build_modrinth_remote_file(
 
   let mut downloads = Vec::new();
 
-  if let Ok(remote) =
-    fetch_remote_resource_by_local_modrinth(app, full.to_string_lossy().as_ref()).await
+  if let Ok(remote) = fetch_remote_resource_by_local_cached(
+    app,
+    full.to_string_lossy().as_ref(),
+    &OtherResourceSource::Modrinth,
+  )
+  .await
   {
     downloads.push(remote.download_url);
   }
 
   if !skip_curseforge
-    && let Ok(remote) =
-      fetch_remote_resource_by_local_curseforge(app, full.to_string_lossy().as_ref()).await
+    && let Ok(remote) = fetch_remote_resource_by_local_cached(
+      app,
+      full.to_string_lossy().as_ref(),
+      &OtherResourceSource::CurseForge,
+    )
+    .await
   {
     downloads.push(remote.download_url);
   }
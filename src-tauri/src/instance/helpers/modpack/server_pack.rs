@@ -0,0 +1,270 @@
+use async_trait::async_trait;
+use regex::Regex;
+use sjmcl_types::error::SJMCLResult;
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::sync::LazyLock;
+use tauri::AppHandle;
+use zip::ZipArchive;
+
+use crate::instance::helpers::client_jar::load_game_version_from_jar;
+use crate::instance::helpers::modpack::import::{ModpackManifest, ModpackMetaInfo};
+use crate::instance::models::misc::{InstanceError, ModLoader, ModLoaderType};
+use crate::resource::models::OtherResourceSource;
+use crate::tasks::PTaskParam;
+
+// ============================================================================
+// Loader and game version heuristics, shared with other manifest parsers
+// ============================================================================
+
+static INSTALLER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  // "forge-1.20.1-47.2.0-installer.jar", "neoforge-21.1.77-installer.jar"
+  Regex::new(r"^(neoforge|forge)-(.+)-installer\.jar$").expect("Invalid regex")
+});
+
+static FABRIC_SERVER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  // "fabric-server-mc.1.20.1-loader.0.15.11-launcher.1.0.1.jar"
+  Regex::new(r"^fabric-server-mc\.([^-]+)-loader\.([^-]+)-launcher").expect("Invalid regex")
+});
+
+// (maven group:artifact, loader type)
+const LOADER_LIBRARIES: [(&str, ModLoaderType); 5] = [
+  ("net.minecraftforge:forge", ModLoaderType::Forge),
+  ("net.neoforged:forge", ModLoaderType::NeoForge), // NeoForge for 1.20.1
+  ("net.neoforged:neoforge", ModLoaderType::NeoForge),
+  ("net.fabricmc:fabric-loader", ModLoaderType::Fabric),
+  ("org.quiltmc:quilt-loader", ModLoaderType::Quilt),
+];
+
+/// NeoForge versions encode the game version, e.g. "20.4.237" -> "1.20.4", "21.0.10" -> "1.21".
+fn neoforge_to_game_version(version: &str) -> Option<String> {
+  let mut parts = version.split('.');
+  let major = parts.next()?.parse::<u32>().ok()?;
+  let minor = parts.next()?.parse::<u32>().ok()?;
+  if major == 0 {
+    return None; // april fools snapshots
+  }
+  match minor {
+    0 => Some(format!("1.{}", major)),
+    _ => Some(format!("1.{}.{}", major, minor)),
+  }
+}
+
+/// Detect the loader from a library coordinate, e.g. `net.minecraftforge:forge:1.20.1-47.2.0`.
+/// Returns (loader type, loader version, game version if encoded).
+pub fn detect_loader_from_library(
+  coordinate: &str,
+) -> Option<(ModLoaderType, String, Option<String>)> {
+  let (group_artifact, version) = coordinate.rsplit_once(':')?;
+  let (_, loader_type) = LOADER_LIBRARIES
+    .iter()
+    .find(|(name, _)| *name == group_artifact)?;
+
+  match (loader_type, group_artifact) {
+    (ModLoaderType::Forge, _) | (ModLoaderType::NeoForge, "net.neoforged:forge") => {
+      // "1.20.1-47.2.0", or "1.7.10-10.13.4.1614-1.7.10" for legacy versions
+      let (game_version, loader_version) = version.split_once('-')?;
+      let loader_version = loader_version
+        .strip_suffix(&format!("-{}", game_version))
+        .unwrap_or(loader_version);
+      Some((
+        *loader_type,
+        loader_version.to_string(),
+        Some(game_version.to_string()),
+      ))
+    }
+    (ModLoaderType::NeoForge, _) => Some((
+      *loader_type,
+      version.to_string(),
+      neoforge_to_game_version(version),
+    )),
+    _ => Some((*loader_type, version.to_string(), None)),
+  }
+}
+
+/// Same as `detect_loader_from_library`, from a path like
+/// `libraries/net/minecraftforge/forge/1.20.1-47.2.0/forge-1.20.1-47.2.0.jar`.
+fn detect_loader_from_library_path(path: &str) -> Option<(ModLoaderType, String, Option<String>)> {
+  let rel = &path[path.find("libraries/")? + "libraries/".len()..];
+  let parts = rel.split('/').collect::<Vec<_>>();
+  if parts.len() < 4 {
+    return None;
+  }
+  let version = parts[parts.len() - 2];
+  let artifact = parts[parts.len() - 3];
+  let group = parts[..parts.len() - 3].join(".");
+  detect_loader_from_library(&format!("{}:{}:{}", group, artifact, version))
+}
+
+/// Detect the loader from a bundled installer or server launcher file name.
+fn detect_loader_from_file_name(name: &str) -> Option<(ModLoaderType, String, Option<String>)> {
+  if let Some(caps) = INSTALLER_REGEX.captures(name) {
+    let group_artifact = match &caps[1] {
+      "neoforge" => "net.neoforged:neoforge",
+      _ => "net.minecraftforge:forge",
+    };
+    return detect_loader_from_library(&format!("{}:{}", group_artifact, &caps[2]));
+  }
+  FABRIC_SERVER_REGEX.captures(name).map(|caps| {
+    (
+      ModLoaderType::Fabric,
+      caps[2].to_string(),
+      Some(caps[1].to_string()),
+    )
+  })
+}
+
+/// Read the game version from a client or server jar nested in the archive.
+pub fn load_game_version_from_nested_jar(
+  archive: &mut ZipArchive<&File>,
+  name: &str,
+) -> Option<String> {
+  let mut buffer = Vec::new();
+  archive.by_name(name).ok()?.read_to_end(&mut buffer).ok()?;
+  let mut jar = ZipArchive::new(Cursor::new(buffer)).ok()?;
+  load_game_version_from_jar(&mut jar)
+}
+
+// ============================================================================
+// Manifest-less packs (e.g. server packs)
+// ============================================================================
+
+// top-level folders of a server pack that are not part of the game directory
+const SERVER_ONLY_DIRS: [&str; 3] = ["libraries", "versions", ".fabric"];
+
+#[derive(Debug, Clone)]
+pub struct ServerPackManifest {
+  pub name: String,
+  pub base_path: String,
+  pub client_version: String,
+  pub mod_loader: Option<(ModLoaderType, String)>,
+}
+
+#[async_trait]
+impl ModpackManifest for ServerPackManifest {
+  fn from_archive(file: &File) -> SJMCLResult<Self> {
+    let mut archive = ZipArchive::new(file)?;
+    let names = archive
+      .file_names()
+      .map(|name| name.replace('\\', "/"))
+      .collect::<Vec<_>>();
+
+    // packs are often wrapped in a single top-level folder
+    let base_path = match names.first().and_then(|n| n.split_once('/')) {
+      Some((top, _)) if names.iter().all(|n| n.starts_with(&format!("{}/", top))) => {
+        format!("{}/", top)
+      }
+      _ => String::new(),
+    };
+    let rel_names = names
+      .iter()
+      .filter_map(|n| n.strip_prefix(&base_path))
+      .collect::<Vec<_>>();
+    if !rel_names.iter().any(|n| n.starts_with("mods/")) {
+      return Err(InstanceError::ModpackManifestParseError.into());
+    }
+
+    let mut mod_loader = None;
+    let mut game_version = None;
+    for name in &rel_names {
+      let detected = if name.contains('/') {
+        detect_loader_from_library_path(name)
+      } else {
+        detect_loader_from_file_name(name)
+      };
+      if let Some((loader_type, loader_version, mc_version)) = detected {
+        mod_loader.get_or_insert((loader_type, loader_version));
+        if game_version.is_none() {
+          game_version = mc_version;
+        }
+      }
+    }
+
+    // modern Forge server layout: libraries/net/minecraft/server/<version>-<mcp>/
+    if game_version.is_none() {
+      game_version = rel_names.iter().find_map(|name| {
+        let rest = name.strip_prefix("libraries/net/minecraft/server/")?;
+        let version = rest.split('/').next()?;
+        Some(version.split('-').next().unwrap_or(version).to_string())
+      });
+    }
+    // bundled vanilla client or server jar at the pack root
+    if game_version.is_none() {
+      let jars = rel_names
+        .iter()
+        .filter(|n| !n.contains('/') && n.ends_with(".jar") && !n.ends_with("-installer.jar"))
+        .map(|n| format!("{}{}", base_path, n))
+        .collect::<Vec<_>>();
+      game_version = jars
+        .iter()
+        .find_map(|jar| load_game_version_from_nested_jar(&mut archive, jar));
+    }
+
+    Ok(ServerPackManifest {
+      name: base_path.trim_end_matches('/').to_string(),
+      base_path,
+      client_version: game_version.ok_or(InstanceError::ModpackManifestParseError)?,
+      mod_loader,
+    })
+  }
+
+  async fn get_meta_info(&self, app: &AppHandle) -> SJMCLResult<ModpackMetaInfo> {
+    let client_version = self.get_client_version()?;
+    let mod_loader = if let Ok((loader_type, version)) = self.get_mod_loader_type_version() {
+      Some(
+        ModLoader {
+          loader_type,
+          version,
+          ..Default::default()
+        }
+        .with_branch(app, client_version.clone())
+        .await?,
+      )
+    } else {
+      None
+    };
+    Ok(ModpackMetaInfo {
+      name: self.name.clone(),
+      version: None,
+      description: None,
+      author: None,
+      modpack_source: OtherResourceSource::Unknown,
+      client_version,
+      mod_loader,
+    })
+  }
+
+  fn get_client_version(&self) -> SJMCLResult<String> {
+    Ok(self.client_version.clone())
+  }
+
+  fn get_mod_loader_type_version(&self) -> SJMCLResult<(ModLoaderType, String)> {
+    self
+      .mod_loader
+      .clone()
+      .ok_or(InstanceError::ModLoaderVersionParseError.into())
+  }
+
+  async fn get_download_params(
+    &self,
+    _app: &AppHandle,
+    _instance_path: &Path,
+  ) -> SJMCLResult<Vec<PTaskParam>> {
+    // everything is bundled in the archive
+    Ok(Vec::new())
+  }
+
+  fn get_overrides_path(&self) -> String {
+    self.base_path.clone()
+  }
+
+  fn is_override_excluded(&self, relative_path: &Path) -> bool {
+    // root files are server jars, scripts and server configs
+    relative_path.components().count() <= 1
+      || relative_path
+        .components()
+        .next()
+        .is_some_and(|c| SERVER_ONLY_DIRS.contains(&c.as_os_str().to_string_lossy().as_ref()))
+  }
+}
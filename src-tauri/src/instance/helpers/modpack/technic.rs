@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use sjmcl_types::error::SJMCLResult;
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use tauri::AppHandle;
+use zip::ZipArchive;
+
+use crate::instance::helpers::modpack::import::{ModpackManifest, ModpackMetaInfo};
+use crate::instance::helpers::modpack::server_pack::{
+  detect_loader_from_library, load_game_version_from_nested_jar,
+};
+use crate::instance::models::misc::{InstanceError, ModLoader, ModLoaderType};
+use crate::resource::models::OtherResourceSource;
+use crate::tasks::PTaskParam;
+
+// Technic packs are zipped game directories, with the loader profile in `bin/version.json`
+// or inside `bin/modpack.jar` (the loader jar itself), ref: HMCL TechnicModpack.
+
+#[derive(Debug, Clone)]
+pub struct TechnicManifest {
+  pub name: String,
+  pub client_version: String,
+  pub mod_loader: Option<(ModLoaderType, String)>,
+}
+
+fn read_version_json(archive: &mut ZipArchive<&File>) -> Option<Value> {
+  if let Ok(file) = archive.by_name("bin/version.json") {
+    return serde_json::from_reader(file).ok();
+  }
+  let mut buffer = Vec::new();
+  archive
+    .by_name("bin/modpack.jar")
+    .ok()?
+    .read_to_end(&mut buffer)
+    .ok()?;
+  let mut jar = ZipArchive::new(Cursor::new(buffer)).ok()?;
+  let file = jar.by_name("version.json").ok()?;
+  serde_json::from_reader(file).ok()
+}
+
+#[async_trait]
+impl ModpackManifest for TechnicManifest {
+  fn from_archive(file: &File) -> SJMCLResult<Self> {
+    let mut archive = ZipArchive::new(file)?;
+    if archive.by_name("bin/modpack.jar").is_err() && archive.by_name("bin/version.json").is_err() {
+      return Err(InstanceError::ModpackManifestParseError.into());
+    }
+    let version_json = read_version_json(&mut archive).unwrap_or_default();
+
+    let detected = version_json
+      .get("libraries")
+      .and_then(Value::as_array)
+      .into_iter()
+      .flatten()
+      .filter_map(|lib| lib.get("name").and_then(Value::as_str))
+      .find_map(detect_loader_from_library);
+
+    let client_version = version_json
+      .get("inheritsFrom")
+      .or(version_json.get("jar"))
+      .and_then(Value::as_str)
+      .map(str::to_string)
+      .or(detected.as_ref().and_then(|(_, _, v)| v.clone()))
+      .or_else(|| load_game_version_from_nested_jar(&mut archive, "bin/minecraft.jar"))
+      .ok_or(InstanceError::ModpackManifestParseError)?;
+
+    Ok(TechnicManifest {
+      name: version_json
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string(),
+      client_version,
+      mod_loader: detected.map(|(loader_type, version, _)| (loader_type, version)),
+    })
+  }
+
+  async fn get_meta_info(&self, app: &AppHandle) -> SJMCLResult<ModpackMetaInfo> {
+    let client_version = self.get_client_version()?;
+    let mod_loader = if let Ok((loader_type, version)) = self.get_mod_loader_type_version() {
+      Some(
+        ModLoader {
+          loader_type,
+          version,
+          ..Default::default()
+        }
+        .with_branch(app, client_version.clone())
+        .await?,
+      )
+    } else {
+      None
+    };
+    Ok(ModpackMetaInfo {
+      name: self.name.clone(),
+      version: None,
+      description: None,
+      author: None,
+      modpack_source: OtherResourceSource::Technic,
+      client_version,
+      mod_loader,
+    })
+  }
+
+  fn get_client_version(&self) -> SJMCLResult<String> {
+    Ok(self.client_version.clone())
+  }
+
+  fn get_mod_loader_type_version(&self) -> SJMCLResult<(ModLoaderType, String)> {
+    self
+      .mod_loader
+      .clone()
+      .ok_or(InstanceError::ModLoaderVersionParseError.into())
+  }
+
+  async fn get_download_params(
+    &self,
+    _app: &AppHandle,
+    _instance_path: &Path,
+  ) -> SJMCLResult<Vec<PTaskParam>> {
+    // Technic packs bundle all files
+    Ok(Vec::new())
+  }
+
+  fn get_overrides_path(&self) -> String {
+    String::new()
+  }
+
+  fn is_override_excluded(&self, relative_path: &Path) -> bool {
+    relative_path.starts_with("bin")
+  }
+}
@@ -0,0 +1,464 @@
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sjmcl_types::error::SJMCLResult;
+use sjmcl_types::storage::{load_json_async, save_json_async};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+use crate::instance::helpers::modpack::import::{extract_override_entries, list_overrides};
+use crate::tasks::PTaskParam;
+use crate::tasks::download::DownloadParam;
+
+// files owned by the installed modpack, used to tell user changes apart on update
+pub const MODPACK_RECORD_FILE_NAME: &str = "sjmclmodpack.json";
+// record of an update whose downloads are still running, see `finish_modpack_update`
+pub const MODPACK_PENDING_RECORD_FILE_NAME: &str = "sjmclmodpack.pending.json";
+// pristine copies of mergeable override files, the base of three-way merges
+pub const MODPACK_BASE_DIR_NAME: &str = ".sjmclmodpack";
+
+const DISABLED_SUFFIX: &str = ".disabled";
+const MERGEABLE_EXTENSIONS: [&str; 11] = [
+  "cfg",
+  "conf",
+  "ini",
+  "json",
+  "json5",
+  "properties",
+  "snbt",
+  "toml",
+  "txt",
+  "yaml",
+  "yml",
+];
+const MAX_MERGE_LINES: usize = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ModpackRecord {
+  pub version: Option<String>,
+  // path relative to the instance -> sha1, None if the manifest does not provide it
+  pub files: HashMap<String, Option<String>>,
+  // files dropped by the new pack, removed once the update downloads succeeded
+  pub pending_removals: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModpackUpdateReport {
+  pub added: Vec<String>,
+  pub replaced: Vec<String>,
+  pub removed: Vec<String>,
+  pub merged: Vec<String>,
+  pub kept: Vec<String>, // changed by the user and left untouched
+  pub new_mod_loader_version: Option<String>, // the loader itself is not updated in place
+}
+
+#[derive(Debug, Default)]
+pub struct ModpackUpdatePlan {
+  pub downloads: Vec<PTaskParam>,
+  pub extracts: Vec<(usize, PathBuf)>, // (archive index, path relative to the instance)
+  pub merged: Vec<(PathBuf, String)>,
+  pub record: ModpackRecord, // with the files to remove in `pending_removals`
+  pub report: ModpackUpdateReport,
+}
+
+enum ModpackFileSource {
+  Download(DownloadParam),
+  Override(usize),
+}
+
+fn to_relative_string(path: &Path) -> String {
+  path.to_string_lossy().replace('\\', "/")
+}
+
+fn sha1_of_reader<R: Read>(reader: &mut R) -> Option<String> {
+  let mut hasher = Sha1::new();
+  std::io::copy(reader, &mut hasher).ok()?;
+  Some(hex::encode(hasher.finalize()))
+}
+
+fn sha1_of_file(path: &Path) -> Option<String> {
+  sha1_of_reader(&mut File::open(path).ok()?)
+}
+
+fn same_sha1(a: Option<&str>, b: Option<&str>) -> bool {
+  matches!((a, b), (Some(a), Some(b)) if a.eq_ignore_ascii_case(b))
+}
+
+fn is_mergeable(rel: &str) -> bool {
+  Path::new(rel).extension().is_some_and(|ext| {
+    MERGEABLE_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str())
+  })
+}
+
+// the local file of a pack path, which may have been disabled by the user: (full path, relative path)
+fn resolve_local_file(instance_path: &Path, rel: &str) -> Option<(PathBuf, String)> {
+  [rel.to_string(), format!("{}{}", rel, DISABLED_SUFFIX)]
+    .into_iter()
+    .map(|rel| (instance_path.join(&rel), rel))
+    .find(|(full, _)| full.is_file())
+}
+
+fn read_archive_entry(archive: &mut ZipArchive<&File>, index: usize) -> Option<String> {
+  let mut content = String::new();
+  archive
+    .by_index(index)
+    .ok()?
+    .read_to_string(&mut content)
+    .ok()?;
+  Some(content)
+}
+
+// for each line of `a`, the index of the matched line of `b` in their longest common subsequence
+fn match_lines(a: &[&str], b: &[&str]) -> Vec<Option<usize>> {
+  let (n, m) = (a.len(), b.len());
+  let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+  for i in (0..n).rev() {
+    for j in (0..m).rev() {
+      lcs[i * (m + 1) + j] = if a[i] == b[j] {
+        lcs[(i + 1) * (m + 1) + j + 1] + 1
+      } else {
+        lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+      };
+    }
+  }
+
+  let mut matches = vec![None; n];
+  let (mut i, mut j) = (0, 0);
+  while i < n && j < m {
+    if a[i] == b[j] {
+      matches[i] = Some(j);
+      i += 1;
+      j += 1;
+    } else if lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1] {
+      i += 1;
+    } else {
+      j += 1;
+    }
+  }
+  matches
+}
+
+/// Line based three-way merge (diff3). Returns None if both sides changed the same region.
+pub fn merge_three_way(base: &str, ours: &str, theirs: &str) -> Option<String> {
+  let base = base.split_inclusive('\n').collect::<Vec<_>>();
+  let ours = ours.split_inclusive('\n').collect::<Vec<_>>();
+  let theirs = theirs.split_inclusive('\n').collect::<Vec<_>>();
+  if [base.len(), ours.len(), theirs.len()]
+    .iter()
+    .any(|len| *len > MAX_MERGE_LINES)
+  {
+    return None;
+  }
+  let ours_matches = match_lines(&base, &ours);
+  let theirs_matches = match_lines(&base, &theirs);
+
+  let mut merged = String::new();
+  let (mut b, mut o, mut t) = (0, 0, 0);
+  loop {
+    // next base line kept by both sides
+    let stable = (b..base.len()).find_map(|i| Some((i, ours_matches[i]?, theirs_matches[i]?)));
+    let (b_end, o_end, t_end) = stable.unwrap_or((base.len(), ours.len(), theirs.len()));
+    let (base_chunk, ours_chunk, theirs_chunk) =
+      (&base[b..b_end], &ours[o..o_end], &theirs[t..t_end]);
+
+    if ours_chunk == base_chunk {
+      merged.extend(theirs_chunk.iter().copied());
+    } else if theirs_chunk == base_chunk || theirs_chunk == ours_chunk {
+      merged.extend(ours_chunk.iter().copied());
+    } else {
+      return None;
+    }
+
+    let Some((b_end, o_end, t_end)) = stable else {
+      break;
+    };
+    merged.push_str(base[b_end]);
+    (b, o, t) = (b_end + 1, o_end + 1, t_end + 1);
+  }
+  Some(merged)
+}
+
+/// Record the files of a modpack archive as they are installed.
+pub fn build_modpack_record(
+  file: &File,
+  instance_path: &Path,
+  download_params: &[PTaskParam],
+  version: Option<String>,
+) -> SJMCLResult<ModpackRecord> {
+  let mut record = ModpackRecord {
+    version,
+    ..Default::default()
+  };
+  for param in download_params {
+    if let PTaskParam::Download(param) = param
+      && let Ok(rel) = param.dest.strip_prefix(instance_path)
+    {
+      record
+        .files
+        .insert(to_relative_string(rel), param.sha1.clone());
+    }
+  }
+
+  let mut archive = ZipArchive::new(file)?;
+  for (index, rel) in list_overrides(file)? {
+    let sha1 = sha1_of_reader(&mut archive.by_index(index)?);
+    record.files.insert(to_relative_string(&rel), sha1);
+  }
+  Ok(record)
+}
+
+/// Record the files of a freshly installed modpack and keep base copies of its mergeable overrides.
+pub async fn save_modpack_record(
+  file: &File,
+  instance_path: &Path,
+  download_params: &[PTaskParam],
+  version: Option<String>,
+) -> SJMCLResult<()> {
+  let record = build_modpack_record(file, instance_path, download_params, version)?;
+  save_modpack_base_files(file, instance_path, &list_overrides(file)?)?;
+
+  save_json_async(&record, &instance_path.join(MODPACK_RECORD_FILE_NAME)).await?;
+  Ok(())
+}
+
+pub fn save_modpack_base_files(
+  file: &File,
+  instance_path: &Path,
+  overrides: &[(usize, PathBuf)],
+) -> SJMCLResult<()> {
+  let base_dir = instance_path.join(MODPACK_BASE_DIR_NAME);
+  if base_dir.exists() {
+    fs::remove_dir_all(&base_dir)?;
+  }
+  let mergeable = overrides
+    .iter()
+    .filter(|(_, rel)| is_mergeable(&to_relative_string(rel)))
+    .cloned()
+    .collect::<Vec<_>>();
+  extract_override_entries(file, &base_dir, &mergeable)
+}
+
+/// Diff the installed pack files against a new version of the pack.
+/// Files the pack owned and the user did not touch follow the new pack, files changed or deleted
+/// by the user are kept that way, and changed config overrides are three-way merged where possible.
+/// Without a record of the installed files, mods and other binary files are taken as untouched
+/// and config files as changed by the user.
+pub fn plan_modpack_update(
+  file: &File,
+  instance_path: &Path,
+  old_record: Option<&ModpackRecord>,
+  download_params: Vec<PTaskParam>,
+  version: Option<String>,
+) -> SJMCLResult<ModpackUpdatePlan> {
+  let mut plan = ModpackUpdatePlan {
+    record: ModpackRecord {
+      version,
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let mut new_files = Vec::new();
+  for param in download_params {
+    if let PTaskParam::Download(param) = param
+      && let Ok(rel) = param.dest.strip_prefix(instance_path)
+    {
+      let rel = to_relative_string(rel);
+      let sha1 = param.sha1.clone();
+      new_files.push((rel, sha1, ModpackFileSource::Download(param)));
+    }
+  }
+  let mut archive = ZipArchive::new(file)?;
+  for (index, rel) in list_overrides(file)? {
+    let sha1 = sha1_of_reader(&mut archive.by_index(index)?);
+    new_files.push((
+      to_relative_string(&rel),
+      sha1,
+      ModpackFileSource::Override(index),
+    ));
+  }
+  new_files.sort_by(|a, b| a.0.cmp(&b.0));
+
+  // a file is untouched if it still has the hash recorded at install (or no hash was known)
+  let is_unmodified = |rel: &str, local_sha1: Option<&str>| match old_record {
+    Some(record) => match record.files.get(rel) {
+      Some(None) => true,
+      Some(Some(old_sha1)) => same_sha1(local_sha1, Some(old_sha1)),
+      None => false,
+    },
+    None => !is_mergeable(rel),
+  };
+
+  for (rel, new_sha1, source) in new_files {
+    plan.record.files.insert(rel.clone(), new_sha1.clone());
+    let old_sha1 = old_record.and_then(|r| r.files.get(&rel));
+
+    let (target_rel, local) = match resolve_local_file(instance_path, &rel) {
+      // installed with the pack and deleted by the user
+      None if old_sha1.is_some() => {
+        plan.report.kept.push(rel);
+        continue;
+      }
+      None => {
+        plan.report.added.push(rel.clone());
+        (rel.clone(), None)
+      }
+      Some((full, target_rel)) => {
+        let local_sha1 = sha1_of_file(&full);
+        if same_sha1(local_sha1.as_deref(), new_sha1.as_deref()) {
+          continue; // up to date
+        }
+        (target_rel, Some((full, local_sha1)))
+      }
+    };
+
+    if let Some((full, local_sha1)) = local {
+      if is_unmodified(&rel, local_sha1.as_deref()) {
+        plan.report.replaced.push(rel.clone());
+      } else {
+        // changed by the user, merge if the base is known
+        let base_path = instance_path.join(MODPACK_BASE_DIR_NAME).join(&rel);
+        let merged = match (&source, old_sha1.is_some() && is_mergeable(&rel)) {
+          (ModpackFileSource::Override(index), true) => fs::read_to_string(&base_path)
+            .ok()
+            .zip(fs::read_to_string(&full).ok())
+            .zip(read_archive_entry(&mut archive, *index))
+            .and_then(|((base, ours), theirs)| merge_three_way(&base, &ours, &theirs)),
+          _ => None,
+        };
+        match merged {
+          Some(merged) => {
+            plan.merged.push((full, merged));
+            plan.report.merged.push(rel);
+          }
+          None => plan.report.kept.push(rel),
+        }
+        continue;
+      }
+    }
+
+    match source {
+      ModpackFileSource::Download(mut param) => {
+        param.dest = instance_path.join(&target_rel);
+        plan.downloads.push(PTaskParam::Download(param));
+      }
+      ModpackFileSource::Override(index) => {
+        plan.extracts.push((index, PathBuf::from(target_rel)));
+      }
+    }
+  }
+
+  // files dropped by the new pack
+  if let Some(old_record) = old_record {
+    let mut dropped = old_record
+      .files
+      .keys()
+      .filter(|rel| !plan.record.files.contains_key(*rel))
+      .collect::<Vec<_>>();
+    dropped.sort();
+    for rel in dropped {
+      let Some((full, _)) = resolve_local_file(instance_path, rel) else {
+        continue;
+      };
+      if is_unmodified(rel, sha1_of_file(&full).as_deref()) {
+        let removal = full.strip_prefix(instance_path).unwrap_or(&full);
+        plan
+          .record
+          .pending_removals
+          .push(to_relative_string(removal));
+        plan.report.removed.push(rel.clone());
+      } else {
+        plan.report.kept.push(rel.clone());
+      }
+    }
+  }
+
+  Ok(plan)
+}
+
+/// Apply the local part of an update plan and keep its record as pending.
+/// Downloads are scheduled by the caller, which then calls `finish_modpack_update`.
+pub async fn apply_modpack_update(
+  file: &File,
+  instance_path: &Path,
+  plan: &ModpackUpdatePlan,
+) -> SJMCLResult<()> {
+  extract_override_entries(file, instance_path, &plan.extracts)?;
+  for (path, content) in &plan.merged {
+    fs::write(path, content)?;
+  }
+  save_modpack_base_files(file, instance_path, &list_overrides(file)?)?;
+  save_json_async(
+    &plan.record,
+    &instance_path.join(MODPACK_PENDING_RECORD_FILE_NAME),
+  )
+  .await?;
+  Ok(())
+}
+
+/// Remove the files dropped by the new pack and commit the pending record,
+/// returns the updated pack version.
+pub async fn finish_modpack_update(instance_path: &Path) -> SJMCLResult<Option<String>> {
+  let pending_path = instance_path.join(MODPACK_PENDING_RECORD_FILE_NAME);
+  let mut record = load_json_async::<ModpackRecord>(&pending_path).await?;
+  for rel in std::mem::take(&mut record.pending_removals) {
+    let path = instance_path.join(rel);
+    if path.is_file() {
+      fs::remove_file(path)?;
+    }
+  }
+
+  save_json_async(&record, &instance_path.join(MODPACK_RECORD_FILE_NAME)).await?;
+  fs::remove_file(pending_path)?;
+  Ok(record.version)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn merge_keeps_changes_from_both_sides() {
+    let base = "a\nb\nc\nd\ne\n";
+    let ours = "a\nB\nc\nd\ne\n";
+    let theirs = "a\nb\nc\nD\ne\n";
+    assert_eq!(
+      merge_three_way(base, ours, theirs),
+      Some("a\nB\nc\nD\ne\n".to_string())
+    );
+  }
+
+  #[test]
+  fn merge_conflicts_when_both_sides_change_the_same_line() {
+    assert_eq!(merge_three_way("a\nb\nc\n", "a\nX\nc\n", "a\nY\nc\n"), None);
+  }
+
+  #[test]
+  fn merge_accepts_identical_changes() {
+    assert_eq!(
+      merge_three_way("a\nb\nc\n", "a\nX\nc\n", "a\nX\nc\n"),
+      Some("a\nX\nc\n".to_string())
+    );
+  }
+
+  #[test]
+  fn merge_appends_lines_inserted_at_the_end() {
+    assert_eq!(
+      merge_three_way("a\nb\nc\nd\n", "a\nB\nc\nd\n", "a\nb\nc\nd\ne\n"),
+      Some("a\nB\nc\nd\ne\n".to_string())
+    );
+    assert_eq!(
+      merge_three_way("a\nb\nc\n", "a\nb\nc\nx\n", "a\nb\nc\n"),
+      Some("a\nb\nc\nx\n".to_string())
+    );
+  }
+
+  #[test]
+  fn merge_conflicts_when_both_sides_append_different_lines() {
+    assert_eq!(merge_three_way("a\nb\n", "a\nb\nx\n", "a\nb\ny\n"), None);
+  }
+}
@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 use image::imageops::FilterType;
+use regex::RegexBuilder;
+use serde_json::Value;
 use sjmcl_types::error::{SJMCLError, SJMCLResult};
+use std::collections::HashSet;
 use std::io::{Cursor, Read, Seek};
 use std::path::{Path, PathBuf};
 use zip::ZipArchive;
@@ -8,7 +11,9 @@ use zip::ZipArchive;
 use crate::instance::constants::COMPRESSED_ICON_SIZE;
 use crate::instance::helpers::mods::{fabric, forge, legacy_forge, liteloader, quilt};
 use crate::instance::models::misc::{LocalModInfo, ModLoaderType};
+use crate::utils::fs::get_files_with_regex;
 use crate::utils::image::ImageWrapper;
+use crate::utils::string::deserialize_lenient_json;
 
 pub fn compress_icon(wrapper: ImageWrapper) -> ImageWrapper {
   let resized_image = image::imageops::resize(
@@ -153,6 +158,143 @@ impl ModLoaderType {
   }
 }
 
+// nested jars deeper than this are not inspected
+const MAX_NESTED_JAR_DEPTH: usize = 3;
+
+fn read_zip_entry<R: Read + Seek>(jar: &mut ZipArchive<R>, name: &str) -> Option<Vec<u8>> {
+  let mut buffer = Vec::new();
+  jar.by_name(name).ok()?.read_to_end(&mut buffer).ok()?;
+  Some(buffer)
+}
+
+fn read_zip_json_entry<R: Read + Seek>(jar: &mut ZipArchive<R>, name: &str) -> Option<Value> {
+  let content = String::from_utf8(read_zip_entry(jar, name)?).ok()?;
+  deserialize_lenient_json(&content).ok()
+}
+
+/// Paths of the jars bundled in a mod jar, from fabric / quilt `jars` entries,
+/// the forge / neoforge jarjar metadata, and the conventional folders for undeclared ones.
+fn get_nested_jar_paths<R: Read + Seek>(jar: &mut ZipArchive<R>) -> Vec<String> {
+  let mut paths = Vec::new();
+  // fabric: {"jars": [{"file": "META-INF/jars/foo.jar"}]}
+  if let Some(meta) = read_zip_json_entry(jar, "fabric.mod.json") {
+    paths.extend(
+      meta
+        .get("jars")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("file").and_then(Value::as_str))
+        .map(str::to_string),
+    );
+  }
+  // quilt: {"quilt_loader": {"jars": ["META-INF/jars/foo.jar"]}}
+  if let Some(meta) = read_zip_json_entry(jar, "quilt.mod.json") {
+    paths.extend(
+      meta
+        .pointer("/quilt_loader/jars")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string),
+    );
+  }
+  // forge / neoforge: {"jars": [{"identifier": {...}, "version": {...}, "path": "META-INF/jarjar/foo.jar"}]}
+  if let Some(meta) = read_zip_json_entry(jar, "META-INF/jarjar/metadata.json") {
+    paths.extend(
+      meta
+        .get("jars")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("path").and_then(Value::as_str))
+        .map(str::to_string),
+    );
+  }
+  paths.extend(
+    jar
+      .file_names()
+      .filter(|name| {
+        (name.starts_with("META-INF/jars/") || name.starts_with("META-INF/jarjar/"))
+          && name.to_lowercase().ends_with(".jar")
+      })
+      .map(str::to_string),
+  );
+
+  let mut seen = HashSet::new();
+  paths.retain(|path| seen.insert(path.trim_start_matches('/').to_string()));
+  paths
+}
+
+fn parse_mod_info_with_nested(
+  jar: &mut ZipArchive<Cursor<Vec<u8>>>,
+  prior_loader_type: Option<ModLoaderType>,
+  depth: usize,
+) -> Option<LocalModInfo> {
+  let mut local_mod_info = prior_loader_type
+    .into_iter()
+    .chain(DEFAULT_MOD_LOADER_PRIORITY_LIST)
+    .find_map(|loader_type| loader_type.parse_mod_info_from_jar(jar))?;
+  if depth < MAX_NESTED_JAR_DEPTH {
+    local_mod_info.nested_mods = get_nested_mod_infos(jar, prior_loader_type, depth + 1);
+  }
+  Some(local_mod_info)
+}
+
+fn get_nested_mod_infos(
+  jar: &mut ZipArchive<Cursor<Vec<u8>>>,
+  prior_loader_type: Option<ModLoaderType>,
+  depth: usize,
+) -> Vec<LocalModInfo> {
+  let mut nested_mods = Vec::new();
+  for path in get_nested_jar_paths(jar) {
+    let Some(buffer) = read_zip_entry(jar, path.trim_start_matches('/')) else {
+      continue;
+    };
+    let Ok(mut nested_jar) = ZipArchive::new(Cursor::new(buffer)) else {
+      continue;
+    };
+    // plain libraries without mod metadata are skipped
+    if let Some(mut local_mod_info) =
+      parse_mod_info_with_nested(&mut nested_jar, prior_loader_type, depth)
+      && !local_mod_info.mod_id.is_empty()
+    {
+      local_mod_info.icon_src = ImageWrapper::default();
+      local_mod_info.file_name = Path::new(&path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or(path);
+      nested_mods.push(local_mod_info);
+    }
+  }
+  nested_mods
+}
+
+fn set_nested_mod_file_fields(local_mod_info: &mut LocalModInfo, path: &Path, enabled: bool) {
+  for nested in &mut local_mod_info.nested_mods {
+    nested.file_path = path.to_path_buf();
+    nested.enabled = enabled;
+    set_nested_mod_file_fields(nested, path, enabled);
+  }
+}
+
+/// Fill in the fields that depend on the mod file path rather than its content.
+pub fn set_mod_file_fields(local_mod_info: &mut LocalModInfo, path: &Path) {
+  let file_name = path
+    .file_name()
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or_default();
+  let normalized_file_name = file_name.strip_suffix(".disabled").unwrap_or(&file_name);
+  local_mod_info.file_name = Path::new(normalized_file_name)
+    .file_stem()
+    .map(|stem| stem.to_string_lossy().to_string())
+    .unwrap_or_else(|| normalized_file_name.to_string());
+  local_mod_info.file_path = path.to_path_buf();
+  local_mod_info.enabled = !file_name.ends_with(".disabled");
+  set_nested_mod_file_fields(local_mod_info, path, local_mod_info.enabled);
+}
+
 pub async fn get_mod_info_from_jar(
   path: &PathBuf,
   prior_loader_type: Option<ModLoaderType>,
@@ -162,25 +304,11 @@ pub async fn get_mod_info_from_jar(
     .file_name()
     .map(|name| name.to_string_lossy().to_string())
     .ok_or_else(|| SJMCLError(format!("invalid mod file path: {}", path.display())))?;
-  let normalized_file_name = file_name.strip_suffix(".disabled").unwrap_or(&file_name);
-  let file_stem = Path::new(normalized_file_name)
-    .file_stem()
-    .map(|stem| stem.to_string_lossy().to_string())
-    .unwrap_or_else(|| normalized_file_name.to_string());
-  let file_path = path.clone();
-  let enabled = !file_name.ends_with(".disabled");
   let mut jar = ZipArchive::new(file)?;
 
-  for loader_type in prior_loader_type
-    .into_iter()
-    .chain(DEFAULT_MOD_LOADER_PRIORITY_LIST)
-  {
-    if let Some(mut local_mod_info) = loader_type.parse_mod_info_from_jar(&mut jar) {
-      local_mod_info.enabled = enabled;
-      local_mod_info.file_name = file_stem.clone();
-      local_mod_info.file_path = file_path.clone();
-      return Ok(local_mod_info);
-    }
+  if let Some(mut local_mod_info) = parse_mod_info_with_nested(&mut jar, prior_loader_type, 0) {
+    set_mod_file_fields(&mut local_mod_info, path);
+    return Ok(local_mod_info);
   }
 
   Err(SJMCLError(format!(
@@ -271,3 +399,35 @@ pub fn check_potential_incompatibility(
     mod_info.potential_incompatibility = !supported_loaders.contains(&mod_info.loader_type);
   });
 }
+
+/// Load the metadata of all mod files in the folder, without icons translation or remote info.
+pub async fn load_local_mod_infos(
+  mods_dir: &Path,
+  prior_loader_type: Option<ModLoaderType>,
+) -> Vec<LocalModInfo> {
+  let valid_extensions = RegexBuilder::new(r"\.(jar|zip)(\.disabled)*$")
+    .case_insensitive(true)
+    .build()
+    .unwrap();
+  let mut mod_infos = Vec::new();
+  for path in get_files_with_regex(mods_dir, &valid_extensions).unwrap_or_default() {
+    if let Ok(info) = get_mod_info_from_jar(&path, prior_loader_type).await {
+      mod_infos.push(info);
+    }
+  }
+  mod_infos
+}
+
+/// Collect the lowercase mod ids of all enabled mods in the folder, including bundled ones.
+pub async fn get_local_mod_ids(
+  mods_dir: &Path,
+  prior_loader_type: Option<ModLoaderType>,
+) -> HashSet<String> {
+  load_local_mod_infos(mods_dir, prior_loader_type)
+    .await
+    .iter()
+    .filter(|info| info.enabled)
+    .flat_map(LocalModInfo::with_nested_mods)
+    .map(|info| info.mod_id.to_lowercase())
+    .collect()
+}
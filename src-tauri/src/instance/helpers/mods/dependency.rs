@@ -0,0 +1,419 @@
+// version predicates, ref:
+// https://wiki.fabricmc.net/documentation:fabric_mod_json_spec#versionrange
+// https://maven.apache.org/enforcer/enforcer-rules/versionRanges.html
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::instance::models::misc::{
+  LocalModInfo, ModDependencyIssue, ModDependencyIssueType, ModLoaderType, ModRelationType,
+};
+
+lazy_static! {
+  static ref MAVEN_RANGE_REGEX: Regex = Regex::new(r"[\[\(][^\]\)]*[\]\)]").unwrap();
+}
+
+/// Convert a fabric / quilt version field (string or array of strings) to a predicate string.
+/// Alternatives are joined with ` || `, `None` means any version.
+pub fn parse_version_predicate(value: &Value) -> Option<String> {
+  let predicates = match value {
+    Value::String(s) => vec![s.trim()],
+    Value::Array(arr) => arr
+      .iter()
+      .filter_map(Value::as_str)
+      .map(str::trim)
+      .collect(),
+    _ => return None,
+  };
+  if predicates.is_empty() || predicates.iter().any(|p| p.is_empty() || *p == "*") {
+    return None;
+  }
+  Some(predicates.join(" || "))
+}
+
+/// Forge treats a bare version as a lower bound ("soft requirement").
+pub fn normalize_maven_range(range: &str) -> Option<String> {
+  let range = range.trim();
+  if range.is_empty() || range == "*" {
+    None
+  } else if range.starts_with(['[', '(']) {
+    Some(range.to_string())
+  } else {
+    Some(format!("[{},)", range))
+  }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum VersionToken {
+  Number(u64),
+  Text(String),
+}
+
+fn tokenize_version(version: &str) -> Vec<VersionToken> {
+  // build metadata does not take part in precedence
+  let version = version.split('+').next().unwrap_or_default();
+  let mut tokens = Vec::new();
+  let mut current = String::new();
+  let mut is_digit = false;
+  for c in version.chars().chain(std::iter::once('.')) {
+    let split =
+      !c.is_ascii_alphanumeric() || (!current.is_empty() && c.is_ascii_digit() != is_digit);
+    if split && !current.is_empty() {
+      tokens.push(match is_digit {
+        true => VersionToken::Number(current.parse().unwrap_or(u64::MAX)),
+        false => VersionToken::Text(current.to_lowercase()),
+      });
+      current.clear();
+    }
+    if c.is_ascii_alphanumeric() {
+      is_digit = c.is_ascii_digit();
+      current.push(c);
+    }
+  }
+  tokens
+}
+
+/// Lenient version comparison for mod versions, which are often not strict semver.
+/// Numeric parts are compared numerically, and a trailing text part (e.g. `beta`) sorts before the release.
+pub fn compare_mod_versions(a: &str, b: &str) -> Ordering {
+  let (a, b) = (tokenize_version(a), tokenize_version(b));
+  for i in 0..a.len().max(b.len()) {
+    let ord = match (a.get(i), b.get(i)) {
+      (Some(VersionToken::Number(x)), Some(VersionToken::Number(y))) => x.cmp(y),
+      (Some(VersionToken::Text(x)), Some(VersionToken::Text(y))) => x.cmp(y),
+      (Some(VersionToken::Number(_)), Some(VersionToken::Text(_))) => Ordering::Greater,
+      (Some(VersionToken::Text(_)), Some(VersionToken::Number(_))) => Ordering::Less,
+      (Some(VersionToken::Number(_)), None) => Ordering::Greater,
+      (Some(VersionToken::Text(_)), None) => Ordering::Less,
+      (None, Some(VersionToken::Number(_))) => Ordering::Less,
+      (None, Some(VersionToken::Text(_))) => Ordering::Greater,
+      (None, None) => Ordering::Equal,
+    };
+    if ord != Ordering::Equal {
+      return ord;
+    }
+  }
+  Ordering::Equal
+}
+
+fn get_numeric_parts(version: &str) -> Vec<u64> {
+  version
+    .split(['.', '-', '+'])
+    .map_while(|part| part.parse().ok())
+    .collect()
+}
+
+fn bump_version(version: &str, index: usize) -> String {
+  let mut parts = get_numeric_parts(version);
+  parts.resize(index + 1, 0);
+  parts[index] += 1;
+  parts
+    .iter()
+    .map(u64::to_string)
+    .collect::<Vec<_>>()
+    .join(".")
+}
+
+fn matches_comparator(version: &str, comparator: &str) -> bool {
+  let cmp = |target: &str| compare_mod_versions(version, target);
+  if comparator == "*" {
+    return true;
+  }
+  if let Some(target) = comparator.strip_prefix(">=") {
+    return cmp(target).is_ge();
+  }
+  if let Some(target) = comparator.strip_prefix("<=") {
+    return cmp(target).is_le();
+  }
+  if let Some(target) = comparator.strip_prefix('>') {
+    return cmp(target).is_gt();
+  }
+  if let Some(target) = comparator.strip_prefix('<') {
+    return cmp(target).is_lt();
+  }
+  if let Some(target) = comparator.strip_prefix('~') {
+    // same minor version
+    return cmp(target).is_ge() && cmp(&bump_version(target, 1)).is_lt();
+  }
+  if let Some(target) = comparator.strip_prefix('^') {
+    // same major version
+    return cmp(target).is_ge() && cmp(&bump_version(target, 0)).is_lt();
+  }
+  let target = comparator.strip_prefix('=').unwrap_or(comparator);
+  if target
+    .split('.')
+    .any(|part| matches!(part, "x" | "X" | "*"))
+  {
+    let version_parts = version.split(['.', '-', '+']).collect::<Vec<_>>();
+    return target
+      .split('.')
+      .enumerate()
+      .all(|(i, part)| matches!(part, "x" | "X" | "*") || version_parts.get(i) == Some(&part));
+  }
+  cmp(target).is_eq()
+}
+
+fn matches_maven_range(version: &str, range: &str) -> bool {
+  let cmp = |target: &str| compare_mod_versions(version, target.trim());
+  MAVEN_RANGE_REGEX.find_iter(range).any(|m| {
+    let s = m.as_str();
+    let (lower_inclusive, upper_inclusive) = (s.starts_with('['), s.ends_with(']'));
+    let inner = &s[1..s.len() - 1];
+    match inner.split_once(',') {
+      None => cmp(inner).is_eq(),
+      Some((lower, upper)) => {
+        let lower_ok = lower.trim().is_empty()
+          || match lower_inclusive {
+            true => cmp(lower).is_ge(),
+            false => cmp(lower).is_gt(),
+          };
+        let upper_ok = upper.trim().is_empty()
+          || match upper_inclusive {
+            true => cmp(upper).is_le(),
+            false => cmp(upper).is_lt(),
+          };
+        lower_ok && upper_ok
+      }
+    }
+  })
+}
+
+/// Check a version against a fabric style predicate (space separated comparators, alternatives
+/// joined by ` || `) or a maven style range.
+pub fn version_matches_range(version: &str, range: &str) -> bool {
+  let range = range.trim();
+  if range.starts_with(['[', '(']) {
+    return matches_maven_range(version, range);
+  }
+  range.split("||").any(|alternative| {
+    alternative
+      .split_whitespace()
+      .all(|comparator| matches_comparator(version, comparator))
+  })
+}
+
+fn is_release_game_version(version: &str) -> bool {
+  !version.is_empty() && version.split('.').all(|part| part.parse::<u32>().is_ok())
+}
+
+fn get_builtin_mod_ids(loader_type: ModLoaderType) -> &'static [&'static str] {
+  match loader_type {
+    ModLoaderType::Fabric => &["fabricloader", "fabric-loader"],
+    ModLoaderType::Quilt => &["quilt_loader", "quilt-loader", "fabricloader"],
+    ModLoaderType::Forge | ModLoaderType::LegacyForge => &["forge", "fml", "javafml", "mcp"],
+    ModLoaderType::NeoForge => &["neoforge", "fml", "javafml"],
+    _ => &[],
+  }
+}
+
+// ids provided by the game, the loader or the runtime, never reported as missing.
+const IGNORED_MOD_IDS: &[&str] = &[
+  "minecraft",
+  "java",
+  "fabricloader",
+  "fabric-loader",
+  "quilt_loader",
+  "quilt-loader",
+  "forge",
+  "neoforge",
+  "fml",
+  "javafml",
+  "mcp",
+];
+
+/// Check the enabled mods against each other: duplicate ids, missing dependencies,
+/// unsatisfied version ranges and declared breaks / conflicts.
+/// Mods bundled as jar-in-jar take part as well, reported under the file that bundles them.
+pub fn check_local_mod_dependencies(
+  mod_infos: &[LocalModInfo],
+  game_version: &str,
+  loader_type: ModLoaderType,
+  loader_version: &str,
+) -> Vec<ModDependencyIssue> {
+  // (host file, mod, bundled) of every enabled mod, bundled ones included
+  let enabled_mods = mod_infos
+    .iter()
+    .filter(|m| m.enabled)
+    .flat_map(|host| {
+      host
+        .with_nested_mods()
+        .into_iter()
+        .enumerate()
+        .map(move |(i, m)| (host, m, i > 0))
+    })
+    .filter(|(_, m, _)| !m.mod_id.is_empty())
+    .collect::<Vec<_>>();
+  let mut issues = Vec::new();
+
+  // mod id -> (version, host file name, bundled) of every mod providing it
+  let mut available: HashMap<String, Vec<(String, String, bool)>> = HashMap::new();
+  for (host, mod_info, bundled) in &enabled_mods {
+    available
+      .entry(mod_info.mod_id.to_lowercase())
+      .or_default()
+      .push((mod_info.version.clone(), host.file_name.clone(), *bundled));
+  }
+
+  // a top-level file clashes with any other file supplying the same id, bundled or not,
+  // while the loaders silently pick one of several bundled copies
+  let mut duplicates = available
+    .iter()
+    .filter_map(|(mod_id, providers)| {
+      let mut file_names = providers
+        .iter()
+        .map(|(_, f, _)| f.clone())
+        .collect::<Vec<_>>();
+      file_names.sort();
+      file_names.dedup();
+      (file_names.len() > 1 && providers.iter().any(|(_, _, bundled)| !bundled))
+        .then_some((mod_id, file_names))
+    })
+    .collect::<Vec<_>>();
+  duplicates.sort_by(|a, b| a.0.cmp(b.0));
+  for (mod_id, file_names) in duplicates {
+    issues.push(ModDependencyIssue {
+      issue_type: ModDependencyIssueType::DuplicateModId,
+      mod_id: mod_id.clone(),
+      file_names,
+      target_mod_id: None,
+      version_range: None,
+      found_version: None,
+    });
+  }
+
+  for (host, mod_info, bundled) in &enabled_mods {
+    for alias in &mod_info.provides {
+      available.entry(alias.to_lowercase()).or_default().push((
+        mod_info.version.clone(),
+        host.file_name.clone(),
+        *bundled,
+      ));
+    }
+  }
+  if is_release_game_version(game_version) {
+    available.insert(
+      "minecraft".to_string(),
+      vec![(game_version.to_string(), String::new(), false)],
+    );
+  }
+  if !loader_version.is_empty() {
+    for id in get_builtin_mod_ids(loader_type) {
+      available.insert(
+        id.to_string(),
+        vec![(loader_version.to_string(), String::new(), false)],
+      );
+    }
+  }
+
+  for (host, mod_info, _) in &enabled_mods {
+    for relation in &mod_info.relations {
+      let target_id = relation.mod_id.to_lowercase();
+      if target_id == mod_info.mod_id.to_lowercase() || target_id == "java" {
+        continue;
+      }
+      let providers = available.get(&target_id);
+      let matched = providers.and_then(|providers| {
+        providers.iter().find(|(version, _, _)| {
+          relation
+            .version_range
+            .as_deref()
+            .is_none_or(|range| version_matches_range(version, range))
+        })
+      });
+
+      let issue_type = match (relation.relation_type, providers, matched) {
+        (ModRelationType::Required, None, _) if IGNORED_MOD_IDS.contains(&target_id.as_str()) => {
+          continue;
+        }
+        (ModRelationType::Required, None, _) => ModDependencyIssueType::MissingDependency,
+        (ModRelationType::Required | ModRelationType::Optional, Some(_), None) => {
+          ModDependencyIssueType::VersionMismatch
+        }
+        (ModRelationType::Breaks, _, Some(_)) => ModDependencyIssueType::Breaks,
+        (ModRelationType::Conflicts, _, Some(_)) => ModDependencyIssueType::Conflicts,
+        _ => continue,
+      };
+      issues.push(ModDependencyIssue {
+        issue_type,
+        mod_id: mod_info.mod_id.clone(),
+        file_names: vec![host.file_name.clone()],
+        target_mod_id: Some(relation.mod_id.clone()),
+        version_range: relation.version_range.clone(),
+        found_version: matched
+          .or_else(|| providers.and_then(|p| p.first()))
+          .map(|(version, _, _)| version.clone()),
+      });
+    }
+  }
+
+  issues
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bare_maven_version_is_a_lower_bound() {
+    assert_eq!(normalize_maven_range("1.2"), Some("[1.2,)".to_string()));
+    assert_eq!(
+      normalize_maven_range(" 47.1.0 "),
+      Some("[47.1.0,)".to_string())
+    );
+  }
+
+  #[test]
+  fn bracketed_maven_range_is_kept() {
+    assert_eq!(
+      normalize_maven_range(" [1.0,2.0) "),
+      Some("[1.0,2.0)".to_string())
+    );
+    assert_eq!(normalize_maven_range("(,3]"), Some("(,3]".to_string()));
+  }
+
+  #[test]
+  fn empty_or_wildcard_maven_range_matches_any_version() {
+    assert_eq!(normalize_maven_range(""), None);
+    assert_eq!(normalize_maven_range("*"), None);
+  }
+
+  #[test]
+  fn compare_numeric_parts_numerically() {
+    assert_eq!(compare_mod_versions("1.10.0", "1.9.2"), Ordering::Greater);
+    assert_eq!(compare_mod_versions("2.0.1", "2.0.1"), Ordering::Equal);
+  }
+
+  #[test]
+  fn compare_pre_release_before_release() {
+    assert_eq!(compare_mod_versions("1.0.0-beta", "1.0.0"), Ordering::Less);
+    assert_eq!(
+      compare_mod_versions("1.0.0-alpha", "1.0.0-Beta"),
+      Ordering::Less
+    );
+    assert_eq!(
+      compare_mod_versions("1.0.0-beta.2", "1.0.0-beta.10"),
+      Ordering::Less
+    );
+  }
+
+  #[test]
+  fn compare_ignores_build_metadata() {
+    assert_eq!(
+      compare_mod_versions("1.0.0+build.5", "1.0.0"),
+      Ordering::Equal
+    );
+  }
+
+  #[test]
+  fn maven_ranges_match_versions() {
+    assert!(version_matches_range("1.5", "[1.0,2.0)"));
+    assert!(!version_matches_range("2.0", "[1.0,2.0)"));
+    assert!(version_matches_range(
+      "3.1",
+      &normalize_maven_range("3.0").unwrap()
+    ));
+  }
+}
@@ -11,7 +11,10 @@ use tokio;
 use zip::ZipArchive;
 
 use crate::instance::helpers::mods::common::{LocalModMetadataParser, compress_icon};
-use crate::instance::models::misc::{LocalModInfo, ModLoaderType};
+use crate::instance::helpers::mods::dependency::parse_version_predicate;
+use crate::instance::models::misc::{
+  LocalModInfo, LocalModRelation, ModLoaderType, ModRelationType,
+};
 use crate::utils::image::{ImageWrapper, load_image_from_dir_async, load_image_from_jar};
 use crate::utils::string::deserialize_lenient_json;
 
@@ -25,16 +28,51 @@ pub struct FabricModMetadata {
   pub icon: Option<String>,
   pub authors: Option<Value>,
   pub contact: Option<HashMap<String, String>>,
+  #[serde(default)]
+  pub provides: Vec<String>,
+  #[serde(default)]
+  pub depends: HashMap<String, Value>,
+  #[serde(default)]
+  pub recommends: HashMap<String, Value>,
+  #[serde(default)]
+  pub suggests: HashMap<String, Value>,
+  #[serde(default)]
+  pub breaks: HashMap<String, Value>,
+  #[serde(default)]
+  pub conflicts: HashMap<String, Value>,
+  pub environment: Option<String>, // "*", "client" or "server"
 }
 
 impl From<FabricModMetadata> for LocalModInfo {
   fn from(meta: FabricModMetadata) -> Self {
+    let relation_sections = [
+      (&meta.depends, ModRelationType::Required),
+      (&meta.recommends, ModRelationType::Optional),
+      (&meta.suggests, ModRelationType::Optional),
+      (&meta.breaks, ModRelationType::Breaks),
+      (&meta.conflicts, ModRelationType::Conflicts),
+    ];
+    let relations = relation_sections
+      .into_iter()
+      .flat_map(|(section, relation_type)| {
+        section
+          .iter()
+          .map(move |(mod_id, predicate)| LocalModRelation {
+            mod_id: mod_id.clone(),
+            version_range: parse_version_predicate(predicate),
+            relation_type,
+          })
+      })
+      .collect();
     Self {
       mod_id: meta.id,
       name: meta.name.unwrap_or_default(),
       version: meta.version,
       description: meta.description.unwrap_or_default(),
       loader_type: ModLoaderType::Fabric,
+      relations,
+      provides: meta.provides,
+      client_only: meta.environment.as_deref() == Some("client"),
       ..Default::default()
     }
   }
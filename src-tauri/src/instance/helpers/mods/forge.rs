@@ -12,7 +12,10 @@ use zip::ZipArchive;
 use {java_properties, tokio, toml};
 
 use crate::instance::helpers::mods::common::{LocalModMetadataParser, compress_icon};
-use crate::instance::models::misc::{LocalModInfo, ModLoaderType};
+use crate::instance::helpers::mods::dependency::normalize_maven_range;
+use crate::instance::models::misc::{
+  LocalModInfo, LocalModRelation, ModLoaderType, ModRelationType,
+};
 use crate::utils::image::{ImageWrapper, load_image_from_dir_async, load_image_from_jar};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -23,8 +26,11 @@ pub struct ForgeModMetadata {
   pub loader_version: String,
   pub license: String,
   pub mods: Vec<ForgeModSubItem>,
+  // declaring mod id -> dependencies, kept loose so that a malformed entry only drops itself
+  pub dependencies: Value,
   // some non-standard mods write logo_file field in toml meta section.
   pub logo_file: Option<String>,
+  pub client_side_only: bool, // since Forge 49 and NeoForge 20.4
   // not in file, added by sjmcl
   pub valid_logo_file: Option<ImageWrapper>,
 }
@@ -43,8 +49,64 @@ pub struct ForgeModSubItem {
   pub logo_file: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ForgeModDependency {
+  pub mod_id: String,
+  pub mandatory: Option<bool>, // before neoforge 20.5
+  pub r#type: Option<String>,  // required, optional, incompatible or discouraged
+  pub version_range: Option<String>,
+  pub side: Option<String>,
+}
+
+impl ForgeModDependency {
+  fn relation_type(&self) -> ModRelationType {
+    match self.r#type.as_deref().map(str::to_lowercase).as_deref() {
+      Some("required") => ModRelationType::Required,
+      Some("optional") => ModRelationType::Optional,
+      Some("incompatible") => ModRelationType::Breaks,
+      Some("discouraged") => ModRelationType::Conflicts,
+      _ => match self.mandatory {
+        Some(false) => ModRelationType::Optional,
+        _ => ModRelationType::Required,
+      },
+    }
+  }
+}
+
 impl From<ForgeModMetadata> for LocalModInfo {
   fn from(meta: ForgeModMetadata) -> Self {
+    // other mods declared in the same file are treated as aliases of the first one
+    let mod_ids = meta
+      .mods
+      .iter()
+      .map(|m| m.mod_id.clone())
+      .collect::<Vec<_>>();
+    let dependencies = meta
+      .dependencies
+      .as_object()
+      .into_iter()
+      .flat_map(|deps| deps.values())
+      .filter_map(Value::as_array)
+      .flatten()
+      .filter_map(|entry| serde_json::from_value::<ForgeModDependency>(entry.clone()).ok());
+    let mut relations = Vec::<LocalModRelation>::new();
+    for dep in dependencies {
+      if mod_ids.contains(&dep.mod_id)
+        || dep
+          .side
+          .as_deref()
+          .is_some_and(|side| side.eq_ignore_ascii_case("server"))
+        || relations.iter().any(|r| r.mod_id == dep.mod_id)
+      {
+        continue;
+      }
+      relations.push(LocalModRelation {
+        mod_id: dep.mod_id.clone(),
+        version_range: dep.version_range.as_deref().and_then(normalize_maven_range),
+        relation_type: dep.relation_type(),
+      });
+    }
     let first_mod = meta.mods.into_iter().next().unwrap_or_default();
     Self {
       mod_id: first_mod.mod_id,
@@ -52,6 +114,9 @@ impl From<ForgeModMetadata> for LocalModInfo {
       version: first_mod.version.unwrap_or_default(),
       description: first_mod.description.unwrap_or_default(),
       loader_type: meta.loader_type,
+      relations,
+      provides: mod_ids.into_iter().skip(1).collect(),
+      client_only: meta.client_side_only,
       ..Default::default()
     }
   }
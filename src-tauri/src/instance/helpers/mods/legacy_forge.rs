@@ -10,7 +10,10 @@ use tokio;
 use zip::ZipArchive;
 
 use crate::instance::helpers::mods::common::{LocalModMetadataParser, compress_icon};
-use crate::instance::models::misc::{LocalModInfo, ModLoaderType};
+use crate::instance::helpers::mods::dependency::normalize_maven_range;
+use crate::instance::models::misc::{
+  LocalModInfo, LocalModRelation, ModLoaderType, ModRelationType,
+};
 use crate::utils::image::{ImageWrapper, load_image_from_dir_async, load_image_from_jar};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -26,16 +29,34 @@ pub struct LegacyForgeModMetadata {
   pub update_url: Option<String>,
   pub credits: Option<String>,
   pub author_list: Option<Vec<Value>>,
+  pub required_mods: Option<Vec<String>>, // `modid` or `modid@versionRange`
 }
 
 impl From<LegacyForgeModMetadata> for LocalModInfo {
   fn from(meta: LegacyForgeModMetadata) -> Self {
+    let relations = meta
+      .required_mods
+      .unwrap_or_default()
+      .iter()
+      .map(|required| {
+        let (mod_id, version_range) = match required.split_once('@') {
+          Some((mod_id, range)) => (mod_id, normalize_maven_range(range)),
+          None => (required.as_str(), None),
+        };
+        LocalModRelation {
+          mod_id: mod_id.trim().to_string(),
+          version_range,
+          relation_type: ModRelationType::Required,
+        }
+      })
+      .collect();
     Self {
       mod_id: meta.modid,
       name: meta.name.unwrap_or_default(),
       version: meta.version.unwrap_or_default(),
       description: meta.description.unwrap_or_default(),
       loader_type: ModLoaderType::Forge,
+      relations,
       ..Default::default()
     }
   }
@@ -1,4 +1,5 @@
 pub mod common;
+pub mod dependency;
 pub mod fabric;
 pub mod forge;
 pub mod legacy_forge;
@@ -10,15 +10,24 @@ use tokio;
 use zip::ZipArchive;
 
 use crate::instance::helpers::mods::common::{LocalModMetadataParser, compress_icon};
-use crate::instance::models::misc::{LocalModInfo, ModLoaderType};
+use crate::instance::helpers::mods::dependency::parse_version_predicate;
+use crate::instance::models::misc::{
+  LocalModInfo, LocalModRelation, ModLoaderType, ModRelationType,
+};
 use crate::utils::image::{ImageWrapper, load_image_from_dir_async, load_image_from_jar};
 
-#[expect(dead_code, reason = "reserved for future use")]
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 #[serde(default)]
 pub struct QuiltModMetadata {
   pub schema_version: i32,
   pub quilt_loader: QuiltLoader,
+  pub minecraft: QuiltMinecraft,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct QuiltMinecraft {
+  pub environment: Option<String>, // "*", "client" or "dedicated_server"
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -28,6 +37,12 @@ pub struct QuiltLoader {
   pub id: String,
   pub version: String,
   pub metadata: QuiltLoaderMetadata,
+  pub provides: Vec<Value>, // id string or object with `id` field
+  pub depends: Vec<Value>,  // id string or object with `id`, `versions` and `optional` fields
+  pub breaks: Vec<Value>,
+  // not in the loader section, copied from the `minecraft` section by sjmcl
+  #[serde(skip)]
+  pub environment: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -40,14 +55,59 @@ pub struct QuiltLoaderMetadata {
   pub contact: Option<Value>,
 }
 
+fn parse_quilt_relation(value: &Value, relation_type: ModRelationType) -> Option<LocalModRelation> {
+  match value {
+    Value::String(mod_id) => Some(LocalModRelation {
+      mod_id: mod_id.clone(),
+      version_range: None,
+      relation_type,
+    }),
+    Value::Object(obj) => {
+      let relation_type = match obj.get("optional").and_then(Value::as_bool) {
+        Some(true) if relation_type == ModRelationType::Required => ModRelationType::Optional,
+        _ => relation_type,
+      };
+      Some(LocalModRelation {
+        mod_id: obj.get("id")?.as_str()?.to_string(),
+        version_range: obj.get("versions").and_then(parse_version_predicate),
+        relation_type,
+      })
+    }
+    _ => None,
+  }
+}
+
 impl From<QuiltLoader> for LocalModInfo {
   fn from(meta: QuiltLoader) -> Self {
+    let relations = meta
+      .depends
+      .iter()
+      .filter_map(|v| parse_quilt_relation(v, ModRelationType::Required))
+      .chain(
+        meta
+          .breaks
+          .iter()
+          .filter_map(|v| parse_quilt_relation(v, ModRelationType::Breaks)),
+      )
+      .collect();
+    let provides = meta
+      .provides
+      .iter()
+      .filter_map(|v| match v {
+        Value::String(id) => Some(id.clone()),
+        Value::Object(obj) => obj.get("id").and_then(Value::as_str).map(str::to_string),
+        _ => None,
+      })
+      .collect();
     Self {
       mod_id: meta.id,
       name: meta.metadata.name.unwrap_or_default(),
       version: meta.version,
       description: meta.metadata.description.unwrap_or_default(),
       loader_type: ModLoaderType::Quilt,
+      relations,
+      provides,
+      client_only: meta.environment.as_deref() == Some("client"),
       ..Default::default()
     }
   }
@@ -63,21 +123,27 @@ impl LocalModMetadataParser for QuiltModMetadataParser {
   fn get_mod_metadata_from_jar<R: Read + Seek>(
     jar: &mut ZipArchive<R>,
   ) -> SJMCLResult<Self::Metadata> {
-    let meta: QuiltLoader = match jar.by_name("quilt.mod.json") {
+    let meta: QuiltModMetadata = match jar.by_name("quilt.mod.json") {
       Ok(val) => match serde_json::from_reader(val) {
         Ok(val) => val,
         Err(e) => return Err(SJMCLError::from(e)),
       },
       Err(e) => return Err(SJMCLError::from(e)),
     };
-    Ok(meta)
+    Ok(QuiltLoader {
+      environment: meta.minecraft.environment,
+      ..meta.quilt_loader
+    })
   }
 
   async fn get_mod_metadata_from_dir(dir_path: &Path) -> SJMCLResult<Self::Metadata> {
     let quilt_file_path = dir_path.join("quilt.mod.json");
     let content = tokio::fs::read_to_string(quilt_file_path).await?;
-    let meta: QuiltLoader = serde_json::from_str(&content)?;
-    Ok(meta)
+    let meta: QuiltModMetadata = serde_json::from_str(&content)?;
+    Ok(QuiltLoader {
+      environment: meta.minecraft.environment,
+      ..meta.quilt_loader
+    })
   }
 
   fn get_icon_from_jar<R: Read + Seek>(
@@ -1,6 +1,22 @@
+use serde::{Deserialize, Serialize};
+use sjmcl_types::error::SJMCLResult;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 
 use crate::instance::helpers::game_version::build_game_version_cmp_fn;
+use crate::instance::helpers::misc::get_instance_subdir_path_by_id;
+use crate::instance::models::misc::InstanceSubdirType;
+
+pub const OPTIONS_TXT_FILENAME: &str = "options.txt";
+
+// the game stores fov as a normalized float, 0.0 -> 70 degrees, 1.0 -> 110 degrees.
+const FOV_BASE_DEGREES: f64 = 70.0;
+const FOV_RANGE_DEGREES: f64 = 40.0;
+
+const KEYBIND_PREFIX: &str = "key_";
+const SOUND_CATEGORY_PREFIX: &str = "soundCategory_";
 
 /// Ordered by `min_version` ascending so that `.last()` on a filtered iterator
 /// picks the best (highest) matching version for a given locale.
@@ -39,4 +55,282 @@ pub fn get_minecraft_lang_tag(
     .map(|(_, _, tag)| *tag)
 }
 
-// TBD: struct of options.txt and more helpers?
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OptionsLine {
+  Entry { key: String, value: String },
+  Raw(String), // blank or malformed lines, kept as-is
+}
+
+/// In-memory representation of `options.txt`.
+/// Keeps the original line order and all keys unknown to SJMCL, so a load-save round trip
+/// only touches the entries that have been explicitly modified.
+/// Also used for `key=value` style files such as `optionsshaders.txt` and `iris.properties`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionsTxt {
+  lines: Vec<OptionsLine>,
+  separator: char,
+}
+
+impl Default for OptionsTxt {
+  fn default() -> Self {
+    Self {
+      lines: Vec::new(),
+      separator: ':',
+    }
+  }
+}
+
+impl OptionsTxt {
+  fn parse_with_separator(content: &str, separator: char) -> Self {
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+    let lines = content
+      .lines()
+      .map(|line| {
+        if separator == '=' && line.trim_start().starts_with(['#', '!']) {
+          return OptionsLine::Raw(line.to_string()); // comments in properties files
+        }
+        match line.split_once(separator) {
+          Some((key, value)) if !key.is_empty() => OptionsLine::Entry {
+            key: key.to_string(),
+            value: value.to_string(),
+          },
+          _ => OptionsLine::Raw(line.to_string()),
+        }
+      })
+      .collect();
+    Self { lines, separator }
+  }
+
+  pub fn parse(content: &str) -> Self {
+    Self::parse_with_separator(content, ':')
+  }
+
+  pub fn parse_properties(content: &str) -> Self {
+    Self::parse_with_separator(content, '=')
+  }
+
+  pub async fn load(path: &Path) -> SJMCLResult<Self> {
+    if !path.exists() {
+      return Ok(Self::default());
+    }
+    let content = tokio::fs::read_to_string(path).await?;
+    Ok(Self::parse(&content))
+  }
+
+  pub async fn load_properties(path: &Path) -> SJMCLResult<Self> {
+    if !path.exists() {
+      return Ok(Self::parse_properties(""));
+    }
+    let content = tokio::fs::read_to_string(path).await?;
+    Ok(Self::parse_properties(&content))
+  }
+
+  pub async fn save(&self, path: &Path) -> SJMCLResult<()> {
+    if let Some(parent) = path.parent() {
+      tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, self.to_string()).await?;
+    Ok(())
+  }
+
+  pub fn get(&self, key: &str) -> Option<&str> {
+    self.entries().find(|(k, _)| *k == key).map(|(_, v)| v)
+  }
+
+  /// Update the value in place if the key exists, otherwise append a new entry.
+  pub fn set(&mut self, key: &str, value: &str) {
+    for line in self.lines.iter_mut() {
+      if let OptionsLine::Entry { key: k, value: v } = line
+        && k == key
+      {
+        *v = value.to_string();
+        return;
+      }
+    }
+    self.lines.push(OptionsLine::Entry {
+      key: key.to_string(),
+      value: value.to_string(),
+    });
+  }
+
+  pub fn remove(&mut self, key: &str) {
+    self
+      .lines
+      .retain(|line| !matches!(line, OptionsLine::Entry { key: k, .. } if k == key));
+  }
+
+  pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+    self.lines.iter().filter_map(|line| match line {
+      OptionsLine::Entry { key, value } => Some((key.as_str(), value.as_str())),
+      OptionsLine::Raw(_) => None,
+    })
+  }
+
+  pub fn get_f64(&self, key: &str) -> Option<f64> {
+    self.get(key).and_then(|v| v.trim().parse().ok())
+  }
+
+  pub fn get_u32(&self, key: &str) -> Option<u32> {
+    self.get(key).and_then(|v| v.trim().parse().ok())
+  }
+
+  /// Read a JSON string array value, e.g. `resourcePacks:["vanilla","file/foo.zip"]`.
+  pub fn get_list(&self, key: &str) -> Vec<String> {
+    self
+      .get(key)
+      .and_then(|v| serde_json::from_str::<Vec<String>>(v.trim()).ok())
+      .unwrap_or_default()
+  }
+
+  pub fn set_list(&mut self, key: &str, values: &[String]) {
+    self.set(
+      key,
+      &serde_json::to_string(values).unwrap_or_else(|_| "[]".to_string()),
+    );
+  }
+
+  /// Copy entries whose key matches any of `key_filters` from `other` (all entries if empty).
+  pub fn merge_from(&mut self, other: &OptionsTxt, key_filters: &[String]) {
+    for (key, value) in other.entries() {
+      if key_filters.is_empty() || key_filters.iter().any(|f| match_key_filter(f, key)) {
+        self.set(key, value);
+      }
+    }
+  }
+}
+
+impl fmt::Display for OptionsTxt {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for line in &self.lines {
+      match line {
+        OptionsLine::Entry { key, value } => writeln!(f, "{}{}{}", key, self.separator, value)?,
+        OptionsLine::Raw(raw) => writeln!(f, "{}", raw)?,
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Key filters are exact option keys, or prefixes ending with `*` (e.g. `key_*`, `soundCategory_*`).
+pub fn match_key_filter(filter: &str, key: &str) -> bool {
+  match filter.strip_suffix('*') {
+    Some(prefix) => key.starts_with(prefix),
+    None => key == filter,
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameOptions {
+  pub lang: Option<String>,
+  pub fov: Option<f64>, // in degrees
+  pub render_distance: Option<u32>,
+  pub simulation_distance: Option<u32>,
+  pub gui_scale: Option<u32>,
+  pub max_fps: Option<u32>,
+  pub sound_levels: HashMap<String, f64>, // category (e.g. master, music) -> volume in [0, 1]
+  pub keybinds: HashMap<String, String>,  // key name (e.g. key.jump) -> key code
+}
+
+impl From<&OptionsTxt> for GameOptions {
+  fn from(options: &OptionsTxt) -> Self {
+    let mut sound_levels = HashMap::new();
+    let mut keybinds = HashMap::new();
+    for (key, value) in options.entries() {
+      if let Some(category) = key.strip_prefix(SOUND_CATEGORY_PREFIX) {
+        if let Ok(level) = value.trim().parse::<f64>() {
+          sound_levels.insert(category.to_string(), level);
+        }
+      } else if let Some(name) = key.strip_prefix(KEYBIND_PREFIX) {
+        keybinds.insert(name.to_string(), value.to_string());
+      }
+    }
+
+    Self {
+      lang: options.get("lang").map(str::to_string),
+      fov: options
+        .get_f64("fov")
+        .map(|v| FOV_BASE_DEGREES + v * FOV_RANGE_DEGREES),
+      render_distance: options.get_u32("renderDistance"),
+      simulation_distance: options.get_u32("simulationDistance"),
+      gui_scale: options.get_u32("guiScale"),
+      max_fps: options.get_u32("maxFps"),
+      sound_levels,
+      keybinds,
+    }
+  }
+}
+
+impl GameOptions {
+  /// Write all fields that are set into `options`, leaving other entries untouched.
+  pub fn apply_to(&self, options: &mut OptionsTxt) {
+    if let Some(lang) = &self.lang {
+      options.set("lang", lang);
+    }
+    if let Some(fov) = self.fov {
+      let normalized = (fov - FOV_BASE_DEGREES) / FOV_RANGE_DEGREES;
+      options.set("fov", &normalized.to_string());
+    }
+    let integer_fields = [
+      ("renderDistance", self.render_distance),
+      ("simulationDistance", self.simulation_distance),
+      ("guiScale", self.gui_scale),
+      ("maxFps", self.max_fps),
+    ];
+    for (key, value) in integer_fields {
+      if let Some(value) = value {
+        options.set(key, &value.to_string());
+      }
+    }
+    for (category, level) in &self.sound_levels {
+      options.set(
+        &format!("{}{}", SOUND_CATEGORY_PREFIX, category),
+        &level.clamp(0.0, 1.0).to_string(),
+      );
+    }
+    for (name, code) in &self.keybinds {
+      options.set(&format!("{}{}", KEYBIND_PREFIX, name), code);
+    }
+  }
+}
+
+pub fn get_options_txt_path_by_instance_id(
+  app: &AppHandle,
+  instance_id: &String,
+) -> Option<PathBuf> {
+  let game_root_dir = get_instance_subdir_path_by_id(app, instance_id, &InstanceSubdirType::Root)?;
+  Some(game_root_dir.join(OPTIONS_TXT_FILENAME))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_properties_reads_key_value_pairs() {
+    let props = OptionsTxt::parse_properties("shaderPack=BSL.zip\nenableShaders=true\n");
+    assert_eq!(props.get("shaderPack"), Some("BSL.zip"));
+    assert_eq!(props.get("enableShaders"), Some("true"));
+  }
+
+  #[test]
+  fn parse_properties_splits_at_the_first_separator() {
+    let props = OptionsTxt::parse_properties("url=https://example.com/?a=b\n");
+    assert_eq!(props.get("url"), Some("https://example.com/?a=b"));
+  }
+
+  #[test]
+  fn parse_properties_keeps_comments_and_blank_lines() {
+    let content = "\u{FEFF}#Iris config\n! legacy=comment\n\nshaderPack=BSL.zip\n";
+    let mut props = OptionsTxt::parse_properties(content);
+    assert_eq!(props.entries().count(), 1);
+    assert_eq!(props.get("! legacy"), None);
+
+    props.set("shaderPack", "Complementary.zip");
+    props.set("enableShaders", "true");
+    assert_eq!(
+      props.to_string(),
+      "#Iris config\n! legacy=comment\n\nshaderPack=Complementary.zip\nenableShaders=true\n"
+    );
+  }
+}
@@ -1,13 +1,68 @@
 use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sjmcl_types::error::{SJMCLError, SJMCLResult};
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use tauri::AppHandle;
 use zip::ZipArchive;
 
+use crate::instance::helpers::game_version::build_game_version_cmp_fn;
 use crate::utils::image::{load_image_from_dir_async, load_image_from_jar};
 
-pub fn load_resourcepack_from_zip(path: &PathBuf) -> SJMCLResult<(String, Option<RgbaImage>)> {
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PackMeta {
+  pub description: String,
+  pub pack_format: Option<u32>,
+  // inclusive range declared by `supported_formats` (since 23w31a)
+  pub supported_formats: Option<(u32, u32)>,
+}
+
+impl PackMeta {
+  pub fn supports_format(&self, format: u32) -> bool {
+    match (self.supported_formats, self.pack_format) {
+      (Some((min, max)), _) => (min..=max).contains(&format),
+      (None, Some(pack_format)) => pack_format == format,
+      (None, None) => true,
+    }
+  }
+}
+
+fn parse_pack_mcmeta(contents: &str) -> SJMCLResult<PackMeta> {
+  // Check for and remove the UTF-8 BOM if present
+  let contents = contents.strip_prefix('\u{FEFF}').unwrap_or(contents);
+  let json_result = serde_json::from_str::<Value>(contents)?;
+  let Some(pack_data) = json_result.get("pack") else {
+    return Ok(PackMeta::default());
+  };
+
+  let as_u32 = |v: &Value| v.as_u64().map(|n| n as u32);
+  // `supported_formats` may be a single number, a [min, max] array or a {min_inclusive, max_inclusive} object
+  let supported_formats = pack_data.get("supported_formats").and_then(|v| match v {
+    Value::Number(_) => as_u32(v).map(|n| (n, n)),
+    Value::Array(arr) if arr.len() == 2 => Some((as_u32(&arr[0])?, as_u32(&arr[1])?)),
+    Value::Object(obj) => Some((
+      as_u32(obj.get("min_inclusive")?)?,
+      as_u32(obj.get("max_inclusive")?)?,
+    )),
+    _ => None,
+  });
+
+  Ok(PackMeta {
+    // Safely extract `description`
+    description: pack_data
+      .get("description")
+      .and_then(Value::as_str)
+      .unwrap_or_default()
+      .to_string(),
+    pack_format: pack_data.get("pack_format").and_then(as_u32),
+    supported_formats,
+  })
+}
+
+pub fn load_resourcepack_from_zip(path: &PathBuf) -> SJMCLResult<(PackMeta, Option<RgbaImage>)> {
   let file = match fs::File::open(path) {
     Ok(val) => val,
     Err(e) => return Err(SJMCLError::from(e)),
@@ -16,69 +71,80 @@ pub fn load_resourcepack_from_zip(path: &PathBuf) -> SJMCLResult<(String, Option
     Ok(val) => val,
     Err(e) => return Err(SJMCLError::from(e)),
   };
-  let mut description = String::new();
 
-  if let Ok(mut file) = zip.by_name("pack.mcmeta") {
+  let meta = if let Ok(mut file) = zip.by_name("pack.mcmeta") {
     let mut contents = String::new();
     if let Err(e) = file.read_to_string(&mut contents) {
       return Err(SJMCLError::from(e));
-    } else {
-      // Check for and remove the UTF-8 BOM if present
-      if contents.starts_with('\u{FEFF}') {
-        contents = contents.strip_prefix('\u{FEFF}').unwrap().to_string();
-      }
-      match serde_json::from_str::<serde_json::Value>(&contents) {
-        Ok(json_result) => {
-          // Safely extract `description`
-          if let Some(pack_data) = json_result.get("pack")
-            && let Some(desc) = pack_data.get("description")
-            && let Some(desc_str) = desc.as_str()
-          {
-            description = desc_str.to_string();
-          }
-        }
-        Err(e) => {
-          return Err(SJMCLError::from(e));
-        }
-      }
     }
+    parse_pack_mcmeta(&contents)?
   } else {
     return Err(SJMCLError(format!(
       "pack.mcmeta not found in zip file '{}'",
       path.to_str().unwrap_or("")
     )));
-  }
+  };
 
   let icon_src = load_image_from_jar(&mut zip, "pack.png");
-  Ok((description, icon_src))
+  Ok((meta, icon_src))
 }
 
-pub async fn load_resourcepack_from_dir(path: &Path) -> SJMCLResult<(String, Option<RgbaImage>)> {
-  let mut description = String::new();
-
-  if let Ok(mut contents) = tokio::fs::read_to_string(path.join("pack.mcmeta")).await {
-    // Check for and remove the UTF-8 BOM if present
-    if contents.starts_with('\u{FEFF}') {
-      contents = contents.strip_prefix('\u{FEFF}').unwrap().to_string();
-    }
-    match serde_json::from_str::<serde_json::Value>(&contents) {
-      Ok(json_result) => {
-        // Safely extract `description`
-        if let Some(pack_data) = json_result.get("pack")
-          && let Some(desc) = pack_data.get("description")
-          && let Some(desc_str) = desc.as_str()
-        {
-          description = desc_str.to_string();
-        }
-      }
-      Err(e) => {
-        return Err(SJMCLError::from(e));
-      }
-    }
+pub async fn load_resourcepack_from_dir(path: &Path) -> SJMCLResult<(PackMeta, Option<RgbaImage>)> {
+  let meta = if let Ok(contents) = tokio::fs::read_to_string(path.join("pack.mcmeta")).await {
+    parse_pack_mcmeta(&contents)?
   } else {
     return Err(SJMCLError("pack.mcmeta not found in ''".to_string()));
-  }
+  };
 
   let icon_src = load_image_from_dir_async(&path.join("pack.png")).await;
-  Ok((description, icon_src))
+  Ok((meta, icon_src))
+}
+
+// (first game version, resource pack format), ordered by game version ascending.
+// ref: https://minecraft.wiki/w/Pack_format
+const RESOURCE_PACK_FORMATS: &[(&str, u32)] = &[
+  ("1.6.1", 1),
+  ("1.9", 2),
+  ("1.11", 3),
+  ("1.13", 4),
+  ("1.15", 5),
+  ("1.16.2", 6),
+  ("1.17", 7),
+  ("1.18", 8),
+  ("1.19", 9),
+  ("1.19.3", 12),
+  ("1.19.4", 13),
+  ("1.20", 15),
+  ("1.20.2", 18),
+  ("1.20.3", 22),
+  ("1.20.5", 32),
+  ("1.21", 34),
+  ("1.21.2", 42),
+  ("1.21.4", 46),
+  ("1.21.5", 55),
+  ("1.21.6", 63),
+  ("1.21.7", 64),
+  ("1.21.9", 69),
+];
+
+/// Get the resource pack format expected by the given game version (None if unknown or before 1.6.1).
+/// Snapshots are mapped to the format of the latest release before them.
+pub fn get_resource_pack_format(app: &AppHandle, game_version: &str) -> Option<u32> {
+  let cmp = build_game_version_cmp_fn(app);
+  RESOURCE_PACK_FORMATS
+    .iter()
+    .filter(|(min_ver, _)| cmp(game_version, min_ver).is_ge())
+    .next_back()
+    .map(|(_, format)| *format)
+}
+
+/// Name of a resource pack used by `resourcePacks` in options.txt.
+/// Since 1.13 (17w43a), packs from the resourcepacks folder are prefixed with `file/`.
+pub fn get_resource_pack_option_name(path: &Path, has_file_prefix: bool) -> Option<String> {
+  let file_name = path.file_name()?.to_string_lossy().to_string();
+  Some(if has_file_prefix {
+    format!("file/{}", file_name)
+  } else {
+    file_name
+  })
 }
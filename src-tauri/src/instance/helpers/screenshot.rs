@@ -0,0 +1,244 @@
+use chrono::{Local, NaiveDateTime, TimeZone};
+use image::ImageFormat;
+use image::imageops::FilterType;
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sjmcl_types::error::SJMCLResult;
+use sjmcl_types::storage::{load_json_async, save_json_async};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Semaphore;
+
+use crate::instance::models::misc::{InstanceError, ScreenshotInfo, ScreenshotSortBy};
+use crate::utils::fs::get_files_with_regex;
+use crate::utils::image::{ImageWrapper, load_image_from_dir};
+
+const SCREENSHOT_CACHE_DIR: &str = "screenshots";
+const THUMBNAIL_MAX_SIDE: u32 = 320;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScreenshotIndexEntry {
+  size: u64,
+  modified: u64,
+  captured_at: u64,
+  width: u32,
+  height: u32,
+  thumbnail_path: Option<PathBuf>,
+}
+
+/// Persistent index of one screenshots folder, keyed by file name.
+/// Entries are reused as long as the file size and modified time are unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct ScreenshotIndex {
+  entries: HashMap<String, ScreenshotIndexEntry>,
+}
+
+fn get_screenshot_cache_dir(app: &AppHandle, screenshots_dir: &Path) -> SJMCLResult<PathBuf> {
+  // folders may be shared by several instances (without version isolation), so key the cache by path.
+  let mut hasher = Sha1::new();
+  hasher.update(screenshots_dir.to_string_lossy().as_bytes());
+  let key = hex::encode(hasher.finalize());
+  Ok(
+    app
+      .path()
+      .resolve::<PathBuf>(SCREENSHOT_CACHE_DIR.into(), BaseDirectory::AppCache)?
+      .join(key),
+  )
+}
+
+/// Screenshots taken by the game are named like `2024-01-31_12.34.56.png` (optionally with `_N` suffix).
+fn parse_capture_time(file_stem: &str) -> Option<u64> {
+  let datetime_str = file_stem.get(..19)?;
+  let naive = NaiveDateTime::parse_from_str(datetime_str, "%Y-%m-%d_%H.%M.%S").ok()?;
+  let local = Local.from_local_datetime(&naive).earliest()?;
+  u64::try_from(local.timestamp()).ok()
+}
+
+fn build_thumbnail(path: &Path, thumbnail_path: &Path) -> Option<(u32, u32)> {
+  let image = ImageWrapper::from(load_image_from_dir(path)?);
+  let (width, height) = (image.image.width(), image.image.height());
+  let scale = THUMBNAIL_MAX_SIDE as f32 / width.max(height).max(1) as f32;
+  let thumbnail = if scale < 1.0 {
+    image.scaled(scale, FilterType::Triangle)
+  } else {
+    image
+  };
+  thumbnail
+    .image
+    .save_with_format(thumbnail_path, ImageFormat::Png)
+    .ok()?;
+  Some((width, height))
+}
+
+fn get_modified_secs(metadata: &std::fs::Metadata) -> u64 {
+  metadata
+    .modified()
+    .ok()
+    .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+    .map(|d| d.as_secs())
+    .unwrap_or_default()
+}
+
+fn list_screenshot_paths(screenshots_dir: &Path) -> SJMCLResult<Vec<PathBuf>> {
+  // The default screenshot format in Minecraft is PNG. For broader compatibility, JPG and JPEG formats are also included here.
+  let valid_extensions = RegexBuilder::new(r"\.(jpg|jpeg|png)$")
+    .case_insensitive(true)
+    .build()
+    .unwrap();
+  get_files_with_regex(screenshots_dir, &valid_extensions)
+}
+
+/// List the screenshots of a folder from the file names and metadata only, enough to sort them.
+/// Dimensions and thumbnails are filled in by `index_screenshots`.
+pub fn list_screenshots(
+  screenshots_dir: &Path,
+  instance_id: &str,
+) -> SJMCLResult<Vec<ScreenshotInfo>> {
+  if !screenshots_dir.exists() {
+    return Ok(Vec::new());
+  }
+  let mut screenshots = Vec::new();
+  for file_path in list_screenshot_paths(screenshots_dir)? {
+    let Ok(metadata) = file_path.metadata() else {
+      continue;
+    };
+    let file_name = file_path
+      .file_stem()
+      .map(|s| s.to_string_lossy().to_string())
+      .unwrap_or_default();
+    screenshots.push(ScreenshotInfo {
+      time: parse_capture_time(&file_name).unwrap_or(get_modified_secs(&metadata)),
+      file_name,
+      file_path,
+      size: metadata.len(),
+      instance_id: instance_id.to_string(),
+      ..Default::default()
+    });
+  }
+  Ok(screenshots)
+}
+
+/// Fill in the dimensions and cached thumbnails of screenshots listed from `screenshots_dir`.
+/// Only new or changed files are decoded, and entries of removed files are dropped with their thumbnails.
+pub async fn index_screenshots(
+  app: &AppHandle,
+  screenshots_dir: &Path,
+  screenshots: &mut [ScreenshotInfo],
+) -> SJMCLResult<()> {
+  let cache_dir = get_screenshot_cache_dir(app, screenshots_dir)?;
+  let index_path = cache_dir.join("index.json");
+  let mut index = load_json_async::<ScreenshotIndex>(&index_path)
+    .await
+    .unwrap_or_default();
+  tokio::fs::create_dir_all(&cache_dir).await?;
+
+  // entries of deleted or renamed files
+  let file_names = list_screenshot_paths(screenshots_dir)
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|path| path.file_name().map(|n| n.to_string_lossy().to_string()))
+    .collect::<HashSet<_>>();
+  let stale_names = index
+    .entries
+    .keys()
+    .filter(|name| !file_names.contains(*name))
+    .cloned()
+    .collect::<Vec<_>>();
+  for name in stale_names {
+    if let Some(thumbnail_path) = index.entries.remove(&name).and_then(|e| e.thumbnail_path) {
+      let _ = tokio::fs::remove_file(thumbnail_path).await;
+    }
+  }
+
+  let semaphore = Arc::new(Semaphore::new(
+    std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get),
+  ));
+  let mut tasks = Vec::new();
+  for screenshot in screenshots.iter() {
+    let path = screenshot.file_path.clone();
+    let Some(file_name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+      continue;
+    };
+    let Ok(metadata) = path.metadata() else {
+      continue;
+    };
+    let modified = get_modified_secs(&metadata);
+
+    if let Some(entry) = index.entries.get(&file_name)
+      && entry.size == metadata.len()
+      && entry.modified == modified
+      && entry.thumbnail_path.as_ref().is_none_or(|p| p.exists())
+    {
+      continue;
+    }
+
+    let thumbnail_path = cache_dir.join(format!("{}.png", file_name));
+    let size = metadata.len();
+    let captured_at = screenshot.time;
+    let permit = semaphore
+      .clone()
+      .acquire_owned()
+      .await
+      .map_err(|_| InstanceError::SemaphoreAcquireFailed)?;
+    tasks.push(tokio::task::spawn_blocking(move || {
+      let _permit = permit;
+      let dims = build_thumbnail(&path, &thumbnail_path);
+      let entry = ScreenshotIndexEntry {
+        size,
+        modified,
+        captured_at,
+        width: dims.map(|d| d.0).unwrap_or_default(),
+        height: dims.map(|d| d.1).unwrap_or_default(),
+        thumbnail_path: dims.map(|_| thumbnail_path),
+      };
+      (file_name, entry)
+    }));
+  }
+  for task in tasks {
+    if let Ok((file_name, entry)) = task.await {
+      index.entries.insert(file_name, entry);
+    }
+  }
+  save_json_async(&index, &index_path).await?;
+
+  for screenshot in screenshots.iter_mut() {
+    let Some(file_name) = screenshot.file_path.file_name() else {
+      continue;
+    };
+    if let Some(entry) = index.entries.get(file_name.to_string_lossy().as_ref()) {
+      screenshot.width = entry.width;
+      screenshot.height = entry.height;
+      screenshot.thumbnail_path = entry.thumbnail_path.clone();
+    }
+  }
+  Ok(())
+}
+
+/// Load the screenshots of a folder with metadata and cached thumbnails.
+pub async fn load_screenshots_with_index(
+  app: &AppHandle,
+  screenshots_dir: &Path,
+  instance_id: &str,
+) -> SJMCLResult<Vec<ScreenshotInfo>> {
+  let mut screenshots = list_screenshots(screenshots_dir, instance_id)?;
+  if !screenshots.is_empty() {
+    index_screenshots(app, screenshots_dir, &mut screenshots).await?;
+  }
+  Ok(screenshots)
+}
+
+pub fn sort_screenshots(screenshots: &mut [ScreenshotInfo], sort_by: ScreenshotSortBy) {
+  match sort_by {
+    ScreenshotSortBy::TimeDesc => screenshots.sort_by(|a, b| b.time.cmp(&a.time)),
+    ScreenshotSortBy::TimeAsc => screenshots.sort_by_key(|s| s.time),
+    ScreenshotSortBy::NameAsc => screenshots.sort_by(|a, b| a.file_name.cmp(&b.file_name)),
+    ScreenshotSortBy::NameDesc => screenshots.sort_by(|a, b| b.file_name.cmp(&a.file_name)),
+    ScreenshotSortBy::SizeDesc => screenshots.sort_by(|a, b| b.size.cmp(&a.size)),
+  }
+}
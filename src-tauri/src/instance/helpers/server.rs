@@ -1,7 +1,11 @@
 use mc_server_status::{McClient, McError, ServerData, ServerEdition, ServerInfo, ServerStatus};
-use quartz_nbt::io::Flavor;
+use quartz_nbt::io::{Flavor, read_nbt, write_nbt};
+use quartz_nbt::{NbtCompound, NbtList};
 use serde::{self, Deserialize, Serialize};
+use serde_json::Value;
 use sjmcl_types::error::SJMCLResult;
+use std::collections::HashSet;
+use std::io::Cursor;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tauri::AppHandle;
@@ -25,52 +29,116 @@ pub struct GameServerInfo {
   pub players_max: usize,
   pub online: bool, // if false, it may be offline in the query result or failed in the query.
   pub latency: Option<u64>, // ping latency in milliseconds
+  pub accept_textures: Option<bool>, // server resource pack policy, None means prompt
+  pub version: Option<ServerVersionInfo>,
+  pub is_version_compatible: Option<bool>, // None if it cannot be determined
+  pub motd: Vec<MotdSpan>,
+  pub players_sample: Vec<ServerPlayerSample>,
+  pub mods: Option<Vec<ServerModInfo>>, // None if the server is not a (reported) modded server
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerVersionInfo {
+  pub name: String,
+  pub protocol: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MotdSpan {
+  pub text: String,
+  pub color: Option<String>, // named color (e.g. "gold") or hex color (e.g. "#ff0000")
+  pub bold: bool,
+  pub italic: bool,
+  pub underlined: bool,
+  pub strikethrough: bool,
+  pub obfuscated: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerPlayerSample {
+  pub name: String,
+  pub id: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerModInfo {
+  pub mod_id: String,
+  pub version: String,
+  pub installed: bool,
+}
+
+/// A single entry of `servers.dat`.
+/// Tags not modelled here (e.g. `preventsChatReports`, or those written by newer game versions)
+/// are kept in `extra` so that the file round-trips without losing information.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct NbtServerInfo {
   pub ip: String,
   pub icon: Option<String>,
   pub name: String,
-  #[serde(default)]
   pub hidden: bool,
+  // None means the game will prompt the player (the tag is absent).
+  pub accept_textures: Option<bool>,
+  pub extra: NbtCompound,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-struct NbtServersInfo {
-  pub servers: Vec<NbtServerInfo>,
-}
+const NBT_SERVER_KNOWN_TAGS: [&str; 5] = ["ip", "icon", "name", "hidden", "acceptTextures"];
 
-impl From<NbtServerInfo> for GameServerInfo {
-  fn from(nbt: NbtServerInfo) -> Self {
+impl From<&NbtCompound> for NbtServerInfo {
+  fn from(compound: &NbtCompound) -> Self {
+    let mut extra = NbtCompound::new();
+    for (key, tag) in compound.inner().iter() {
+      if !NBT_SERVER_KNOWN_TAGS.contains(&key.as_str()) {
+        extra.insert(key.clone(), tag.clone());
+      }
+    }
     Self {
-      ip: nbt.ip,
-      name: nbt.name,
-      icon_src: nbt.icon.unwrap_or_default(),
-      hidden: nbt.hidden,
-      ..Default::default()
+      ip: compound
+        .get::<_, &str>("ip")
+        .map(str::to_string)
+        .unwrap_or_default(),
+      icon: compound.get::<_, &str>("icon").map(str::to_string).ok(),
+      name: compound
+        .get::<_, &str>("name")
+        .map(str::to_string)
+        .unwrap_or_default(),
+      hidden: compound.get::<_, bool>("hidden").unwrap_or(false),
+      accept_textures: compound.get::<_, bool>("acceptTextures").ok(),
+      extra,
     }
   }
 }
 
-impl From<GameServerInfo> for NbtServerInfo {
-  fn from(server: GameServerInfo) -> Self {
-    Self {
-      ip: server.ip,
-      icon: (!server.icon_src.is_empty()).then_some(server.icon_src),
-      name: server.name,
-      hidden: server.hidden,
+impl From<&NbtServerInfo> for NbtCompound {
+  fn from(server: &NbtServerInfo) -> Self {
+    let mut compound = server.extra.clone();
+    compound.insert("ip", server.ip.as_str());
+    compound.insert("name", server.name.as_str());
+    if let Some(icon) = &server.icon {
+      compound.insert("icon", icon.as_str());
+    }
+    if server.hidden {
+      compound.insert("hidden", true);
+    }
+    if let Some(accept_textures) = server.accept_textures {
+      compound.insert("acceptTextures", accept_textures);
     }
+    compound
   }
 }
 
-impl From<&GameServerInfo> for NbtServerInfo {
-  fn from(server: &GameServerInfo) -> Self {
+impl From<NbtServerInfo> for GameServerInfo {
+  fn from(nbt: NbtServerInfo) -> Self {
     Self {
-      ip: server.ip.clone(),
-      icon: (!server.icon_src.is_empty()).then_some(server.icon_src.clone()),
-      name: server.name.clone(),
-      hidden: server.hidden,
+      ip: nbt.ip,
+      name: nbt.name,
+      icon_src: nbt.icon.unwrap_or_default(),
+      hidden: nbt.hidden,
+      accept_textures: nbt.accept_textures,
+      ..Default::default()
     }
   }
 }
@@ -83,32 +151,75 @@ pub fn get_servers_nbt_path_by_instance_id(
   Some(game_root_dir.join(SERVERS_DAT_FILENAME))
 }
 
-pub async fn load_servers_info_from_nbt(path: &Path) -> SJMCLResult<Vec<GameServerInfo>> {
+async fn load_servers_nbt_root(path: &Path) -> SJMCLResult<(NbtCompound, String)> {
   if !path.exists() {
-    return Ok(Vec::new());
+    return Ok((NbtCompound::new(), String::new()));
   }
   let bytes = tokio::fs::read(path).await?;
-  let (servers_info, _snbt) =
-    quartz_nbt::serde::deserialize::<NbtServersInfo>(&bytes, Flavor::Uncompressed)?;
-  let game_server_list = servers_info
-    .servers
-    .into_iter()
-    .map(|nbt| nbt.into())
-    .collect();
-
-  Ok(game_server_list)
+  Ok(read_nbt(&mut Cursor::new(bytes), Flavor::Uncompressed)?)
 }
 
-pub async fn save_servers_to_nbt(path: &Path, servers: &[GameServerInfo]) -> SJMCLResult<()> {
-  let servers_info = NbtServersInfo {
-    servers: servers.iter().map(NbtServerInfo::from).collect(),
+/// Load the raw server entries (with unknown tags preserved) from `servers.dat`.
+pub async fn load_servers_from_nbt(path: &Path) -> SJMCLResult<Vec<NbtServerInfo>> {
+  let (root, _) = load_servers_nbt_root(path).await?;
+  let Ok(servers) = root.get::<_, &NbtList>("servers") else {
+    return Ok(Vec::new());
   };
-  let bytes = quartz_nbt::serde::serialize(&servers_info, None, Flavor::Uncompressed)?;
+
+  Ok(
+    servers
+      .iter_map::<&NbtCompound>()
+      .filter_map(Result::ok)
+      .map(NbtServerInfo::from)
+      .collect(),
+  )
+}
+
+pub async fn load_servers_info_from_nbt(path: &Path) -> SJMCLResult<Vec<GameServerInfo>> {
+  Ok(
+    load_servers_from_nbt(path)
+      .await?
+      .into_iter()
+      .map(GameServerInfo::from)
+      .collect(),
+  )
+}
+
+/// Save server entries to `servers.dat`, keeping other tags of the root compound untouched.
+pub async fn save_servers_to_nbt(path: &Path, servers: &[NbtServerInfo]) -> SJMCLResult<()> {
+  let (mut root, root_name) = load_servers_nbt_root(path).await?;
+  let server_list = servers
+    .iter()
+    .map(NbtCompound::from)
+    .collect::<Vec<NbtCompound>>();
+  root.insert("servers", NbtList::from(server_list));
+
+  let mut bytes = Vec::new();
+  write_nbt(
+    &mut bytes,
+    Some(root_name.as_str()),
+    &root,
+    Flavor::Uncompressed,
+  )?;
   tokio::fs::write(path, bytes).await?;
 
   Ok(())
 }
 
+/// Reorder `servers` to follow `ordered_addrs`.
+/// Servers not mentioned (e.g. hidden ones) keep their relative order and are placed at the end.
+pub fn reorder_servers(
+  servers: Vec<NbtServerInfo>,
+  ordered_addrs: &[String],
+) -> Vec<NbtServerInfo> {
+  let (mut ordered, rest): (Vec<_>, Vec<_>) = servers
+    .into_iter()
+    .partition(|server| ordered_addrs.contains(&server.ip));
+  ordered.sort_by_key(|server| ordered_addrs.iter().position(|addr| *addr == server.ip));
+  ordered.extend(rest);
+  ordered
+}
+
 /// Query multiple servers online status in parallel.
 pub async fn query_servers_online(
   mut servers: Vec<GameServerInfo>,
@@ -152,6 +263,7 @@ pub async fn query_servers_online(
         server.players_online = sv.players.online as usize;
         server.players_max = sv.players.max as usize;
         server.description = sv.description.clone();
+        enrich_server_info_from_status(server, &sv.raw_data);
 
         if let Some(favicon) = sv.favicon {
           server.icon_src = favicon;
@@ -162,3 +274,237 @@ pub async fn query_servers_online(
 
   Ok(servers)
 }
+
+fn enrich_server_info_from_status(server: &mut GameServerInfo, raw: &Value) {
+  if let Some(version) = raw.get("version") {
+    server.version = Some(ServerVersionInfo {
+      name: version
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string(),
+      protocol: version
+        .get("protocol")
+        .and_then(Value::as_i64)
+        .unwrap_or(-1),
+    });
+  }
+
+  server.motd = match raw.get("description") {
+    Some(description) => parse_chat_component(description),
+    None => parse_legacy_text(&server.description, &MotdSpan::default()),
+  };
+
+  server.players_sample = raw
+    .pointer("/players/sample")
+    .and_then(Value::as_array)
+    .map(|sample| {
+      sample
+        .iter()
+        .map(|player| ServerPlayerSample {
+          name: player
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+          id: player
+            .get("id")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+
+  server.mods = parse_server_mod_list(raw);
+}
+
+// Forge (1.13+, also used by NeoForge 1.20.1) reports `forgeData`, while Forge 1.7 - 1.12 reports `modinfo`.
+// ref: https://wiki.vg/Server_List_Ping#Response
+// NOTE: the compressed `forgeData.d` field (Forge 1.18+ with large mod lists) is not decoded here.
+fn parse_server_mod_list(raw: &Value) -> Option<Vec<ServerModInfo>> {
+  let (mods, id_key, version_key) = if let Some(forge_data) = raw.get("forgeData") {
+    (forge_data.get("mods")?.as_array()?, "modId", "modmarker")
+  } else if let Some(mod_info) = raw.get("modinfo") {
+    (mod_info.get("modList")?.as_array()?, "modid", "version")
+  } else {
+    return None;
+  };
+
+  Some(
+    mods
+      .iter()
+      .filter_map(|m| {
+        Some(ServerModInfo {
+          mod_id: m.get(id_key)?.as_str()?.to_string(),
+          version: m
+            .get(version_key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+          installed: false,
+        })
+      })
+      .collect(),
+  )
+}
+
+const LEGACY_COLOR_CODES: [(char, &str); 16] = [
+  ('0', "black"),
+  ('1', "dark_blue"),
+  ('2', "dark_green"),
+  ('3', "dark_aqua"),
+  ('4', "dark_red"),
+  ('5', "dark_purple"),
+  ('6', "gold"),
+  ('7', "gray"),
+  ('8', "dark_gray"),
+  ('9', "blue"),
+  ('a', "green"),
+  ('b', "aqua"),
+  ('c', "red"),
+  ('d', "light_purple"),
+  ('e', "yellow"),
+  ('f', "white"),
+];
+
+/// Split text with legacy `§` formatting codes into styled spans, starting from `base` style.
+pub fn parse_legacy_text(text: &str, base: &MotdSpan) -> Vec<MotdSpan> {
+  let mut spans = Vec::new();
+  let mut current = MotdSpan {
+    text: String::new(),
+    ..base.clone()
+  };
+  let mut chars = text.chars();
+
+  while let Some(ch) = chars.next() {
+    if ch != '§' {
+      current.text.push(ch);
+      continue;
+    }
+    let Some(code) = chars.next().map(|c| c.to_ascii_lowercase()) else {
+      break;
+    };
+    if !current.text.is_empty() {
+      spans.push(current.clone());
+      current.text.clear();
+    }
+    if let Some((_, color)) = LEGACY_COLOR_CODES.iter().find(|(c, _)| *c == code) {
+      // a color code also resets all formatting in legacy text
+      current = MotdSpan {
+        color: Some(color.to_string()),
+        ..Default::default()
+      };
+      continue;
+    }
+    match code {
+      'k' => current.obfuscated = true,
+      'l' => current.bold = true,
+      'm' => current.strikethrough = true,
+      'n' => current.underlined = true,
+      'o' => current.italic = true,
+      'r' => {
+        current = MotdSpan {
+          text: String::new(),
+          ..base.clone()
+        }
+      }
+      _ => {}
+    }
+  }
+  if !current.text.is_empty() {
+    spans.push(current);
+  }
+  spans
+}
+
+/// Flatten a JSON text component (string, object or array) into styled spans.
+pub fn parse_chat_component(component: &Value) -> Vec<MotdSpan> {
+  fn walk(component: &Value, parent: &MotdSpan, spans: &mut Vec<MotdSpan>) {
+    match component {
+      Value::String(text) => spans.extend(parse_legacy_text(text, parent)),
+      Value::Array(items) => items.iter().for_each(|item| walk(item, parent, spans)),
+      Value::Object(obj) => {
+        let flag =
+          |key: &str, inherited: bool| obj.get(key).and_then(Value::as_bool).unwrap_or(inherited);
+        let style = MotdSpan {
+          text: String::new(),
+          color: obj
+            .get("color")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| parent.color.clone()),
+          bold: flag("bold", parent.bold),
+          italic: flag("italic", parent.italic),
+          underlined: flag("underlined", parent.underlined),
+          strikethrough: flag("strikethrough", parent.strikethrough),
+          obfuscated: flag("obfuscated", parent.obfuscated),
+        };
+        // translatable components are not resolved, show the raw key instead
+        let text = obj
+          .get("text")
+          .or_else(|| obj.get("translate"))
+          .and_then(Value::as_str)
+          .unwrap_or_default();
+        spans.extend(parse_legacy_text(text, &style));
+        if let Some(extra) = obj.get("extra") {
+          walk(extra, &style, spans);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  let mut spans = Vec::new();
+  walk(component, &MotdSpan::default(), &mut spans);
+  spans
+}
+
+// mod ids reported by modded servers which are provided by the loader itself
+const SERVER_BUILTIN_MOD_IDS: [&str; 5] = ["minecraft", "mcp", "fml", "forge", "neoforge"];
+
+/// Compare the queried server version and mod list against the local instance.
+/// `client_protocol` is read from the client jar, and game version names are used as fallback.
+pub fn check_servers_compatibility(
+  servers: &mut [GameServerInfo],
+  game_version: &str,
+  client_protocol: Option<i64>,
+  local_mod_ids: &HashSet<String>,
+) {
+  for server in servers.iter_mut() {
+    server.is_version_compatible =
+      server
+        .version
+        .as_ref()
+        .and_then(|version| match client_protocol {
+          Some(protocol) if version.protocol >= 0 => Some(protocol == version.protocol),
+          _ => match_version_name(&version.name, game_version),
+        });
+
+    if let Some(mods) = server.mods.as_mut() {
+      for m in mods.iter_mut() {
+        let mod_id = m.mod_id.to_lowercase();
+        m.installed =
+          SERVER_BUILTIN_MOD_IDS.contains(&mod_id.as_str()) || local_mod_ids.contains(&mod_id);
+      }
+    }
+  }
+}
+
+// Version names may look like "1.20.1", "Paper 1.20.1" or "Velocity 3.3.0 (1.7.2-1.21)".
+fn match_version_name(version_name: &str, game_version: &str) -> Option<bool> {
+  let candidates: Vec<&str> = version_name
+    .split(|c: char| c.is_whitespace() || matches!(c, '-' | '(' | ')' | ','))
+    .filter(|token| token.contains('.') && token.starts_with(|c: char| c.is_ascii_digit()))
+    .collect();
+  if candidates.is_empty() {
+    return None;
+  }
+  Some(candidates.iter().any(|token| {
+    *token == game_version
+      || token.strip_suffix(".x").is_some_and(|prefix| {
+        game_version == prefix || game_version.starts_with(&format!("{}.", prefix))
+      })
+  }))
+}
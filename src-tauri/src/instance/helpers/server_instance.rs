@@ -0,0 +1,498 @@
+use regex::Regex;
+use serde::Deserialize;
+use sjmcl_types::error::SJMCLResult;
+use sjmcl_types::storage::{load_json_async, save_json_async};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use url::Url;
+
+use crate::instance::constants::INSTANCE_CFG_FILE_NAME;
+use crate::instance::helpers::client_json::McClientInfo;
+use crate::instance::helpers::loader::forge::install_forge_loader;
+use crate::instance::helpers::loader::neoforge::install_neoforge_loader;
+use crate::instance::helpers::local_cache::get_local_resource_sha1;
+use crate::instance::helpers::mods::common::load_local_mod_infos;
+use crate::instance::models::misc::{
+  Instance, InstanceError, ModLoader, ModLoaderStatus, ModLoaderType, ServerInstance,
+};
+use crate::launch::helpers::file_validator::convert_library_name_to_path;
+use crate::launch::helpers::jre_selector::select_java_runtime;
+use crate::launch::helpers::misc::get_separator;
+use crate::launcher_config::helpers::misc::get_global_game_config;
+use crate::launcher_config::models::{GameDirectory, LauncherConfig};
+use crate::resource::helpers::misc::{
+  convert_url_to_target_source, get_download_api, get_source_priority_list,
+};
+use crate::resource::helpers::modrinth::fetch_server_side_support_modrinth;
+use crate::resource::models::{ResourceType, SourceType};
+use crate::tasks::PTaskParam;
+use crate::tasks::commands::schedule_progressive_task_group;
+use crate::tasks::download::DownloadParam;
+use crate::utils::fs::{RemoveDirGuard, get_files_with_regex};
+
+pub const SERVER_INSTANCES_DIR_NAME: &str = "servers";
+pub const SERVER_JAR_FILE_NAME: &str = "server.jar";
+// file names of the mods copied from the paired client by the last mirror
+const MIRRORED_MODS_FILE_NAME: &str = "sjmclmirroredmods.json";
+
+// server launch profile published by the Fabric and Quilt meta servers
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct LoaderServerProfile {
+  pub main_class: String,
+  pub libraries: Vec<LoaderServerLibrary>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct LoaderServerLibrary {
+  pub name: String,
+  pub url: Option<String>,
+}
+
+pub async fn refresh_and_update_server_instances(app: &AppHandle) {
+  let local_game_directories = {
+    let binding = app.state::<Mutex<LauncherConfig>>();
+    let state = binding.lock().unwrap();
+    state.local_game_directories.clone()
+  };
+
+  let mut server_map = HashMap::new();
+  for game_directory in local_game_directories {
+    let Ok(entries) = fs::read_dir(game_directory.dir.join(SERVER_INSTANCES_DIR_NAME)) else {
+      continue;
+    };
+    for entry in entries.flatten() {
+      let server_path = entry.path();
+      let Ok(name) = entry.file_name().into_string() else {
+        continue;
+      };
+      let cfg_path = server_path.join(INSTANCE_CFG_FILE_NAME);
+      if !cfg_path.is_file() {
+        continue;
+      }
+      match load_json_async::<ServerInstance>(&cfg_path).await {
+        Ok(mut server) => {
+          // the folder may have been renamed or moved since the config was saved
+          server.id = format!("{}:{}", game_directory.name, name);
+          server.name = name;
+          server.server_path = server_path;
+          server_map.insert(server.id.clone(), server);
+        }
+        Err(e) => log::warn!(
+          "Skipped invalid server instance at {:?}: {:?}",
+          server_path,
+          e
+        ),
+      }
+    }
+  }
+
+  let binding = app.state::<Mutex<HashMap<String, ServerInstance>>>();
+  let mut state = binding.lock().unwrap();
+  *state = server_map;
+}
+
+pub fn get_server_instance_by_id(app: &AppHandle, server_id: &str) -> SJMCLResult<ServerInstance> {
+  let binding = app.state::<Mutex<HashMap<String, ServerInstance>>>();
+  let state = binding.lock()?;
+  state
+    .get(server_id)
+    .cloned()
+    .ok_or(InstanceError::ServerInstanceNotFound.into())
+}
+
+async fn fetch_loader_server_profile(
+  app: &AppHandle,
+  priority: &[SourceType],
+  server: &ServerInstance,
+) -> SJMCLResult<LoaderServerProfile> {
+  let (meta_type, api_version) = match server.mod_loader.loader_type {
+    ModLoaderType::Fabric => (ResourceType::FabricMeta, "v2"),
+    ModLoaderType::Quilt => (ResourceType::QuiltMeta, "v3"),
+    _ => return Err(InstanceError::UnsupportedModLoader.into()),
+  };
+  let client = app.state::<reqwest::Client>();
+  for source_type in priority.iter() {
+    if let Ok(root) = get_download_api(*source_type, meta_type)
+      && let Ok(url) = root.join(&format!(
+        "{api_version}/versions/loader/{}/{}/server/json",
+        server.version, server.mod_loader.version
+      ))
+    {
+      match client.get(url).send().await {
+        Ok(resp) if resp.status().is_success() => {
+          if let Ok(profile) = resp.json::<LoaderServerProfile>().await {
+            return Ok(profile);
+          }
+        }
+        _ => continue,
+      }
+    }
+  }
+  Err(InstanceError::NetworkError.into())
+}
+
+/// Build the download tasks of a new server instance and fill in its launch arguments.
+/// Forge and NeoForge only download their installer here, see `run_server_loader_installer`.
+pub async fn build_server_install_params(
+  app: &AppHandle,
+  priority: &[SourceType],
+  server: &mut ServerInstance,
+  version_info: &McClientInfo,
+) -> SJMCLResult<Vec<PTaskParam>> {
+  let mut task_params = Vec::new();
+  let lib_dir = server.server_path.join("libraries");
+
+  if matches!(
+    server.mod_loader.loader_type,
+    ModLoaderType::Unknown | ModLoaderType::Fabric | ModLoaderType::Quilt
+  ) {
+    // the vanilla jar is only published since 1.2.5
+    let server_download_info = version_info
+      .downloads
+      .get("server")
+      .ok_or(InstanceError::ServerJarNotAvailable)?;
+    task_params.push(PTaskParam::Download(DownloadParam {
+      src: Url::parse(&server_download_info.url)
+        .map_err(|_| InstanceError::ClientJsonParseError)?,
+      dest: server.server_path.join(SERVER_JAR_FILE_NAME),
+      filename: None,
+      sha1: Some(server_download_info.sha1.clone()),
+    }));
+  }
+
+  match server.mod_loader.loader_type {
+    ModLoaderType::Unknown => {
+      server.launch_args = vec!["-jar".to_string(), SERVER_JAR_FILE_NAME.to_string()];
+      server.mod_loader.status = ModLoaderStatus::Installed;
+    }
+    ModLoaderType::Fabric | ModLoaderType::Quilt => {
+      let profile = fetch_loader_server_profile(app, priority, server).await?;
+      let mut class_paths = Vec::new();
+      for library in profile.libraries.iter() {
+        let rel = convert_library_name_to_path(&library.name, None)?;
+        let url_root = match &library.url {
+          Some(url) => Url::parse(url)?,
+          None => get_download_api(priority[0], ResourceType::Libraries)?,
+        };
+        let src = url_root.join(&rel)?;
+        task_params.push(PTaskParam::Download(DownloadParam {
+          src: convert_url_to_target_source(
+            &src,
+            &[
+              ResourceType::FabricMaven,
+              ResourceType::QuiltMaven,
+              ResourceType::Libraries,
+            ],
+            &priority[0],
+          )
+          .unwrap_or(src),
+          dest: lib_dir.join(&rel),
+          filename: None,
+          sha1: None,
+        }));
+        class_paths.push(format!("libraries/{}", rel));
+      }
+      // the loader finds the game jar on the class path, including 1.18+ bundler jars
+      class_paths.push(SERVER_JAR_FILE_NAME.to_string());
+      server.launch_args = vec![
+        "-cp".to_string(),
+        class_paths.join(get_separator()),
+        profile.main_class,
+      ];
+      server.mod_loader.status = ModLoaderStatus::Installed;
+    }
+    ModLoaderType::Forge => {
+      install_forge_loader(
+        priority,
+        &server.version,
+        &server.mod_loader,
+        lib_dir,
+        &mut task_params,
+      )
+      .await?;
+      server.mod_loader.status = ModLoaderStatus::Downloading;
+    }
+    ModLoaderType::NeoForge => {
+      install_neoforge_loader(priority, &server.mod_loader, lib_dir, &mut task_params).await?;
+      server.mod_loader.status = ModLoaderStatus::Downloading;
+    }
+    _ => return Err(InstanceError::UnsupportedModLoader.into()),
+  }
+
+  Ok(task_params)
+}
+
+/// Create the server folder and config, then schedule its downloads.
+pub async fn install_server_instance(
+  app: &AppHandle,
+  directory: &GameDirectory,
+  name: &str,
+  description: String,
+  mod_loader: ModLoader,
+  version_info: &McClientInfo,
+  game_version: &str,
+  paired_instance_id: Option<String>,
+) -> SJMCLResult<ServerInstance> {
+  let priority_list = {
+    let launcher_config_state = app.state::<Mutex<LauncherConfig>>();
+    let launcher_config = launcher_config_state.lock()?;
+    get_source_priority_list(&launcher_config)
+  };
+
+  let server_path = directory.dir.join(SERVER_INSTANCES_DIR_NAME).join(name);
+  if server_path.exists() {
+    return Err(InstanceError::ConflictNameError.into());
+  }
+  let dir_guard = RemoveDirGuard::new(server_path.clone());
+  fs::create_dir_all(&server_path).map_err(|_| InstanceError::FolderCreationFailed)?;
+
+  let mut server = ServerInstance {
+    id: format!("{}:{}", directory.name, name),
+    name: name.to_string(),
+    description,
+    version: game_version.to_string(),
+    server_path,
+    mod_loader,
+    java_major_version: version_info
+      .java_version
+      .as_ref()
+      .map_or(0i32, |v| v.major_version),
+    paired_instance_id,
+    ..Default::default()
+  };
+  let task_params =
+    build_server_install_params(app, &priority_list, &mut server, version_info).await?;
+  server.save_json_cfg().await?;
+
+  schedule_progressive_task_group(
+    app.clone(),
+    format!("game-server?{}", name),
+    task_params,
+    true,
+  )
+  .await?;
+
+  {
+    let binding = app.state::<Mutex<HashMap<String, ServerInstance>>>();
+    let mut state = binding.lock()?;
+    state.insert(server.id.clone(), server.clone());
+  }
+  dir_guard.commit();
+  Ok(server)
+}
+
+// same coordinates as used by `install_forge_loader` and `install_neoforge_loader`
+fn get_server_loader_installer_path(server: &ServerInstance) -> SJMCLResult<PathBuf> {
+  let loader_version = &server.mod_loader.version;
+  let installer_coord = match server.mod_loader.loader_type {
+    ModLoaderType::Forge => format!("net.minecraftforge:forge:{}-installer", loader_version),
+    ModLoaderType::NeoForge if loader_version.starts_with("1.20.1-") => {
+      format!("net.neoforged:forge:{}-installer", loader_version)
+    }
+    ModLoaderType::NeoForge => format!("net.neoforged:neoforge:{}-installer", loader_version),
+    _ => return Err(InstanceError::UnsupportedModLoader.into()),
+  };
+  Ok(
+    server
+      .server_path
+      .join("libraries")
+      .join(convert_library_name_to_path(&installer_coord, None)?),
+  )
+}
+
+/// Locate what the Forge or NeoForge server installer produced in the server folder.
+fn detect_loader_server_launch_args(server_path: &Path) -> SJMCLResult<Vec<String>> {
+  // modern installers write the launch arguments to a file next to the loader library
+  let args_file_name = if cfg!(windows) {
+    "win_args.txt"
+  } else {
+    "unix_args.txt"
+  };
+  for loader_dir in [
+    "net/minecraftforge/forge",
+    "net/neoforged/neoforge",
+    "net/neoforged/forge",
+  ] {
+    let Ok(entries) = fs::read_dir(server_path.join("libraries").join(loader_dir)) else {
+      continue;
+    };
+    for entry in entries.flatten() {
+      if entry.path().join(args_file_name).is_file() {
+        return Ok(vec![format!(
+          "@libraries/{}/{}/{}",
+          loader_dir,
+          entry.file_name().to_string_lossy(),
+          args_file_name
+        )]);
+      }
+    }
+  }
+
+  // legacy installers produce an executable jar, e.g. "forge-1.12.2-14.23.5.2860.jar"
+  let jar_regex = Regex::new(r"^(neoforge|forge)-.+\.jar$")?;
+  get_files_with_regex(server_path, &jar_regex)?
+    .into_iter()
+    .filter_map(|path| {
+      path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+    })
+    .find(|name| !name.contains("installer"))
+    .map(|name| vec!["-jar".to_string(), name])
+    .ok_or(InstanceError::MainClassNotFound.into())
+}
+
+/// Run the downloaded Forge or NeoForge installer in server mode, returns the launch arguments.
+pub async fn run_server_loader_installer(
+  app: &AppHandle,
+  server: &ServerInstance,
+) -> SJMCLResult<Vec<String>> {
+  let installer_path = get_server_loader_installer_path(server)?;
+  if !installer_path.exists() {
+    return Err(InstanceError::LoaderInstallerNotFound.into());
+  }
+
+  let game_config = get_global_game_config(app);
+  let selected_java = select_java_runtime(
+    app,
+    Some(&game_config.game_java),
+    &Instance::from(server),
+    server.java_major_version,
+  )
+  .await?;
+
+  // the installer downloads and patches libraries, which may take minutes
+  let exec_path = selected_java.exec_path.clone();
+  let server_path = server.server_path.clone();
+  let output = tokio::task::spawn_blocking(move || {
+    let mut cmd_base = Command::new(&exec_path);
+    #[cfg(target_os = "windows")]
+    {
+      use std::os::windows::process::CommandExt;
+      cmd_base.creation_flags(0x08000000);
+    }
+    cmd_base
+      .current_dir(&server_path)
+      .arg("-jar")
+      .arg(&installer_path)
+      .arg("--installServer")
+      .output()
+  })
+  .await??;
+  if !output.status.success() {
+    log::error!(
+      "[{}] Server installer failed with exit code: {:?}",
+      server.name,
+      output.status.code()
+    );
+    return Err(InstanceError::ProcessorExecutionFailed.into());
+  }
+
+  detect_loader_server_launch_args(&server.server_path)
+}
+
+/// Copy the enabled mods of a client instance, skipping those Modrinth marks as client-only.
+/// Mods unknown to Modrinth are copied. Returns the file names of the skipped mods.
+pub async fn copy_mods_to_server(
+  app: &AppHandle,
+  src_mods_dir: &Path,
+  tgt_mods_dir: &Path,
+) -> SJMCLResult<Vec<String>> {
+  let mod_files = get_files_with_regex(src_mods_dir, &Regex::new(r"\.jar$")?)?;
+  let mut file_hashes = Vec::new();
+  for path in mod_files.iter() {
+    file_hashes.push(get_local_resource_sha1(app, path).await?);
+  }
+
+  let server_sides = match fetch_server_side_support_modrinth(app, &file_hashes).await {
+    Ok(server_sides) => server_sides,
+    Err(e) => {
+      log::warn!(
+        "Failed to query server side support, copying all mods: {:?}",
+        e
+      );
+      HashMap::new()
+    }
+  };
+
+  fs::create_dir_all(tgt_mods_dir)?;
+  let mut skipped = Vec::new();
+  for (path, hash) in mod_files.iter().zip(file_hashes.iter()) {
+    let Some(file_name) = path.file_name() else {
+      continue;
+    };
+    if server_sides.get(hash).map(String::as_str) == Some("unsupported") {
+      skipped.push(file_name.to_string_lossy().to_string());
+      continue;
+    }
+    fs::copy(path, tgt_mods_dir.join(file_name)).map_err(|_| InstanceError::FileCopyFailed)?;
+  }
+
+  Ok(skipped)
+}
+
+pub fn get_paired_server_instance(app: &AppHandle, instance_id: &str) -> Option<ServerInstance> {
+  let binding = app.state::<Mutex<HashMap<String, ServerInstance>>>();
+  let state = binding.lock().ok()?;
+  state
+    .values()
+    .find(|server| server.paired_instance_id.as_deref() == Some(instance_id))
+    .cloned()
+}
+
+/// Replace the mods copied by the previous mirror with the enabled mods of a client instance,
+/// leaving out the ones declared client-only in their metadata. Mods added to the server by hand
+/// are kept. Returns the file names of the skipped mods.
+pub async fn mirror_client_mods_to_server(
+  src_mods_dir: &Path,
+  server_path: &Path,
+  prior_loader_type: Option<ModLoaderType>,
+) -> SJMCLResult<Vec<String>> {
+  let tgt_mods_dir = server_path.join("mods");
+  let manifest_path = server_path.join(MIRRORED_MODS_FILE_NAME);
+  let previous = load_json_async::<Vec<String>>(&manifest_path)
+    .await
+    .unwrap_or_default();
+  for file_name in &previous {
+    let path = tgt_mods_dir.join(file_name);
+    // the manifest only lists plain file names written by sjmcl
+    if Path::new(file_name).file_name() == Some(OsStr::new(file_name)) && path.is_file() {
+      fs::remove_file(path).map_err(|_| InstanceError::FileOperationError)?;
+    }
+  }
+  fs::create_dir_all(&tgt_mods_dir).map_err(|_| InstanceError::FolderCreationFailed)?;
+
+  let mut mirrored = Vec::new();
+  let mut skipped = Vec::new();
+  for info in load_local_mod_infos(src_mods_dir, prior_loader_type).await {
+    let Some(file_name) = info.file_path.file_name() else {
+      continue;
+    };
+    if !info.enabled {
+      continue;
+    }
+    let file_name = file_name.to_string_lossy().to_string();
+    if info.client_only {
+      skipped.push(file_name);
+      continue;
+    }
+    let tgt_path = tgt_mods_dir.join(&file_name);
+    // a server mod of the same name added by hand wins
+    if tgt_path.exists() {
+      continue;
+    }
+    fs::copy(&info.file_path, tgt_path).map_err(|_| InstanceError::FileCopyFailed)?;
+    mirrored.push(file_name);
+  }
+  save_json_async(&mirrored, &manifest_path).await?;
+
+  Ok(skipped)
+}
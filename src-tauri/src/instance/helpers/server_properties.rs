@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use sjmcl_types::error::SJMCLResult;
+use std::path::{Path, PathBuf};
+
+use crate::instance::helpers::options_txt::OptionsTxt;
+
+pub const SERVER_PROPERTIES_FILENAME: &str = "server.properties";
+pub const EULA_TXT_FILENAME: &str = "eula.txt";
+
+const EULA_COMMENT: &str = "#By changing the setting below to TRUE you are indicating your agreement to our EULA (https://aka.ms/MinecraftEULA).";
+
+/// Typed view of the common `server.properties` entries.
+/// Unset fields are left untouched on save, other entries are kept by `OptionsTxt`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerProperties {
+  pub motd: Option<String>,
+  pub server_port: Option<u16>,
+  pub max_players: Option<u32>,
+  pub online_mode: Option<bool>,
+  pub white_list: Option<bool>,
+  pub pvp: Option<bool>,
+  pub hardcore: Option<bool>,
+  pub allow_flight: Option<bool>,
+  pub enable_command_block: Option<bool>,
+  pub difficulty: Option<String>, // "peaceful", "easy", "normal", "hard" (or 0-3 before 1.14)
+  pub gamemode: Option<String>,   // "survival", "creative", "adventure", "spectator"
+  pub level_name: Option<String>,
+  pub level_seed: Option<String>,
+  pub level_type: Option<String>,
+  pub view_distance: Option<u32>,
+  pub simulation_distance: Option<u32>,
+  pub spawn_protection: Option<u32>,
+}
+
+fn parse_bool(value: Option<&str>) -> Option<bool> {
+  value.and_then(|v| v.trim().parse().ok())
+}
+
+fn parse_number<T: std::str::FromStr>(value: Option<&str>) -> Option<T> {
+  value.and_then(|v| v.trim().parse().ok())
+}
+
+impl From<&OptionsTxt> for ServerProperties {
+  fn from(properties: &OptionsTxt) -> Self {
+    let get_string = |key: &str| properties.get(key).map(str::to_string);
+    Self {
+      motd: get_string("motd"),
+      server_port: parse_number(properties.get("server-port")),
+      max_players: parse_number(properties.get("max-players")),
+      online_mode: parse_bool(properties.get("online-mode")),
+      white_list: parse_bool(properties.get("white-list")),
+      pvp: parse_bool(properties.get("pvp")),
+      hardcore: parse_bool(properties.get("hardcore")),
+      allow_flight: parse_bool(properties.get("allow-flight")),
+      enable_command_block: parse_bool(properties.get("enable-command-block")),
+      difficulty: get_string("difficulty"),
+      gamemode: get_string("gamemode"),
+      level_name: get_string("level-name"),
+      level_seed: get_string("level-seed"),
+      level_type: get_string("level-type"),
+      view_distance: parse_number(properties.get("view-distance")),
+      simulation_distance: parse_number(properties.get("simulation-distance")),
+      spawn_protection: parse_number(properties.get("spawn-protection")),
+    }
+  }
+}
+
+impl ServerProperties {
+  /// Write all fields that are set into `properties`, leaving other entries untouched.
+  pub fn apply_to(&self, properties: &mut OptionsTxt) {
+    let string_fields = [
+      ("motd", &self.motd),
+      ("difficulty", &self.difficulty),
+      ("gamemode", &self.gamemode),
+      ("level-name", &self.level_name),
+      ("level-seed", &self.level_seed),
+      ("level-type", &self.level_type),
+    ];
+    for (key, value) in string_fields {
+      if let Some(value) = value {
+        properties.set(key, value);
+      }
+    }
+    let bool_fields = [
+      ("online-mode", self.online_mode),
+      ("white-list", self.white_list),
+      ("pvp", self.pvp),
+      ("hardcore", self.hardcore),
+      ("allow-flight", self.allow_flight),
+      ("enable-command-block", self.enable_command_block),
+    ];
+    for (key, value) in bool_fields {
+      if let Some(value) = value {
+        properties.set(key, &value.to_string());
+      }
+    }
+    let integer_fields = [
+      ("server-port", self.server_port.map(u32::from)),
+      ("max-players", self.max_players),
+      ("view-distance", self.view_distance),
+      ("simulation-distance", self.simulation_distance),
+      ("spawn-protection", self.spawn_protection),
+    ];
+    for (key, value) in integer_fields {
+      if let Some(value) = value {
+        properties.set(key, &value.to_string());
+      }
+    }
+  }
+}
+
+pub fn get_server_properties_path(server_path: &Path) -> PathBuf {
+  server_path.join(SERVER_PROPERTIES_FILENAME)
+}
+
+pub async fn is_eula_accepted(server_path: &Path) -> bool {
+  match OptionsTxt::load_properties(&server_path.join(EULA_TXT_FILENAME)).await {
+    Ok(eula) => parse_bool(eula.get("eula")).unwrap_or(false),
+    Err(_) => false,
+  }
+}
+
+pub async fn accept_eula(server_path: &Path) -> SJMCLResult<()> {
+  let eula_path = server_path.join(EULA_TXT_FILENAME);
+  let mut eula = OptionsTxt::load_properties(&eula_path).await?;
+  if eula.get("eula").is_none() {
+    eula = OptionsTxt::parse_properties(EULA_COMMENT);
+  }
+  eula.set("eula", "true");
+  eula.save(&eula_path).await
+}
@@ -0,0 +1,82 @@
+use sjmcl_types::error::SJMCLResult;
+use std::path::{Path, PathBuf};
+
+use crate::instance::helpers::options_txt::OptionsTxt;
+use crate::instance::models::misc::{Instance, ModLoaderType};
+
+const OPTIFINE_SHADER_CFG: &str = "optionsshaders.txt";
+const IRIS_SHADER_CFG: &str = "config/iris.properties";
+const OCULUS_SHADER_CFG: &str = "config/oculus.properties"; // Iris port for Forge
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaderLoader {
+  OptiFine,
+  Iris,
+  Oculus,
+}
+
+impl ShaderLoader {
+  /// Decide which shader loader config to use, by installed OptiFine, existing config files and mod loader.
+  pub fn detect(game_root: &Path, instance: &Instance) -> Self {
+    if instance.optifine.is_some() {
+      return Self::OptiFine;
+    }
+    if game_root.join(OCULUS_SHADER_CFG).exists() {
+      return Self::Oculus;
+    }
+    if game_root.join(IRIS_SHADER_CFG).exists() {
+      return Self::Iris;
+    }
+    if game_root.join(OPTIFINE_SHADER_CFG).exists() {
+      return Self::OptiFine;
+    }
+    match instance.mod_loader.loader_type {
+      ModLoaderType::Forge | ModLoaderType::LegacyForge | ModLoaderType::Unknown => Self::OptiFine,
+      _ => Self::Iris,
+    }
+  }
+
+  pub fn config_path(self, game_root: &Path) -> PathBuf {
+    game_root.join(match self {
+      Self::OptiFine => OPTIFINE_SHADER_CFG,
+      Self::Iris => IRIS_SHADER_CFG,
+      Self::Oculus => OCULUS_SHADER_CFG,
+    })
+  }
+}
+
+pub async fn get_active_shader_pack(
+  game_root: &Path,
+  instance: &Instance,
+) -> SJMCLResult<Option<String>> {
+  let loader = ShaderLoader::detect(game_root, instance);
+  let config = OptionsTxt::load_properties(&loader.config_path(game_root)).await?;
+  let shader_pack = config.get("shaderPack").map(str::trim).unwrap_or_default();
+  let enabled = match loader {
+    ShaderLoader::OptiFine => shader_pack != "OFF" && shader_pack != "(internal)",
+    ShaderLoader::Iris | ShaderLoader::Oculus => config.get("enableShaders") != Some("false"),
+  };
+
+  Ok((enabled && !shader_pack.is_empty()).then(|| shader_pack.to_string()))
+}
+
+/// Select the active shader pack by its file name, or turn shaders off if `shader_pack` is None.
+pub async fn set_active_shader_pack(
+  game_root: &Path,
+  instance: &Instance,
+  shader_pack: Option<&str>,
+) -> SJMCLResult<()> {
+  let loader = ShaderLoader::detect(game_root, instance);
+  let config_path = loader.config_path(game_root);
+  let mut config = OptionsTxt::load_properties(&config_path).await?;
+  match (loader, shader_pack) {
+    (ShaderLoader::OptiFine, Some(name)) => config.set("shaderPack", name),
+    (ShaderLoader::OptiFine, None) => config.set("shaderPack", "OFF"),
+    (_, Some(name)) => {
+      config.set("shaderPack", name);
+      config.set("enableShaders", "true");
+    }
+    (_, None) => config.set("enableShaders", "false"),
+  }
+  config.save(&config_path).await
+}
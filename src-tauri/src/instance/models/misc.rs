@@ -105,9 +105,31 @@ structstruck::strike! {
     // if use_spec_game_config is false, this field is ignored
     pub spec_game_config: Option<GameConfig>,
     pub modpack_version: Option<String>,
+    // file names in the mods folder excluded from bulk updates
+    pub pinned_mods: Vec<String>,
+    pub mod_sets: Vec<ModSet>,
+    pub mod_bisect: Option<ModBisectState>,
   }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ModSet {
+  pub name: String,
+  pub enabled_mods: Vec<String>, // file names without the `.disabled` suffix
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ModBisectState {
+  pub original_enabled: Vec<String>, // restored when the bisect finishes or stops
+  pub suspects: Vec<String>,
+  pub testing: Vec<String>, // mods enabled for the current test launch
+  pub step: u32,
+  pub finished: bool,
+  pub culprit: Option<String>, // None on finish if no single mod crashes alone
+}
+
 impl Instance {
   pub fn get_json_cfg_path(&self) -> PathBuf {
     self.version_path.join(INSTANCE_CFG_FILE_NAME)
@@ -125,6 +147,60 @@ impl Instance {
   }
 }
 
+// dedicated server instance, stored in `<game directory>/servers/<name>`
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerInstance {
+  pub id: String,
+  pub name: String,
+  pub description: String,
+  pub version: String,
+  pub server_path: PathBuf,
+  pub mod_loader: ModLoader,
+  pub java_major_version: i32, // from the version JSON, used to select the java runtime
+  pub launch_args: Vec<String>, // after the JVM memory options, e.g. ["-jar", "server.jar"]
+  pub max_mem_allocation: Option<u32>, // in MB, None to follow the global game config
+  pub paired_instance_id: Option<String>, // client instance this local test server was built from
+}
+
+impl ServerInstance {
+  pub fn get_json_cfg_path(&self) -> PathBuf {
+    self.server_path.join(INSTANCE_CFG_FILE_NAME)
+  }
+
+  pub async fn save_json_cfg(&self) -> Result<(), std::io::Error> {
+    save_json_async(self, &self.get_json_cfg_path()).await
+  }
+}
+
+// java selection and the launching queue work on client instances
+impl From<&ServerInstance> for Instance {
+  fn from(server: &ServerInstance) -> Self {
+    Self {
+      id: server.id.clone(),
+      name: server.name.clone(),
+      description: server.description.clone(),
+      version: server.version.clone(),
+      version_path: server.server_path.clone(),
+      mod_loader: server.mod_loader.clone(),
+      ..Default::default()
+    }
+  }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerInstanceSummary {
+  pub id: String,
+  pub name: String,
+  pub description: String,
+  pub version: String,
+  pub server_path: PathBuf,
+  pub mod_loader: ModLoader,
+  pub eula_accepted: bool,
+  pub launching_id: Option<u64>, // set while the server is running
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct InstanceSummary {
@@ -193,6 +269,64 @@ pub struct LocalModInfo {
   pub description: String,
   pub translated_description: Option<String>,
   pub potential_incompatibility: bool,
+  #[serde(default)]
+  pub relations: Vec<LocalModRelation>,
+  #[serde(default)]
+  pub provides: Vec<String>, // alias mod ids
+  #[serde(default)]
+  pub nested_mods: Vec<LocalModInfo>, // jar-in-jar mods bundled in this file
+  #[serde(default)]
+  pub client_only: bool, // declared for the client environment only, not loaded by servers
+}
+
+impl LocalModInfo {
+  /// This mod followed by all mods bundled in it, recursively.
+  pub fn with_nested_mods(&self) -> Vec<&LocalModInfo> {
+    let mut mods = vec![self];
+    for nested in &self.nested_mods {
+      mods.extend(nested.with_nested_mods());
+    }
+    mods
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ModRelationType {
+  #[default]
+  Required,
+  Optional,
+  Breaks,    // the game refuses to launch if present
+  Conflicts, // only a warning is printed if present
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalModRelation {
+  pub mod_id: String,
+  pub version_range: Option<String>, // fabric style predicate or maven style range
+  pub relation_type: ModRelationType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModDependencyIssueType {
+  MissingDependency,
+  VersionMismatch,
+  Breaks,
+  Conflicts,
+  DuplicateModId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDependencyIssue {
+  pub issue_type: ModDependencyIssueType,
+  pub mod_id: String,
+  pub file_names: Vec<String>,
+  pub target_mod_id: Option<String>,
+  pub version_range: Option<String>,
+  pub found_version: Option<String>,
 }
 
 impl PartialEq for LocalModInfo {
@@ -225,6 +359,9 @@ pub struct ResourcePackInfo {
   // TODO: is Option necessary?
   pub icon_src: Option<ImageWrapper>,
   pub file_path: PathBuf,
+  pub enabled: bool,
+  pub pack_format: Option<u32>,
+  pub is_incompatible: bool, // pack format not supported by the game version
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
@@ -240,6 +377,7 @@ pub struct SchematicInfo {
 pub struct ShaderPackInfo {
   pub file_name: String,
   pub file_path: PathBuf,
+  pub enabled: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
@@ -247,7 +385,30 @@ pub struct ShaderPackInfo {
 pub struct ScreenshotInfo {
   pub file_name: String,
   pub file_path: PathBuf,
-  pub time: u64,
+  pub time: u64, // capture time parsed from the file name, or modified time as fallback
+  pub size: u64,
+  pub width: u32,
+  pub height: u32,
+  pub thumbnail_path: Option<PathBuf>,
+  pub instance_id: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ScreenshotSortBy {
+  #[default]
+  TimeDesc,
+  TimeAsc,
+  NameAsc,
+  NameDesc,
+  SizeDesc,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotPage {
+  pub items: Vec<ScreenshotInfo>,
+  pub total: usize,
 }
 
 #[derive(Debug, Display)]
@@ -255,7 +416,13 @@ pub struct ScreenshotInfo {
 pub enum InstanceError {
   InstanceNotFoundByID,
   ServerNbtReadError,
+  OptionsTxtReadError,
   DuplicateServer,
+  ServerNotFound,
+  ModSetNotFound,
+  ModBisectNotStarted,
+  InstanceLockParseError,
+  InstanceLockMismatch,
   FileNotFoundError,
   InvalidSourcePath,
   FileCreationFailed,
@@ -275,6 +442,7 @@ pub enum InstanceError {
   InstallProfileParseError,
   ModLoaderVersionParseError,
   ModpackManifestParseError,
+  ModpackUpdateMismatch,
   CurseForgeFileManifestParseError,
   NetworkError,
   UnsupportedModLoader,
@@ -284,6 +452,18 @@ pub enum InstanceError {
   ProcessorExecutionFailed,
   SemaphoreAcquireFailed,
   LoaderInstallerNotFound,
+  OptiFineForgeIncompatible,
+  OptiFineEditionUnsupported,
+  OptiFabricNotFound,
+  ClientPatchNotFound,
+  ClientPatchConflict,
+  ClientPatchNotEditable,
+  ServerInstanceNotFound,
+  ServerJarNotAvailable,
+  ServerEulaNotAccepted,
+  ServerAlreadyRunning,
+  ServerNotRunning,
+  ServerStartTimeout,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
@@ -296,6 +476,17 @@ pub struct OptiFine {
 
 impl std::error::Error for InstanceError {}
 
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientPatchInfo {
+  pub id: String,
+  pub version: Option<String>,
+  pub priority: Option<i64>,
+  pub main_class: Option<String>,
+  pub libraries: Vec<String>,
+  pub is_builtin: bool, // game, mod loader and OptiFine patches are managed by their own commands
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModpackFileList {
@@ -203,6 +203,7 @@ pub fn tool_routes() -> Vec<ToolRoute<McpContext>> {
           game,
           mod_loader,
           optifine,
+          None,
           params.modpack_path,
           params.is_install_fabric_api.or(Some(true)),
           params.is_install_qf_api.or(Some(true)),
@@ -278,6 +279,8 @@ pub fn tool_routes() -> Vec<ToolRoute<McpContext>> {
         server_addr: String,
         #[schemars(description = "Display name saved for this server entry.")]
         server_name: String,
+        #[schemars(description = "Whether to accept the server resource pack. Omit to let the game prompt.")]
+        accept_textures: Option<bool>,
       }
     ),
     mcp_tool!(
@@ -365,6 +368,16 @@ pub fn tool_routes() -> Vec<ToolRoute<McpContext>> {
         Ok(mods)
       }
     ),
+    mcp_tool!(
+      "check_mod_dependencies",
+      check_mod_dependencies,
+      "Check local mods of a Minecraft instance for missing dependencies, version mismatches, conflicts and duplicate mod IDs.",
+      #[serde(deny_unknown_fields)]
+      {
+        #[schemars(description = "Minecraft instance ID.")]
+        instance_id: String,
+      }
+    ),
     mcp_tool!(
       "retrieve_resource_pack_list",
       retrieve_resource_pack_list,
@@ -396,7 +409,7 @@ pub fn tool_routes() -> Vec<ToolRoute<McpContext>> {
       }
     ),
     mcp_tool!(
-      sync "retrieve_shader_pack_list",
+      "retrieve_shader_pack_list",
       retrieve_shader_pack_list,
       "Retrieve shader packs for a Minecraft instance.",
       #[serde(deny_unknown_fields)]
@@ -406,7 +419,7 @@ pub fn tool_routes() -> Vec<ToolRoute<McpContext>> {
       }
     ),
     mcp_tool!(
-      sync "retrieve_screenshot_list",
+      "retrieve_screenshot_list",
       retrieve_screenshot_list,
       "Retrieve screenshots for a Minecraft instance.",
       #[serde(deny_unknown_fields)]
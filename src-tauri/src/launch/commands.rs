@@ -6,8 +6,9 @@ use std::io::BufReader;
 use std::io::prelude::*;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
-use std::sync::{Mutex, mpsc};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Mutex;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::path::BaseDirectory;
 use tauri::{AppHandle, Manager, State};
 
@@ -19,7 +20,16 @@ use crate::account::helpers::offline::yggdrasil_server::YggdrasilServer;
 use crate::account::helpers::{authlib_injector, microsoft};
 use crate::account::models::PlayerType;
 use crate::instance::helpers::client_json::{McClientInfo, replace_native_libraries};
-use crate::instance::helpers::misc::{get_instance_game_config, get_instance_subdir_paths};
+use crate::instance::helpers::misc::{
+  get_instance_game_config, get_instance_subdir_path_by_id, get_instance_subdir_paths,
+};
+use crate::instance::helpers::options_txt::OptionsTxt;
+use crate::instance::helpers::server_instance::{
+  get_paired_server_instance, get_server_instance_by_id, mirror_client_mods_to_server,
+};
+use crate::instance::helpers::server_properties::{
+  ServerProperties, accept_eula, get_server_properties_path,
+};
 use crate::instance::models::misc::{Instance, InstanceError, InstanceSubdirType, ModLoaderStatus};
 use crate::launch::helpers::command_generator::{
   LaunchCommand, export_full_launch_command, generate_launch_command,
@@ -30,13 +40,19 @@ use crate::launch::helpers::file_validator::{
 use crate::launch::helpers::graphics_handler::{
   build_graphics_environment_variables, parse_environment_variables,
 };
-use crate::launch::helpers::jre_selector::select_java_runtime;
+use crate::launch::helpers::jre_selector::{
+  download_recommended_java_runtime, rank_java_runtimes, select_java_runtime,
+};
 use crate::launch::helpers::log_parser::parse_crash_report_path_from_log;
 use crate::launch::helpers::misc::get_separator;
 use crate::launch::helpers::process_monitor::{
   kill_process, monitor_process, set_process_priority,
 };
-use crate::launch::models::{LaunchError, LaunchingState};
+use crate::launch::helpers::server_process::{
+  get_running_server_launching_id, send_server_console_command, start_server_process,
+  stop_server_process,
+};
+use crate::launch::models::{JavaCandidate, LanTestServerInfo, LaunchError, LaunchingState};
 use crate::launcher_config::helpers::java::refresh_and_update_javas;
 use crate::launcher_config::models::{FileValidatePolicy, LauncherConfig, LauncherVisiablity};
 use crate::resource::helpers::misc::get_source_priority_list;
@@ -44,6 +60,7 @@ use crate::tasks::commands::schedule_progressive_task_group;
 use crate::utils::fs::create_zip_from_dirs;
 use crate::utils::logging::get_launcher_log_path;
 use crate::utils::shell::{execute_command_line, split_command_line};
+use crate::utils::sys_info::find_free_port;
 use crate::utils::window::create_webview_window;
 
 #[cfg(target_os = "windows")]
@@ -71,16 +88,27 @@ pub async fn select_suitable_jre(
 
   refresh_and_update_javas(&app).await;
 
-  let selected_java = select_java_runtime(
+  let client_json_req = client_info
+    .java_version
+    .as_ref()
+    .map_or(0i32, |v| v.major_version);
+  let selected_java = match select_java_runtime(
     &app,
     Some(&game_config.game_java),
     &instance,
-    client_info
-      .java_version
-      .as_ref()
-      .map_or(0i32, |v| v.major_version),
+    client_json_req,
   )
-  .await?;
+  .await
+  {
+    Ok(java) => java,
+    Err(err) => {
+      // fetch a fitting runtime in the background, the launch can be retried once it is done
+      if err.0 == LaunchError::NoSuitableJava.to_string() {
+        let _ = download_recommended_java_runtime(&app, &instance, client_json_req).await;
+      }
+      return Err(err);
+    }
+  };
 
   let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
   let mut launching = launching_queue_state.lock()?;
@@ -96,6 +124,36 @@ pub async fn select_suitable_jre(
   Ok(())
 }
 
+#[tauri::command]
+pub async fn retrieve_java_candidates(
+  app: AppHandle,
+  instance_id: String,
+) -> SJMCLResult<Vec<JavaCandidate>> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let client_path = instance
+    .version_path
+    .join(format!("{}.json", instance.name));
+  let client_info = load_json_async::<McClientInfo>(&client_path).await?;
+
+  refresh_and_update_javas(&app).await;
+  rank_java_runtimes(
+    &app,
+    &instance,
+    client_info
+      .java_version
+      .as_ref()
+      .map_or(0i32, |v| v.major_version),
+  )
+  .await
+}
+
 // Step 2: extract native libraries, validate game and dependency files, and prepare legacy game assets (if needed).
 #[tauri::command]
 pub async fn validate_game_files(
@@ -420,6 +478,90 @@ pub fn cancel_launch_process(
   Ok(())
 }
 
+/// Start a server instance, returns the launching id of its console log window.
+#[tauri::command]
+pub async fn start_server_instance(app: AppHandle, server_id: String) -> SJMCLResult<u64> {
+  let server = get_server_instance_by_id(&app, &server_id)?;
+  let (launching_id, _) = start_server_process(&app, &server).await?;
+  Ok(launching_id)
+}
+
+#[tauri::command]
+pub fn stop_server_instance(app: AppHandle, server_id: String) -> SJMCLResult<()> {
+  stop_server_process(&app, &server_id)
+}
+
+#[tauri::command]
+pub fn send_server_command(app: AppHandle, server_id: String, command: String) -> SJMCLResult<()> {
+  send_server_console_command(&app, &server_id, &command)
+}
+
+// a modded server may take minutes to load, give up if it is still not ready after this
+const LAN_TEST_SERVER_READY_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Sync the mods of a client instance to its paired local server, accept its EULA and start it
+/// on a free port. Returns once the server is ready, the caller then launches the client through
+/// the usual launch steps with `quick_play_multiplayer` set to the returned address.
+#[tauri::command]
+pub async fn start_lan_test_server(
+  app: AppHandle,
+  instance_id: String,
+) -> SJMCLResult<LanTestServerInfo> {
+  let server =
+    get_paired_server_instance(&app, &instance_id).ok_or(InstanceError::ServerInstanceNotFound)?;
+  if get_running_server_launching_id(&app, &server.id).is_some() {
+    return Err(InstanceError::ServerAlreadyRunning.into());
+  }
+
+  let mods_dir = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let skipped_mods = mirror_client_mods_to_server(
+    &mods_dir,
+    &server.server_path,
+    Some(server.mod_loader.loader_type),
+  )
+  .await?;
+
+  let port = find_free_port(Some(25565))?;
+  let properties_path = get_server_properties_path(&server.server_path);
+  let mut properties = OptionsTxt::load_properties(&properties_path)
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+  ServerProperties {
+    server_port: Some(port),
+    online_mode: Some(false), // allow offline players to join for testing
+    ..Default::default()
+  }
+  .apply_to(&mut properties);
+  properties
+    .save(&properties_path)
+    .await
+    .map_err(|_| InstanceError::FileOperationError)?;
+
+  // the paired server only exists for local testing
+  accept_eula(&server.server_path).await?;
+
+  let (launching_id, rx) = start_server_process(&app, &server).await?;
+  let ready =
+    tokio::task::spawn_blocking(move || rx.recv_timeout(LAN_TEST_SERVER_READY_TIMEOUT)).await?;
+  match ready {
+    Ok(()) => {}
+    // the sender is dropped if the server exits before it is done loading
+    Err(RecvTimeoutError::Disconnected) => return Err(InstanceError::ServerNotRunning.into()),
+    Err(RecvTimeoutError::Timeout) => {
+      let _ = stop_server_process(&app, &server.id);
+      return Err(InstanceError::ServerStartTimeout.into());
+    }
+  }
+
+  Ok(LanTestServerInfo {
+    server_id: server.id,
+    launching_id,
+    address: format!("localhost:{}", port),
+    skipped_mods,
+  })
+}
+
 #[tauri::command]
 pub async fn open_game_log_window(app: AppHandle, launching_id: u64) -> SJMCLResult<()> {
   create_webview_window(
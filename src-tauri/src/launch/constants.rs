@@ -1,3 +1,8 @@
 pub const GAME_PROCESS_OUTPUT_EVENT: &str = "launch:game-process-output";
 
-pub const READY_FLAG: &[&str] = &["render thread", "lwjgl version", "lwjgl openal"];
+pub const READY_FLAG: &[&str] = &[
+  "render thread",
+  "lwjgl version",
+  "lwjgl openal",
+  "for help, type \"help\"", // dedicated server finished loading
+];
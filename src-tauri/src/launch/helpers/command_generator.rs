@@ -222,15 +222,25 @@ pub async fn generate_launch_command(
   // -----------------------------------------
 
   // set maximum memory allocation
-  cmd.push(format!(
-    "-Xmx{}m",
-    if game_config.performance.auto_mem_allocation {
-      let memory_info = get_memory_info();
-      (memory_info.suggested_max_alloc / 1024 / 1024) as u32
-    } else {
-      game_config.performance.max_mem_allocation
-    }
-  ));
+  let mut max_mem_allocation = if game_config.performance.auto_mem_allocation {
+    let memory_info = get_memory_info();
+    (memory_info.suggested_max_alloc / 1024 / 1024) as u32
+  } else {
+    game_config.performance.max_mem_allocation
+  };
+  // a 32-bit JVM exits if it cannot reserve the heap
+  if let Some(max_heap) = selected_java.max_heap_mb
+    && max_mem_allocation > max_heap
+  {
+    log::warn!(
+      "Max memory allocation {}MB exceeds the {}MB limit of {}, capped",
+      max_mem_allocation,
+      max_heap,
+      selected_java.exec_path
+    );
+    max_mem_allocation = max_heap;
+  }
+  cmd.push(format!("-Xmx{}m", max_mem_allocation));
 
   // advanced JVM options
   let jvm = &game_config.advanced.jvm;
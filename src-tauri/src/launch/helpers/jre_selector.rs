@@ -1,24 +1,228 @@
 use sjmcl_types::error::SJMCLResult;
 use std::cmp::Ordering;
+use std::pin::Pin;
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 
 use crate::instance::helpers::game_version::compare_game_versions;
-use crate::instance::models::misc::Instance;
-use crate::launch::models::LaunchError;
-use crate::launcher_config::models::{GameJava, JavaInfo};
+use crate::instance::helpers::misc::get_instance_game_config;
+use crate::instance::models::misc::{Instance, ModLoaderType};
+use crate::launch::models::{JavaCandidate, JavaSelectionReason, LaunchError};
+use crate::launcher_config::helpers::java::build_mojang_java_download_params;
+use crate::launcher_config::models::{
+  GameConfig, GameJava, GarbageCollector, JavaInfo, LauncherConfig,
+};
+use crate::tasks::commands::schedule_progressive_task_group;
+use crate::tasks::events::GEventStatus;
+use crate::tasks::monitor::TaskMonitor;
+use crate::utils::sys_info::get_memory_info;
+
+// 32-bit JVMs usually cannot reserve a heap much larger than this, if not probed (MB)
+const MAX_32BIT_HEAP_MB: u32 = 1024;
+
+// major versions of the Java runtimes distributed by Mojang
+const MOJANG_JAVA_VERSIONS: [i32; 4] = [8, 17, 21, 25];
+
+/// Java constraints of an instance, derived from the game, mod loader, OptiFine and JVM settings.
+/// ref: https://github.com/Hex-Dragon/PCL2/blob/16e09c792ce8c13435fc6827e6da54170aaa3bc0/Plain%20Craft%20Launcher%202/Modules/Minecraft/ModLaunch.vb#L1130
+#[derive(Debug, Clone)]
+pub struct JavaRequirement {
+  pub min_version: i32,
+  pub max_version: Option<i32>,           // runtimes above are ruled out
+  pub preferred_max_version: Option<i32>, // runtimes above are ranked lower
+  pub garbage_collector: GarbageCollector,
+  pub max_mem_allocation: u32, // MB
+}
+
+pub async fn get_java_requirement(
+  app: &AppHandle,
+  instance: &Instance,
+  game_config: &GameConfig,
+  client_json_req: i32,
+) -> JavaRequirement {
+  let min_version = get_minimum_java_version_by_game(app, instance, true)
+    .await
+    .max(client_json_req);
+
+  // Before 1.13, Forge, LiteLoader and OptiFine (unless via OptiFabric) run on LaunchWrapper,
+  // which breaks on Java 9+. Forge 1.13 - 1.16 boots through ModLauncher (cpw bootstrap) and
+  // runs on newer Java, but it and OptiFine were built against Java 8, so Java 8 is preferred.
+  let is_java8_era_loader = matches!(
+    instance.mod_loader.loader_type,
+    ModLoaderType::Forge | ModLoaderType::LegacyForge | ModLoaderType::LiteLoader
+  ) || (instance.optifine.is_some()
+    && instance.mod_loader.loader_type != ModLoaderType::Fabric);
+  let (mut max_version, mut preferred_max_version) = (None, None);
+  if is_java8_era_loader {
+    // 1.13(17w43a)
+    if compare_game_versions(app, &instance.version, "17w43a", false)
+      .await
+      .is_lt()
+    {
+      max_version = Some(8);
+    }
+    // 1.17(21w19a)
+    else if compare_game_versions(app, &instance.version, "21w19a", false)
+      .await
+      .is_lt()
+    {
+      preferred_max_version = Some(8);
+    }
+  }
+
+  let max_mem_allocation = if game_config.performance.auto_mem_allocation {
+    (get_memory_info().suggested_max_alloc / 1024 / 1024) as u32
+  } else {
+    game_config.performance.max_mem_allocation
+  };
+
+  JavaRequirement {
+    min_version,
+    max_version,
+    preferred_max_version,
+    garbage_collector: game_config.advanced.jvm.garbage_collector.clone(),
+    max_mem_allocation,
+  }
+}
+
+fn is_32bit_arch(arch: &str) -> bool {
+  matches!(arch, "x86" | "arm")
+}
+
+/// Check a runtime against the requirement, unsuitable ones are kept in the list with the reasons.
+pub fn evaluate_java_runtime(
+  java: &JavaInfo,
+  requirement: &JavaRequirement,
+  system_arch: &str,
+) -> JavaCandidate {
+  let mut reasons = Vec::new();
+  let mut suitable = true;
+  let mut score = 0;
+  let major_version = java.major_version;
+
+  if major_version < requirement.min_version {
+    suitable = false;
+    reasons.push(JavaSelectionReason::BelowMinimumVersion {
+      required: requirement.min_version,
+    });
+  }
+  if let Some(maximum) = requirement.max_version
+    && major_version > maximum
+  {
+    suitable = false;
+    reasons.push(JavaSelectionReason::AboveMaximumVersion { maximum });
+  }
+  if let Some(preferred) = requirement.preferred_max_version
+    && major_version > preferred
+  {
+    score -= 50;
+    reasons.push(JavaSelectionReason::AbovePreferredVersion { preferred });
+  }
+
+  // the JVM refuses to start with an unknown GC option, use the probed flags if available
+  let gc_minimum = match requirement.garbage_collector {
+    GarbageCollector::Zgc => 15,
+    GarbageCollector::Shenandoah => 12,
+    _ => 0,
+  };
+  let gc_supported = match (&requirement.garbage_collector, &java.supported_gcs) {
+    (GarbageCollector::Auto, _) => true,
+    (gc, Some(supported_gcs)) => supported_gcs.contains(gc),
+    (_, None) => major_version >= gc_minimum,
+  };
+  if !gc_supported {
+    suitable = false;
+    reasons.push(JavaSelectionReason::GarbageCollectorUnsupported {
+      minimum: gc_minimum,
+    });
+  } else if requirement.garbage_collector == GarbageCollector::Shenandoah
+    && java.supported_gcs.is_none()
+    && java.vendor.contains("Oracle")
+  {
+    suitable = false;
+    reasons.push(JavaSelectionReason::ShenandoahUnavailable);
+  }
+
+  let is_32bit = java
+    .is_64bit
+    .map(|is_64bit| !is_64bit)
+    .or(java.arch.as_deref().map(is_32bit_arch))
+    .unwrap_or(false);
+  if is_32bit {
+    score -= 10;
+  }
+  let max_heap = java.max_heap_mb.or(is_32bit.then_some(MAX_32BIT_HEAP_MB));
+  if let Some(max_heap) = max_heap
+    && requirement.max_mem_allocation > max_heap
+  {
+    score -= 100;
+    reasons.push(JavaSelectionReason::MemoryExceedsHeapLimit {
+      max_mem_allocation: requirement.max_mem_allocation,
+      max_heap,
+    });
+  }
+  // e.g. x86_64 runtimes translated by Rosetta on Apple silicon; 32-bit x86 on x86_64 runs natively
+  if let Some(arch) = &java.arch
+    && arch != system_arch
+    && !system_arch.is_empty()
+    && !(arch == "x86" && system_arch == "x86_64")
+  {
+    score -= 30;
+    reasons.push(JavaSelectionReason::NonNativeArchitecture { arch: arch.clone() });
+  }
+
+  if major_version == requirement.min_version {
+    score += 20;
+    reasons.push(JavaSelectionReason::ExactVersionMatch);
+  }
+  // the closer to the minimum the better
+  score -= (major_version - requirement.min_version).max(0);
+
+  JavaCandidate {
+    java: java.clone(),
+    suitable,
+    score,
+    reasons,
+  }
+}
+
+/// Rank all known runtimes for the instance, suitable ones first.
+pub async fn rank_java_runtimes(
+  app: &AppHandle,
+  instance: &Instance,
+  client_json_req: i32,
+) -> SJMCLResult<Vec<JavaCandidate>> {
+  let java_list = app.state::<Mutex<Vec<JavaInfo>>>().lock()?.clone();
+  let system_arch = app
+    .state::<Mutex<LauncherConfig>>()
+    .lock()?
+    .basic_info
+    .arch
+    .clone();
+  let game_config = get_instance_game_config(app, instance);
+  let requirement = get_java_requirement(app, instance, &game_config, client_json_req).await;
+
+  let mut candidates = java_list
+    .iter()
+    .map(|java| evaluate_java_runtime(java, &requirement, &system_arch))
+    .collect::<Vec<_>>();
+  candidates.sort_by(|a, b| {
+    b.suitable
+      .cmp(&a.suitable)
+      .then_with(|| b.score.cmp(&a.score))
+      .then_with(|| a.java.major_version.cmp(&b.java.major_version))
+  });
+  Ok(candidates)
+}
 
 pub async fn select_java_runtime(
   app: &AppHandle,
   game_java: Option<&GameJava>, // None means force auto selection for some installation flows.
   instance: &Instance,
   client_json_req: i32,
-  // TODO: pass client and mod loader info to calculate version with more rules, instead of passing require version
-  // ref: https://github.com/Hex-Dragon/PCL2/blob/16e09c792ce8c13435fc6827e6da54170aaa3bc0/Plain%20Craft%20Launcher%202/Modules/Minecraft/ModLaunch.vb#L1130
 ) -> SJMCLResult<JavaInfo> {
-  let java_list = app.state::<Mutex<Vec<JavaInfo>>>().lock()?.clone();
-
   if let Some(game_java) = game_java.filter(|game_java| !game_java.auto) {
+    let java_list = app.state::<Mutex<Vec<JavaInfo>>>().lock()?.clone();
     return java_list
       .iter()
       .find(|j| j.exec_path == game_java.exec_path)
@@ -26,27 +230,63 @@ pub async fn select_java_runtime(
       .ok_or_else(|| LaunchError::SelectedJavaUnavailable.into());
   }
 
-  let mut min_version_req = get_minimum_java_version_by_game(app, instance, true).await;
+  rank_java_runtimes(app, instance, client_json_req)
+    .await?
+    .into_iter()
+    .find(|candidate| candidate.suitable)
+    .map(|candidate| candidate.java)
+    .ok_or_else(|| LaunchError::NoSuitableJava.into())
+}
 
-  if client_json_req > min_version_req {
-    min_version_req = client_json_req;
-  }
+/// The Mojang runtime to download when no installed runtime fits the instance.
+pub async fn get_recommended_java_version(
+  app: &AppHandle,
+  instance: &Instance,
+  client_json_req: i32,
+) -> Option<i32> {
+  let game_config = get_instance_game_config(app, instance);
+  let requirement = get_java_requirement(app, instance, &game_config, client_json_req).await;
+  MOJANG_JAVA_VERSIONS.into_iter().find(|version| {
+    let java = JavaInfo {
+      major_version: *version,
+      ..Default::default()
+    };
+    evaluate_java_runtime(&java, &requirement, "").suitable
+  })
+}
 
-  let mut suitable_candidates = Vec::new();
-  for java in &java_list {
-    match java.major_version.cmp(&min_version_req) {
-      Ordering::Equal => return Ok(java.clone()),
-      Ordering::Greater => suitable_candidates.push(java.clone()),
-      _ => {}
-    }
-  }
+/// Download the recommended Mojang runtime in the background, returns its major version.
+pub async fn download_recommended_java_runtime(
+  app: &AppHandle,
+  instance: &Instance,
+  client_json_req: i32,
+) -> SJMCLResult<i32> {
+  let version = get_recommended_java_version(app, instance, client_json_req)
+    .await
+    .ok_or(LaunchError::NoSuitableJava)?;
 
-  if suitable_candidates.is_empty() {
-    Err(LaunchError::NoSuitableJava.into())
-  } else {
-    suitable_candidates.sort_by_key(|j| j.major_version);
-    Ok(suitable_candidates[0].clone())
+  // another launch may have scheduled the same runtime already
+  let task_group_prefix = format!("mojang-java?{}@", version);
+  let is_downloading = app
+    .state::<Pin<Box<TaskMonitor>>>()
+    .state_list()
+    .iter()
+    .any(|group| {
+      group.task_group.starts_with(&task_group_prefix) && group.status == GEventStatus::Started
+    });
+  if is_downloading {
+    return Ok(version);
   }
+
+  let download_params = build_mojang_java_download_params(app, &version.to_string()).await?;
+  schedule_progressive_task_group(
+    app.clone(),
+    format!("mojang-java?{}", version),
+    download_params,
+    true,
+  )
+  .await?;
+  Ok(version)
 }
 
 /// Get minimum java version requirement by game client version
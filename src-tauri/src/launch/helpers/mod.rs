@@ -5,3 +5,4 @@ pub mod jre_selector;
 pub mod log_parser;
 pub mod misc;
 pub mod process_monitor;
+pub mod server_process;
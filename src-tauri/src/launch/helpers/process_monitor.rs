@@ -16,7 +16,7 @@ use tokio;
 
 use crate::instance::models::misc::Instance;
 use crate::launch::constants::*;
-use crate::launch::models::{LaunchError, LaunchingState};
+use crate::launch::models::{LaunchError, LaunchingState, ServerProcess};
 use crate::launcher_config::models::{LauncherVisiablity, ProcessPriority};
 use crate::utils::shell::execute_command_line;
 use crate::utils::window::create_webview_window;
@@ -45,7 +45,7 @@ impl<T: Read + Send + 'static> OutputPipe<T> {
             .emit_to(&self.label, GAME_PROCESS_OUTPUT_EVENT, &line);
         }
         writeln!(self.log_file.lock().unwrap(), "{line}").unwrap();
-        // the first time when log contains 'render thread', 'lwjgl version', or 'lwjgl openal', send signal to launch command, close frontend modal.
+        // the first time when log contains 'render thread', 'lwjgl version', 'lwjgl openal' (or the server "Done" line), send signal to launch command, close frontend modal.
         if !self.game_ready_flag.load(Ordering::SeqCst)
           && READY_FLAG.iter().any(|p| line.to_lowercase().contains(p))
         {
@@ -203,6 +203,12 @@ pub async fn monitor_process(
 
     stop_polling_flag.store(true, Ordering::SeqCst);
     drop(log_file);
+    // release the console of a stopped dedicated server
+    {
+      let server_processes_state = app.state::<Mutex<HashMap<String, ServerProcess>>>();
+      let mut server_processes = server_processes_state.lock().unwrap();
+      server_processes.retain(|_, process| process.launching_id != id);
+    }
     // handle launcher main window visiablity
     match launcher_visibility {
       LauncherVisiablity::RunningHidden => {
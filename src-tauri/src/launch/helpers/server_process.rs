@@ -0,0 +1,175 @@
+use sjmcl_types::error::SJMCLResult;
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use crate::instance::helpers::server_properties::is_eula_accepted;
+use crate::instance::models::misc::{Instance, InstanceError, ModLoaderStatus, ServerInstance};
+use crate::launch::helpers::jre_selector::select_java_runtime;
+use crate::launch::helpers::process_monitor::{kill_process, monitor_process};
+use crate::launch::models::{LaunchError, LaunchingState, ServerProcess};
+use crate::launcher_config::helpers::java::refresh_and_update_javas;
+use crate::launcher_config::helpers::misc::get_global_game_config;
+use crate::launcher_config::models::LauncherVisiablity;
+use crate::utils::sys_info::get_memory_info;
+
+pub fn get_running_server_launching_id(app: &AppHandle, server_id: &str) -> Option<u64> {
+  let binding = app.state::<Mutex<HashMap<String, ServerProcess>>>();
+  let state = binding.lock().ok()?;
+  state.get(server_id).map(|process| process.launching_id)
+}
+
+/// Start a dedicated server with the console log window, returns the launching id and
+/// a receiver signalled once the server is ready to accept players.
+pub async fn start_server_process(
+  app: &AppHandle,
+  server: &ServerInstance,
+) -> SJMCLResult<(u64, Receiver<()>)> {
+  if server.mod_loader.status != ModLoaderStatus::Installed {
+    return Err(LaunchError::ModLoaderNotInstalled.into());
+  }
+  if !is_eula_accepted(&server.server_path).await {
+    return Err(InstanceError::ServerEulaNotAccepted.into());
+  }
+  if get_running_server_launching_id(app, &server.id).is_some() {
+    return Err(InstanceError::ServerAlreadyRunning.into());
+  }
+
+  refresh_and_update_javas(app).await;
+  let game_config = get_global_game_config(app);
+  let instance = Instance::from(server);
+  let selected_java = select_java_runtime(
+    app,
+    Some(&game_config.game_java),
+    &instance,
+    server.java_major_version,
+  )
+  .await?;
+
+  let mut max_mem_allocation = match server.max_mem_allocation {
+    Some(max_mem_allocation) => max_mem_allocation,
+    None if game_config.performance.auto_mem_allocation => {
+      (get_memory_info().suggested_max_alloc / 1024 / 1024) as u32
+    }
+    None => game_config.performance.max_mem_allocation,
+  };
+  if let Some(max_heap) = selected_java.max_heap_mb {
+    max_mem_allocation = max_mem_allocation.min(max_heap);
+  }
+
+  let mut args = vec![format!("-Xmx{}m", max_mem_allocation)];
+  args.extend(server.launch_args.iter().cloned());
+  args.push("nogui".to_string());
+  let full_command = format!("{} {}", selected_java.exec_path, args.join(" "));
+  log::info!("[Server Launch Command] {}", full_command);
+
+  let mut cmd_base = Command::new(&selected_java.exec_path);
+  #[cfg(target_os = "windows")]
+  cmd_base.creation_flags(0x08000000);
+
+  let mut child = cmd_base
+    .current_dir(&server.server_path)
+    .args(&args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()?;
+
+  let id = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+  let pid = child.id();
+  {
+    let binding = app.state::<Mutex<Vec<LaunchingState>>>();
+    let mut launching_queue = binding.lock()?;
+    // client launch steps work on the last state, keep a server from taking its place
+    launching_queue.insert(
+      0,
+      LaunchingState {
+        id,
+        selected_java,
+        selected_instance: instance,
+        game_config,
+        full_command,
+        pid,
+        ..LaunchingState::default()
+      },
+    );
+  }
+  {
+    let binding = app.state::<Mutex<HashMap<String, ServerProcess>>>();
+    let mut state = binding.lock()?;
+    state.insert(
+      server.id.clone(),
+      ServerProcess {
+        launching_id: id,
+        pid,
+        stdin: child.stdin.take(),
+      },
+    );
+  }
+
+  let (tx, rx) = mpsc::channel();
+  monitor_process(
+    app.clone(),
+    id,
+    child,
+    server.id.clone(),
+    true,
+    "",
+    LauncherVisiablity::Always,
+    tx,
+    None,
+  )
+  .await?;
+
+  Ok((id, rx))
+}
+
+/// Write a line to the server console.
+pub fn send_server_console_command(
+  app: &AppHandle,
+  server_id: &str,
+  command: &str,
+) -> SJMCLResult<()> {
+  let binding = app.state::<Mutex<HashMap<String, ServerProcess>>>();
+  let mut state = binding.lock()?;
+  let stdin = state
+    .get_mut(server_id)
+    .and_then(|process| process.stdin.as_mut())
+    .ok_or(InstanceError::ServerNotRunning)?;
+  writeln!(stdin, "{}", command.trim())?;
+  stdin.flush()?;
+  Ok(())
+}
+
+/// Ask the server to save and stop, it is killed if its console is unavailable.
+pub fn stop_server_process(app: &AppHandle, server_id: &str) -> SJMCLResult<()> {
+  let (launching_id, pid) = {
+    let binding = app.state::<Mutex<HashMap<String, ServerProcess>>>();
+    let state = binding.lock()?;
+    let process = state
+      .get(server_id)
+      .ok_or(InstanceError::ServerNotRunning)?;
+    (process.launching_id, process.pid)
+  };
+
+  {
+    // mark as manually stopped to avoid game error window popping up
+    let binding = app.state::<Mutex<Vec<LaunchingState>>>();
+    let mut launching_queue = binding.lock()?;
+    if let Some(launching) = launching_queue.iter_mut().find(|l| l.id == launching_id) {
+      launching.current_step = 0;
+    }
+  }
+
+  if send_server_console_command(app, server_id, "stop").is_err() {
+    kill_process(pid)?;
+  }
+  Ok(())
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
+use std::process::ChildStdin;
 use strum_macros::Display;
 
 use crate::account::models::PlayerInfo;
@@ -24,6 +25,46 @@ pub enum LaunchError {
 
 impl std::error::Error for LaunchError {}
 
+// why a java runtime is ranked lower or ruled out for an instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+  tag = "type",
+  rename_all = "camelCase",
+  rename_all_fields = "camelCase"
+)]
+pub enum JavaSelectionReason {
+  BelowMinimumVersion {
+    required: i32,
+  },
+  AboveMaximumVersion {
+    maximum: i32,
+  },
+  AbovePreferredVersion {
+    preferred: i32,
+  },
+  GarbageCollectorUnsupported {
+    minimum: i32,
+  },
+  ShenandoahUnavailable,
+  MemoryExceedsHeapLimit {
+    max_mem_allocation: u32,
+    max_heap: u32,
+  },
+  NonNativeArchitecture {
+    arch: String,
+  },
+  ExactVersionMatch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaCandidate {
+  pub java: JavaInfo,
+  pub suitable: bool,
+  pub score: i32,
+  pub reasons: Vec<JavaSelectionReason>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, SmartDefault)]
 #[serde(rename_all = "camelCase", default)]
 pub struct LaunchingState {
@@ -41,3 +82,20 @@ pub struct LaunchingState {
   #[default = 0] // default means not set yet
   pub pid: u32,
 }
+
+// a running dedicated server, see `start_server_process`
+#[derive(Debug)]
+pub struct ServerProcess {
+  pub launching_id: u64,
+  pub pid: u32,
+  pub stdin: Option<ChildStdin>, // console input, used to send commands and stop gracefully
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanTestServerInfo {
+  pub server_id: String,
+  pub launching_id: u64,
+  pub address: String, // for quick play multiplayer, e.g. "localhost:25565"
+  pub skipped_mods: Vec<String>, // client-only mods not copied to the server
+}
@@ -17,12 +17,16 @@ use crate::launcher_config::helpers::java::{
   build_mojang_java_download_params, get_java_info_from_command, get_java_info_from_release_file,
   refresh_and_update_javas,
 };
+use crate::launcher_config::helpers::java_runtime::{
+  build_java_runtime_download_params, install_java_runtime_archive, list_managed_java_runtimes,
+  resolve_java_runtime_package, uninstall_managed_java_runtime,
+};
 use crate::launcher_config::helpers::updater::{
   self, download_target_version, fetch_latest_version,
 };
 use crate::launcher_config::models::{
-  BuildType, GameDirectory, GraphicsApi, JavaInfo, LauncherConfig, LauncherConfigError,
-  VersionMetaInfo,
+  BuildType, GameDirectory, GraphicsApi, JavaInfo, JavaRuntimePackage, JavaRuntimeVendor,
+  LauncherConfig, LauncherConfigError, ManagedJavaRuntime, VersionMetaInfo,
 };
 use crate::tasks::{commands::schedule_progressive_task_group, monitor::TaskMonitor};
 use crate::utils::fs::{generate_unique_filename, get_subdirectories};
@@ -254,6 +258,50 @@ pub async fn download_mojang_java(app: AppHandle, version: String) -> SJMCLResul
   Ok(())
 }
 
+#[tauri::command]
+pub async fn download_java_runtime(
+  app: AppHandle,
+  vendor: JavaRuntimeVendor,
+  major_version: u32,
+  arch: Option<String>,
+) -> SJMCLResult<JavaRuntimePackage> {
+  let package = resolve_java_runtime_package(&app, vendor, major_version, arch).await?;
+  let download_params = build_java_runtime_download_params(&app, &package)?;
+
+  schedule_progressive_task_group(
+    app,
+    format!("java-runtime?{}", package.runtime_name),
+    download_params,
+    true,
+  )
+  .await?;
+
+  Ok(package)
+}
+
+#[tauri::command]
+pub async fn finish_java_runtime_install(
+  app: AppHandle,
+  package: JavaRuntimePackage,
+) -> SJMCLResult<()> {
+  let app_clone = app.clone();
+  tokio::task::spawn_blocking(move || install_java_runtime_archive(&app_clone, &package)).await??;
+  refresh_and_update_javas(&app).await;
+  Ok(())
+}
+
+#[tauri::command]
+pub fn retrieve_managed_java_runtimes(app: AppHandle) -> SJMCLResult<Vec<ManagedJavaRuntime>> {
+  list_managed_java_runtimes(&app)
+}
+
+#[tauri::command]
+pub async fn uninstall_java_runtime(app: AppHandle, name: String) -> SJMCLResult<()> {
+  uninstall_managed_java_runtime(&app, &name)?;
+  refresh_and_update_javas(&app).await;
+  Ok(())
+}
+
 #[tauri::command]
 pub async fn check_game_directory(app: AppHandle, dir: String) -> SJMCLResult<String> {
   let local_game_directories: Vec<_>;
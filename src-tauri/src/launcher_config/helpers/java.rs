@@ -1,17 +1,24 @@
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sjmcl_types::error::{SJMCLError, SJMCLResult};
+use sjmcl_types::storage::Storage;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_http::reqwest;
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
-use crate::launcher_config::models::{JavaInfo, LauncherConfig};
+use crate::APP_DATA_DIR;
+use crate::launcher_config::helpers::java_runtime::{
+  get_canonical_managed_runtime_dir, get_managed_runtime_dir,
+};
+use crate::launcher_config::models::{GarbageCollector, JavaInfo, LauncherConfig};
 use crate::resource::helpers::misc::{get_download_api, get_source_priority_list};
 use crate::resource::models::ResourceType;
 use crate::tasks::{PTaskParam, download::DownloadParam};
@@ -21,27 +28,83 @@ use crate::utils::fs::{PermissionOperation, manage_permissions_unix};
 use crate::utils::sys_info::get_all_drive_mount_points;
 
 pub async fn refresh_and_update_javas(app: &AppHandle) {
+  // user-added paths from config state.
+  let extra_java_paths = {
+    let config_binding = app.state::<Mutex<LauncherConfig>>();
+    let config_state = config_binding.lock().unwrap();
+    config_state.extra_java_paths.clone()
+  };
+
+  // new or changed runtimes are spawned several times to probe them, keep it off the async runtime
+  let app_clone = app.clone();
+  let mut java_list =
+    tokio::task::spawn_blocking(move || collect_java_infos(&app_clone, &extra_java_paths))
+      .await
+      .unwrap_or_default();
+  java_list.sort_by(|a, b| {
+    b.major_version
+      .cmp(&a.major_version)
+      .then_with(|| a.exec_path.len().cmp(&b.exec_path.len()))
+  });
+
+  // check selected java in global game config, if not exist, remove it.
+  {
+    let config_binding = app.state::<Mutex<LauncherConfig>>();
+    let mut config_state = config_binding.lock().unwrap();
+    let current_selected_java = &config_state.global_game_config.game_java.exec_path;
+    let is_valid_java = java_list
+      .iter()
+      .any(|java| &java.exec_path == current_selected_java);
+    if !is_valid_java {
+      config_state.global_game_config.game_java.exec_path = "".to_string();
+    }
+  }
+
+  let javas_binding = app.state::<Mutex<Vec<JavaInfo>>>();
+  let mut javas_state = javas_binding.lock().unwrap();
+  *javas_state = java_list;
+
+  let probe_cache_binding = app.state::<Mutex<JavaProbeCache>>();
+  if let Ok(mut probe_cache) = probe_cache_binding.lock() {
+    let listed_paths = javas_state
+      .iter()
+      .map(|java| java.exec_path.as_str())
+      .collect::<HashSet<_>>();
+    probe_cache
+      .entries
+      .retain(|path, _| listed_paths.contains(path.as_str()));
+    let _ = probe_cache.save();
+  }
+}
+
+// Blocking, spawns the java executables not probed yet.
+fn collect_java_infos(app: &AppHandle, extra_java_paths: &[String]) -> Vec<JavaInfo> {
   // get java paths from system PATH, etc.
   let mut java_paths = get_java_paths(app);
+  java_paths.extend(extra_java_paths.iter().cloned());
 
-  // add user-added paths from config state.
-  let config_binding = app.state::<Mutex<LauncherConfig>>();
-  let mut config_state = config_binding.lock().unwrap();
-  let extra_java_paths = config_state.extra_java_paths.clone();
-  java_paths.extend(extra_java_paths.clone());
-
+  let managed_runtime_dir = get_canonical_managed_runtime_dir(app);
   let mut seen_paths: HashMap<String, JavaInfo> = HashMap::new();
 
   for java_exec_path in java_paths {
     // ensure execute permissions (for Linux and macOS)
     let _ = manage_permissions_unix(&java_exec_path, 0o111, PermissionOperation::Upgrade);
 
-    // get java version and vendor (prioritize release file, fallback to command)
+    // get java version and vendor (prioritize release file, fallback to the probe)
+    let probe = get_java_probe_info_cached(app, &java_exec_path);
     let (mut vendor, mut version) = get_java_info_from_release_file(&java_exec_path);
-    if vendor.is_none() || version.is_none() {
-      let (cmd_vendor, cmd_version) = get_java_info_from_command(&java_exec_path);
-      vendor = vendor.or(cmd_vendor);
-      version = version.or(cmd_version);
+    match &probe {
+      Some(probe) => {
+        vendor = vendor.or(probe.vendor.clone());
+        version = version.or(probe.version.clone());
+      }
+      // some JVMs reject the probe flags, read the plain properties instead
+      None if vendor.is_none() || version.is_none() => {
+        let (cmd_vendor, cmd_version) = get_java_info_from_command(&java_exec_path);
+        vendor = vendor.or(cmd_vendor);
+        version = version.or(cmd_version);
+      }
+      None => {}
     }
     let full_version = match version {
       Some(v) => v,
@@ -61,6 +124,13 @@ pub async fn refresh_and_update_javas(app: &AppHandle) {
 
     let (major_version, is_lts) = parse_java_major_version(&full_version);
     let is_user_added = extra_java_paths.contains(&java_exec_path);
+    let arch = probe
+      .as_ref()
+      .and_then(|probe| probe.arch.clone())
+      .or_else(|| get_java_arch_from_release_file(&java_exec_path));
+    let is_managed = managed_runtime_dir
+      .as_ref()
+      .is_some_and(|dir| Path::new(&java_exec_path).starts_with(dir));
 
     let java_info = JavaInfo {
       name: format!("{} {}", if is_jdk { "JDK" } else { "JRE" }, full_version),
@@ -69,30 +139,148 @@ pub async fn refresh_and_update_javas(app: &AppHandle) {
       exec_path: java_exec_path.clone(),
       vendor,
       is_user_added,
+      is_managed,
+      arch,
+      is_jdk,
+      is_64bit: probe.as_ref().and_then(|probe| probe.is_64bit),
+      supported_gcs: probe.as_ref().map(|probe| probe.supported_gcs.clone()),
+      max_heap_mb: probe.as_ref().and_then(|probe| probe.max_heap_mb),
     };
 
     seen_paths.entry(java_exec_path).or_insert(java_info);
   }
 
-  let mut java_list: Vec<JavaInfo> = seen_paths.into_values().collect();
-  java_list.sort_by(|a, b| {
-    b.major_version
-      .cmp(&a.major_version)
-      .then_with(|| a.exec_path.len().cmp(&b.exec_path.len()))
-  });
+  seen_paths.into_values().collect()
+}
 
-  // check selected java in global game config, if not exist, remove it.
-  let current_selected_java = &config_state.global_game_config.game_java.exec_path;
-  let is_valid_java = java_list
-    .iter()
-    .any(|java| &java.exec_path == current_selected_java);
-  if !is_valid_java {
-    config_state.global_game_config.game_java.exec_path = "".to_string();
+const JAVA_PROBE_CACHE_FILE_NAME: &str = "java_probe_cache.json";
+
+// heap sizes tried on 32-bit runtimes, the largest one the JVM can reserve is recorded (MB)
+const PROBE_HEAP_SIZES_32BIT: [u32; 5] = [3072, 2560, 2048, 1536, 1024];
+
+// (-XX flag, collector)
+const GC_FLAGS: [(&str, GarbageCollector); 5] = [
+  ("UseG1GC", GarbageCollector::G1gc),
+  ("UseZGC", GarbageCollector::Zgc),
+  ("UseShenandoahGC", GarbageCollector::Shenandoah),
+  ("UseParallelGC", GarbageCollector::Parallel),
+  ("UseSerialGC", GarbageCollector::Serial),
+];
+
+/// What a java executable reports about itself when run.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct JavaProbeInfo {
+  pub vendor: Option<String>,
+  pub version: Option<String>,
+  pub arch: Option<String>,
+  pub is_64bit: Option<bool>,
+  pub supported_gcs: Vec<GarbageCollector>,
+  pub max_heap_mb: Option<u32>,
+}
+
+/// Probe results by executable path, reused while the executable's mtime is unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct JavaProbeCache {
+  pub entries: HashMap<String, (u64, JavaProbeInfo)>, // exec path -> (mtime, probe)
+}
+
+impl Storage for JavaProbeCache {
+  fn file_path() -> PathBuf {
+    APP_DATA_DIR.get().unwrap().join(JAVA_PROBE_CACHE_FILE_NAME)
   }
+}
 
-  let javas_binding = app.state::<Mutex<Vec<JavaInfo>>>();
-  let mut javas_state = javas_binding.lock().unwrap();
-  *javas_state = java_list;
+fn run_java_silently(java_path: &str, args: &[&str]) -> Option<std::process::Output> {
+  let mut cmd = Command::new(java_path);
+  cmd.args(args);
+  #[cfg(target_os = "windows")]
+  cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+  cmd.output().ok()
+}
+
+/// Run the executable once to read its properties and VM flags.
+pub fn probe_java(java_path: &str) -> Option<JavaProbeInfo> {
+  let output = run_java_silently(
+    java_path,
+    &[
+      "-XshowSettings:properties",
+      "-XX:+PrintFlagsFinal",
+      "-version",
+    ],
+  )?;
+  if !output.status.success() {
+    return None;
+  }
+  let mut output_str = String::from_utf8_lossy(&output.stdout).to_string();
+  output_str.push_str(&String::from_utf8_lossy(&output.stderr));
+
+  let mut probe = JavaProbeInfo::default();
+  for line in output_str.lines() {
+    let trimmed = line.trim();
+    if let Some((key, value)) = trimmed.split_once(" = ") {
+      let value = value.trim().trim_matches('"').to_string();
+      match key.trim() {
+        "java.vendor" => probe.vendor = Some(value),
+        "java.version" => probe.version = Some(value),
+        "os.arch" => probe.arch = Some(normalize_java_arch(&value)),
+        "sun.arch.data.model" => probe.is_64bit = Some(value == "64"),
+        _ => {}
+      }
+    }
+    // "     bool UseZGC        = false        {product} {default}", experimental flags need unlocking
+    let mut tokens = trimmed.split_whitespace();
+    if tokens.next() == Some("bool")
+      && let Some(flag) = tokens.next()
+      && !trimmed.contains("{experimental}")
+      && let Some((_, gc)) = GC_FLAGS.iter().find(|(name, _)| *name == flag)
+      && !probe.supported_gcs.contains(gc)
+    {
+      probe.supported_gcs.push(gc.clone());
+    }
+  }
+  probe.version.as_ref()?;
+
+  if probe.is_64bit == Some(false) {
+    probe.max_heap_mb = PROBE_HEAP_SIZES_32BIT.into_iter().find(|size| {
+      run_java_silently(java_path, &[&format!("-Xmx{}m", size), "-version"])
+        .is_some_and(|output| output.status.success())
+    });
+  }
+  Some(probe)
+}
+
+fn get_file_mtime(path: &str) -> Option<u64> {
+  Some(
+    fs::metadata(path)
+      .ok()?
+      .modified()
+      .ok()?
+      .duration_since(UNIX_EPOCH)
+      .ok()?
+      .as_secs(),
+  )
+}
+
+/// Same as `probe_java`, spawning the executable only if it is new or changed since last time.
+pub fn get_java_probe_info_cached(app: &AppHandle, java_path: &str) -> Option<JavaProbeInfo> {
+  let mtime = get_file_mtime(java_path)?;
+  let probe_cache_binding = app.state::<Mutex<JavaProbeCache>>();
+  if let Ok(probe_cache) = probe_cache_binding.lock()
+    && let Some((cached_mtime, probe)) = probe_cache.entries.get(java_path)
+    && *cached_mtime == mtime
+  {
+    return Some(probe.clone());
+  }
+
+  let probe = probe_java(java_path)?;
+  if let Ok(mut probe_cache) = probe_cache_binding.lock() {
+    probe_cache
+      .entries
+      .insert(java_path.to_string(), (mtime, probe.clone()));
+  }
+  Some(probe)
 }
 
 pub fn get_java_paths(app: &AppHandle) -> Vec<String> {
@@ -302,16 +490,13 @@ fn scan_java_paths_in_common_directories(app: &AppHandle) -> Vec<String> {
 
 fn scan_java_paths_in_sjmcl_data_directory(app: &AppHandle) -> Vec<String> {
   let mut java_paths = Vec::new();
-  #[cfg(any(target_os = "linux", target_os = "windows"))]
-  {
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-      java_paths.extend(search_java_homes_in_directory(app_data_dir.join("runtime")));
-    }
-    // TODO: test on the Linux.
+  // Mojang runtimes on Linux and Windows, and vendor runtimes on all platforms
+  if let Ok(runtime_dir) = get_managed_runtime_dir(app) {
+    java_paths.extend(search_java_homes_in_directory(runtime_dir));
   }
   #[cfg(target_os = "macos")]
   {
-    if let Ok(rt) = app.path().app_data_dir().map(|p| p.join("runtime")) {
+    if let Ok(rt) = get_managed_runtime_dir(app) {
       for v in [8, 11, 17, 21, 25] {
         java_paths.extend(search_java_homes_in_mac_java_virtual_machines(
           rt.join(format!("java-{v}")),
@@ -415,6 +600,26 @@ pub fn get_java_info_from_release_file(java_path: &str) -> (Option<String>, Opti
   (vendor, full_version)
 }
 
+/// Normalize an `os.arch` or `OS_ARCH` value to the names used by `tauri_plugin_os::arch`.
+pub fn normalize_java_arch(arch: &str) -> String {
+  match arch.trim().to_lowercase().as_str() {
+    "amd64" | "x64" | "x86-64" => "x86_64".to_string(),
+    "arm64" => "aarch64".to_string(),
+    "i386" | "i486" | "i586" | "i686" | "x32" => "x86".to_string(),
+    "aarch32" | "armv7" | "armv7l" => "arm".to_string(),
+    other => other.to_string(),
+  }
+}
+
+pub fn get_java_arch_from_release_file(java_path: &str) -> Option<String> {
+  let java_home = PathBuf::from(java_path).parent()?.parent()?.to_path_buf();
+  let content = fs::read_to_string(java_home.join("release")).ok()?;
+  content.lines().find_map(|line| {
+    let value = line.strip_prefix("OS_ARCH=")?;
+    Some(normalize_java_arch(value.trim().trim_matches('"')))
+  })
+}
+
 pub fn get_java_info_from_command(java_path: &str) -> (Option<String>, Option<String>) {
   // use "java -version -XshowSettings:properties" command to get info
   #[cfg(target_os = "windows")]
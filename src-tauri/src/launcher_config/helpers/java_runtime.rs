@@ -0,0 +1,397 @@
+use serde_json::Value;
+use sjmcl_types::error::SJMCLResult;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+use crate::launcher_config::models::{
+  JavaRuntimePackage, JavaRuntimeVendor, LauncherConfig, LauncherConfigError, ManagedJavaRuntime,
+};
+use crate::tasks::PTaskParam;
+use crate::tasks::download::DownloadParam;
+use crate::utils::fs::{RemoveDirGuard, calculate_sha256};
+
+// Runtimes downloaded by the launcher (Mojang's and the vendor builds below) live in
+// `<app data>/runtime/<name>`, each folder being a java home.
+
+pub fn get_managed_runtime_dir(app: &AppHandle) -> SJMCLResult<PathBuf> {
+  Ok(app.path().app_data_dir()?.join("runtime"))
+}
+
+/// Canonicalized runtime directory, for comparing with the canonicalized java paths.
+pub fn get_canonical_managed_runtime_dir(app: &AppHandle) -> Option<PathBuf> {
+  let dir = fs::canonicalize(get_managed_runtime_dir(app).ok()?).ok()?;
+  // remove "\\?\" prefix like the scanned java paths on Windows
+  Some(PathBuf::from(
+    dir
+      .to_string_lossy()
+      .trim_start_matches(r"\\?\")
+      .to_string(),
+  ))
+}
+
+/// (os, arch) in the naming of the vendor, from the launcher os type and `tauri_plugin_os::arch`.
+fn get_vendor_platform(
+  vendor: JavaRuntimeVendor,
+  os_type: &str,
+  arch: &str,
+) -> Option<(&'static str, &'static str)> {
+  let os = match (vendor, os_type) {
+    (JavaRuntimeVendor::Temurin, "macos") => "mac",
+    (_, "macos") => "macos",
+    (_, "windows") => "windows",
+    (_, "linux") => "linux",
+    _ => return None,
+  };
+  let arch = match (vendor, arch) {
+    (_, "x86_64") => "x64",
+    (_, "aarch64") => "aarch64",
+    (JavaRuntimeVendor::Temurin, "x86") => "x32",
+    (JavaRuntimeVendor::Zulu, "x86") => "x86",
+    (JavaRuntimeVendor::Temurin, "arm") => "arm",
+    _ => return None,
+  };
+  Some((os, arch))
+}
+
+// (download url, sha256, archive file name)
+type ResolvedArchive = (String, String, String);
+
+async fn fetch_sha256_file(client: &reqwest::Client, url: &str) -> Option<String> {
+  let response = client.get(url).send().await.ok()?;
+  if !response.status().is_success() {
+    return None;
+  }
+  // "<hash>  <file name>" or the bare hash
+  let sha256 = response
+    .text()
+    .await
+    .ok()?
+    .split_whitespace()
+    .next()?
+    .to_lowercase();
+  (sha256.len() == 64 && sha256.chars().all(|c| c.is_ascii_hexdigit())).then_some(sha256)
+}
+
+// https://api.adoptium.net/q/swagger-ui/
+async fn resolve_temurin_archive(
+  client: &reqwest::Client,
+  major_version: u32,
+  os: &str,
+  arch: &str,
+) -> Option<ResolvedArchive> {
+  // prefer the smaller JRE, not all versions and platforms have one
+  for image_type in ["jre", "jdk"] {
+    let url = format!(
+      "https://api.adoptium.net/v3/assets/latest/{}/hotspot?os={}&architecture={}&image_type={}&vendor=eclipse",
+      major_version, os, arch, image_type
+    );
+    let Ok(response) = client.get(url).send().await else {
+      continue;
+    };
+    let Ok(json) = response.json::<Value>().await else {
+      continue;
+    };
+    let package = json
+      .get(0)
+      .and_then(|asset| asset.pointer("/binary/package"));
+    if let Some(package) = package
+      && let (Some(link), Some(checksum), Some(name)) = (
+        package["link"].as_str(),
+        package["checksum"].as_str(),
+        package["name"].as_str(),
+      )
+    {
+      return Some((link.to_string(), checksum.to_lowercase(), name.to_string()));
+    }
+  }
+  None
+}
+
+// https://docs.azul.com/core/install/metadata-api
+async fn resolve_zulu_archive(
+  client: &reqwest::Client,
+  major_version: u32,
+  os: &str,
+  arch: &str,
+  archive_type: &str,
+) -> Option<ResolvedArchive> {
+  for package_type in ["jre", "jdk"] {
+    let url = format!(
+      "https://api.azul.com/metadata/v1/zulu/packages/?java_version={}&os={}&arch={}&archive_type={}&java_package_type={}&javafx_bundled=false&crac_supported=false&latest=true&release_status=ga&availability_types=CA&page=1&page_size=1",
+      major_version, os, arch, archive_type, package_type
+    );
+    let Ok(response) = client.get(url).send().await else {
+      continue;
+    };
+    let Ok(json) = response.json::<Value>().await else {
+      continue;
+    };
+    let Some(package_uuid) = json.get(0).and_then(|p| p["package_uuid"].as_str()) else {
+      continue;
+    };
+    // the checksum is only in the package details
+    let details = client
+      .get(format!(
+        "https://api.azul.com/metadata/v1/zulu/packages/{}",
+        package_uuid
+      ))
+      .send()
+      .await
+      .ok()?
+      .json::<Value>()
+      .await
+      .ok()?;
+    if let (Some(link), Some(sha256), Some(name)) = (
+      details["download_url"].as_str(),
+      details["sha256_hash"].as_str(),
+      details["name"].as_str(),
+    ) {
+      return Some((link.to_string(), sha256.to_lowercase(), name.to_string()));
+    }
+  }
+  None
+}
+
+// GraalVM and Microsoft publish stable "latest" links, with the checksum next to the archive.
+async fn resolve_static_archive(
+  client: &reqwest::Client,
+  url: String,
+  sha256_url: String,
+) -> Option<ResolvedArchive> {
+  let sha256 = fetch_sha256_file(client, &sha256_url).await?;
+  let name = url.rsplit('/').next()?.to_string();
+  Some((url, sha256, name))
+}
+
+/// Find the latest build of the vendor for the major version and architecture (the current
+/// one by default).
+pub async fn resolve_java_runtime_package(
+  app: &AppHandle,
+  vendor: JavaRuntimeVendor,
+  major_version: u32,
+  arch: Option<String>,
+) -> SJMCLResult<JavaRuntimePackage> {
+  let (os_type, default_arch) = {
+    let config_binding = app.state::<Mutex<LauncherConfig>>();
+    let config_state = config_binding.lock()?;
+    (
+      config_state.basic_info.os_type.clone(),
+      config_state.basic_info.arch.clone(),
+    )
+  };
+  let arch = arch.unwrap_or(default_arch);
+  let (os, vendor_arch) =
+    get_vendor_platform(vendor, &os_type, &arch).ok_or(LauncherConfigError::JavaRuntimeNotFound)?;
+  let archive_type = if os_type == "windows" {
+    "zip"
+  } else {
+    "tar.gz"
+  };
+  let client = app.state::<reqwest::Client>();
+
+  let resolved = match vendor {
+    JavaRuntimeVendor::Temurin => {
+      resolve_temurin_archive(&client, major_version, os, vendor_arch).await
+    }
+    JavaRuntimeVendor::Zulu => {
+      resolve_zulu_archive(&client, major_version, os, vendor_arch, archive_type).await
+    }
+    JavaRuntimeVendor::GraalVM => {
+      let url = format!(
+        "https://download.oracle.com/graal/{0}/latest/graalvm-jdk-{0}_{1}-{2}_bin.{3}",
+        major_version, os, vendor_arch, archive_type
+      );
+      let sha256_url = format!("{}.sha256", url);
+      resolve_static_archive(&client, url, sha256_url).await
+    }
+    JavaRuntimeVendor::Microsoft => {
+      let url = format!(
+        "https://aka.ms/download-jdk/microsoft-jdk-{}-{}-{}.{}",
+        major_version, os, vendor_arch, archive_type
+      );
+      let sha256_url = format!("{}.sha256sum.txt", url);
+      resolve_static_archive(&client, url, sha256_url).await
+    }
+  };
+  let (url, sha256, archive_name) = resolved.ok_or(LauncherConfigError::JavaRuntimeNotFound)?;
+
+  Ok(JavaRuntimePackage {
+    vendor,
+    major_version,
+    arch: vendor_arch.to_string(),
+    url,
+    sha256,
+    archive_name,
+    runtime_name: format!("{}-{}-{}", vendor, major_version, vendor_arch),
+  })
+}
+
+// only plain names directly under the runtime or cache directory, packages come from the frontend
+fn is_plain_file_name(name: &str) -> bool {
+  !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\'])
+}
+
+fn get_archive_path(app: &AppHandle, package: &JavaRuntimePackage) -> SJMCLResult<PathBuf> {
+  if !is_plain_file_name(&package.archive_name) {
+    return Err(LauncherConfigError::JavaRuntimeNotFound.into());
+  }
+  let config_binding = app.state::<Mutex<LauncherConfig>>();
+  let config_state = config_binding.lock()?;
+  Ok(
+    config_state
+      .download
+      .cache
+      .directory
+      .join(&package.archive_name),
+  )
+}
+
+pub fn build_java_runtime_download_params(
+  app: &AppHandle,
+  package: &JavaRuntimePackage,
+) -> SJMCLResult<Vec<PTaskParam>> {
+  Ok(vec![PTaskParam::Download(DownloadParam {
+    src: url::Url::parse(&package.url).map_err(|_| LauncherConfigError::FetchError)?,
+    dest: get_archive_path(app, package)?,
+    filename: Some(package.archive_name.clone()),
+    sha1: None, // verified with sha256 on installation
+  })])
+}
+
+/// Locate the java home in an extracted runtime: the folder itself, a single top-level folder,
+/// or the `Contents/Home` of a macOS bundle.
+fn find_java_home(dir: &Path) -> Option<PathBuf> {
+  let has_java =
+    |home: &Path| home.join("bin/java").is_file() || home.join("bin/java.exe").is_file();
+  if has_java(dir) {
+    return Some(dir.to_path_buf());
+  }
+  fs::read_dir(dir)
+    .ok()?
+    .flatten()
+    .map(|entry| entry.path())
+    .filter(|path| path.is_dir())
+    .flat_map(|path| [path.join("Contents/Home"), path])
+    .find(|home| has_java(home))
+}
+
+/// Verify the downloaded archive and extract it as a managed runtime. Blocking, run it with
+/// `spawn_blocking`.
+pub fn install_java_runtime_archive(
+  app: &AppHandle,
+  package: &JavaRuntimePackage,
+) -> SJMCLResult<PathBuf> {
+  if !is_plain_file_name(&package.runtime_name) {
+    return Err(LauncherConfigError::JavaRuntimeNotFound.into());
+  }
+  let archive_path = get_archive_path(app, package)?;
+  if calculate_sha256(&archive_path)? != package.sha256 {
+    let _ = fs::remove_file(&archive_path);
+    return Err(LauncherConfigError::JavaRuntimeChecksumMismatch.into());
+  }
+
+  let runtime_dir = get_managed_runtime_dir(app)?;
+  let temp_dir = runtime_dir.join(format!(".{}.tmp", package.runtime_name));
+  if temp_dir.exists() {
+    fs::remove_dir_all(&temp_dir)?;
+  }
+  fs::create_dir_all(&temp_dir)?;
+  let _guard = RemoveDirGuard::new(temp_dir.clone());
+
+  if package.archive_name.ends_with(".zip") {
+    ZipArchive::new(File::open(&archive_path)?)?
+      .extract(&temp_dir)
+      .map_err(|_| LauncherConfigError::JavaRuntimeExtractFailed)?;
+  } else {
+    // keep symlinks and permissions, like the macOS launcher updater does
+    let status = Command::new("tar")
+      .arg("-xzf")
+      .arg(&archive_path)
+      .arg("-C")
+      .arg(&temp_dir)
+      .status()?;
+    if !status.success() {
+      return Err(LauncherConfigError::JavaRuntimeExtractFailed.into());
+    }
+  }
+
+  let java_home = find_java_home(&temp_dir).ok_or(LauncherConfigError::JavaRuntimeExtractFailed)?;
+  let target_dir = runtime_dir.join(&package.runtime_name);
+  if target_dir.exists() {
+    fs::remove_dir_all(&target_dir)?;
+  }
+  fs::rename(&java_home, &target_dir)?;
+  let _ = fs::remove_file(&archive_path);
+
+  Ok(target_dir)
+}
+
+fn get_dir_size(dir: &Path) -> u64 {
+  WalkDir::new(dir)
+    .into_iter()
+    .flatten()
+    .filter_map(|entry| entry.metadata().ok())
+    .filter(|metadata| metadata.is_file())
+    .map(|metadata| metadata.len())
+    .sum()
+}
+
+pub fn list_managed_java_runtimes(app: &AppHandle) -> SJMCLResult<Vec<ManagedJavaRuntime>> {
+  let runtime_dir = get_managed_runtime_dir(app)?;
+  let Ok(entries) = fs::read_dir(&runtime_dir) else {
+    return Ok(Vec::new());
+  };
+
+  let mut runtimes = Vec::new();
+  for entry in entries.flatten() {
+    let path = entry.path();
+    let name = entry.file_name().to_string_lossy().to_string();
+    if !path.is_dir() || name.starts_with('.') {
+      continue;
+    }
+    // Mojang runtimes on macOS are nested one level deeper
+    let Some(java_home) = find_java_home(&path).or_else(|| {
+      fs::read_dir(&path)
+        .ok()?
+        .flatten()
+        .find_map(|sub| find_java_home(&sub.path()))
+    }) else {
+      continue;
+    };
+    #[cfg(target_os = "windows")]
+    let java_bin = java_home.join(r"bin\java.exe");
+    #[cfg(not(target_os = "windows"))]
+    let java_bin = java_home.join("bin/java");
+
+    runtimes.push(ManagedJavaRuntime {
+      name,
+      size: get_dir_size(&path),
+      exec_path: fs::canonicalize(&java_bin)
+        .unwrap_or(java_bin)
+        .to_string_lossy()
+        .trim_start_matches(r"\\?\")
+        .to_string(),
+      path,
+    });
+  }
+  runtimes.sort_by(|a, b| a.name.cmp(&b.name));
+  Ok(runtimes)
+}
+
+pub fn uninstall_managed_java_runtime(app: &AppHandle, name: &str) -> SJMCLResult<()> {
+  if !is_plain_file_name(name) {
+    return Err(LauncherConfigError::JavaRuntimeNotFound.into());
+  }
+  let path = get_managed_runtime_dir(app)?.join(name);
+  if !path.is_dir() {
+    return Err(LauncherConfigError::JavaRuntimeNotFound.into());
+  }
+  fs::remove_dir_all(&path).map_err(|_| LauncherConfigError::FileDeletionFailed)?;
+  Ok(())
+}
@@ -1,4 +1,5 @@
 pub mod graphics;
 pub mod java;
+pub mod java_runtime;
 pub mod misc;
 pub mod updater;
@@ -40,6 +40,52 @@ pub struct JavaInfo {
   pub major_version: i32, // major version + LTS flag
   pub is_lts: bool,
   pub is_user_added: bool,
+  #[serde(default)]
+  pub is_managed: bool, // downloaded into the launcher's runtime directory
+  #[serde(default)]
+  pub arch: Option<String>, // normalized, e.g. "x86_64", "aarch64", "x86"
+  #[serde(default)]
+  pub is_jdk: bool,
+  #[serde(default)]
+  pub is_64bit: Option<bool>,
+  #[serde(default)]
+  pub supported_gcs: Option<Vec<GarbageCollector>>, // None if the runtime could not be probed
+  #[serde(default)]
+  pub max_heap_mb: Option<u32>, // only limited on 32-bit runtimes
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, EnumString, Display)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "kebab-case")]
+pub enum JavaRuntimeVendor {
+  Temurin,
+  Zulu,
+  #[serde(rename = "graalvm")]
+  #[strum(serialize = "graalvm")]
+  GraalVM,
+  Microsoft,
+}
+
+// A vendor build resolved for download, passed back to finish the installation.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaRuntimePackage {
+  pub vendor: JavaRuntimeVendor,
+  pub major_version: u32,
+  pub arch: String,
+  pub url: String,
+  pub sha256: String,
+  pub archive_name: String,
+  pub runtime_name: String, // folder name under the runtime directory
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedJavaRuntime {
+  pub name: String,
+  pub path: PathBuf,
+  pub exec_path: String,
+  pub size: u64,
 }
 
 // https://github.com/HMCL-dev/HMCL/blob/d9e3816b8edf9e7275e4349d4fc67a5ef2e3c6cf/HMCLCore/src/main/java/org/jackhuang/hmcl/game/ProcessPriority.java#L20
@@ -295,6 +341,8 @@ structstruck::strike! {
       pub source: struct {
         #[default = "auto"]
         pub strategy: String,
+        // additional manifests in the `version_manifest` format, e.g. Combat Test builds
+        pub extra_version_manifests: Vec<String>,
       },
       pub transmission: struct {
         #[default = true]
@@ -449,6 +497,9 @@ pub enum LauncherConfigError {
   JavaExecInvalid,
   HasActiveDownloadTasks,
   FileDeletionFailed,
+  JavaRuntimeNotFound,
+  JavaRuntimeChecksumMismatch,
+  JavaRuntimeExtractFailed,
 }
 
 impl std::error::Error for LauncherConfigError {}
@@ -12,10 +12,11 @@ mod utils;
 use account::helpers::authlib_injector::info::refresh_and_update_auth_servers;
 use account::helpers::offline::yggdrasil_server::YggdrasilServer;
 use account::models::AccountInfo;
+use instance::helpers::local_cache::LocalResourceCache;
 use instance::helpers::misc::refresh_and_update_instances;
-use instance::models::misc::Instance;
-use launch::models::LaunchingState;
-use launcher_config::helpers::java::refresh_and_update_javas;
+use instance::models::misc::{Instance, ServerInstance};
+use launch::models::{LaunchingState, ServerProcess};
+use launcher_config::helpers::java::{JavaProbeCache, refresh_and_update_javas};
 use launcher_config::models::{JavaInfo, LauncherConfig};
 use resource::helpers::mod_db::{ModDataBase, initialize_mod_db};
 use resource::helpers::translation::LocalModTranslationsCache;
@@ -98,6 +99,10 @@ pub async fn run() {
         launcher_config::commands::retrieve_java_list,
         launcher_config::commands::validate_java,
         launcher_config::commands::download_mojang_java,
+        launcher_config::commands::download_java_runtime,
+        launcher_config::commands::finish_java_runtime_install,
+        launcher_config::commands::retrieve_managed_java_runtimes,
+        launcher_config::commands::uninstall_java_runtime,
         launcher_config::commands::retrieve_supported_graphics_renderers,
         launcher_config::commands::check_game_directory,
         launcher_config::commands::clear_download_cache,
@@ -141,12 +146,36 @@ pub async fn run() {
         instance::commands::retrieve_game_server_list,
         instance::commands::add_game_server,
         instance::commands::delete_game_server,
+        instance::commands::update_game_server,
+        instance::commands::reorder_game_servers,
+        instance::commands::sync_game_server_list,
+        instance::commands::retrieve_game_options,
+        instance::commands::update_game_options,
+        instance::commands::copy_game_options,
         instance::commands::retrieve_local_mod_list,
+        instance::commands::check_mod_dependencies,
+        instance::commands::generate_instance_lock,
+        instance::commands::sync_instance_to_lock,
+        instance::commands::finish_instance_lock_sync,
         instance::commands::retrieve_resource_pack_list,
+        instance::commands::toggle_resource_pack,
+        instance::commands::reorder_resource_packs,
         instance::commands::retrieve_server_resource_pack_list,
         instance::commands::retrieve_schematic_list,
         instance::commands::retrieve_shader_pack_list,
+        instance::commands::select_shader_pack,
         instance::commands::retrieve_screenshot_list,
+        instance::commands::retrieve_screenshot_page,
+        instance::commands::delete_screenshots,
+        instance::commands::copy_screenshots,
+        instance::commands::retrieve_mod_sets,
+        instance::commands::save_mod_set,
+        instance::commands::delete_mod_set,
+        instance::commands::apply_mod_set,
+        instance::commands::start_mod_bisect,
+        instance::commands::report_mod_bisect,
+        instance::commands::stop_mod_bisect,
+        instance::commands::toggle_mod_pin,
         instance::commands::toggle_mod_by_extension,
         instance::commands::create_launch_desktop_shortcut,
         instance::commands::finish_mod_loader_install,
@@ -156,15 +185,39 @@ pub async fn run() {
         instance::commands::change_optifine,
         instance::commands::remove_mod_loader,
         instance::commands::remove_optifine,
+        instance::commands::retrieve_client_patch_list,
+        instance::commands::retrieve_merged_client_info,
+        instance::commands::add_client_patch,
+        instance::commands::reorder_client_patches,
+        instance::commands::remove_client_patch,
+        instance::commands::install_custom_version,
+        instance::commands::install_version_from_zip,
+        instance::commands::finish_version_zip_install,
+        instance::commands::retrieve_server_instance_list,
+        instance::commands::create_server_instance,
+        instance::commands::finish_server_instance_install,
+        instance::commands::delete_server_instance,
+        instance::commands::accept_server_eula,
+        instance::commands::retrieve_server_properties,
+        instance::commands::update_server_properties,
+        instance::commands::copy_mods_to_server_instance,
+        instance::commands::create_lan_test_server,
         instance::commands::retrieve_modpack_meta_info,
+        instance::commands::update_instance_modpack,
+        instance::commands::finish_instance_modpack_update,
         instance::commands::add_custom_instance_icon,
         instance::commands::retrieve_exportable_file_list,
         instance::commands::export_modpack,
         launch::commands::select_suitable_jre,
+        launch::commands::retrieve_java_candidates,
         launch::commands::validate_game_files,
         launch::commands::validate_selected_player,
         launch::commands::launch_game,
         launch::commands::cancel_launch_process,
+        launch::commands::start_server_instance,
+        launch::commands::stop_server_instance,
+        launch::commands::send_server_command,
+        launch::commands::start_lan_test_server,
         launch::commands::open_game_log_window,
         launch::commands::retrieve_game_log,
         launch::commands::retrieve_game_launching_state,
@@ -179,6 +232,10 @@ pub async fn run() {
         resource::commands::fetch_remote_resource_by_local,
         resource::commands::update_mods,
         resource::commands::fetch_remote_resource_by_id,
+        resource::commands::resolve_mod_dependencies,
+        resource::commands::install_mods,
+        resource::commands::check_mod_updates,
+        resource::commands::rollback_mod_updates,
         discover::commands::fetch_news_sources_info,
         discover::commands::fetch_news_post_summaries,
         extension::commands::retrieve_extension_list,
@@ -239,9 +296,15 @@ pub async fn run() {
         let instances: HashMap<String, Instance> = HashMap::new();
         app.manage(Mutex::new(instances));
 
+        let server_instances: HashMap<String, ServerInstance> = HashMap::new();
+        app.manage(Mutex::new(server_instances));
+
         let javas: Vec<JavaInfo> = vec![];
         app.manage(Mutex::new(javas));
 
+        let java_probe_cache = JavaProbeCache::load().unwrap_or_default();
+        app.manage(Mutex::new(java_probe_cache));
+
         let mod_database = ModDataBase::new();
         app.manage(Mutex::new(mod_database));
 
@@ -250,6 +313,9 @@ pub async fn run() {
         let local_mod_translations = LocalModTranslationsCache::load().unwrap_or_default();
         app.manage(Mutex::new(local_mod_translations));
 
+        let local_resource_cache = LocalResourceCache::load_or_default();
+        app.manage(Mutex::new(local_resource_cache));
+
         let resource_translations = ResourceTranslationsCache::load().unwrap_or_default();
         app.manage(Mutex::new(resource_translations));
 
@@ -259,6 +325,9 @@ pub async fn run() {
         let launching_queue = Vec::<LaunchingState>::new();
         app.manage(Mutex::new(launching_queue));
 
+        let server_processes: HashMap<String, ServerProcess> = HashMap::new();
+        app.manage(Mutex::new(server_processes));
+
         // start local yggdrasil server for offline accounts
         let local_ygg_server = YggdrasilServer::new();
         app.manage(Mutex::new(local_ygg_server.clone()));
@@ -1,44 +1,84 @@
+use regex::RegexBuilder;
 use sjmcl_types::error::SJMCLResult;
-use std::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Manager, State};
 use tauri_plugin_http::reqwest;
+use tokio::sync::Semaphore;
 
 use crate::instance::helpers::client_json::McClientInfo;
+use crate::instance::helpers::local_cache::{
+  fetch_remote_resource_by_local_cached, get_cached_remote_project_ids,
+};
 use crate::instance::helpers::misc::get_instance_subdir_path_by_id;
-use crate::instance::models::misc::{InstanceSubdirType, ModLoaderType};
+use crate::instance::helpers::mods::common::{
+  get_local_mod_ids, get_mod_info_from_jar, load_local_mod_infos,
+};
+use crate::instance::models::misc::{
+  Instance, InstanceError, InstanceSubdirType, LocalModInfo, ModLoaderType,
+};
 use crate::launcher_config::models::LauncherConfig;
 use crate::resource::helpers::curseforge::{
-  fetch_remote_resource_by_id_curseforge, fetch_remote_resource_by_local_curseforge,
-  fetch_resource_list_by_name_curseforge, fetch_resource_version_packs_curseforge,
+  fetch_remote_resource_by_id_curseforge, fetch_resource_list_by_name_curseforge,
+  fetch_resource_version_packs_curseforge,
 };
+use crate::resource::helpers::dependency::build_mod_dependency_resolution;
 use crate::resource::helpers::loader_meta::fabric::get_fabric_meta_by_game_version;
 use crate::resource::helpers::loader_meta::forge::get_forge_meta_by_game_version;
+use crate::resource::helpers::loader_meta::liteloader::get_liteloader_meta_by_game_version;
 use crate::resource::helpers::loader_meta::neoforge::get_neoforge_meta_by_game_version;
 use crate::resource::helpers::loader_meta::optifine::get_optifine_meta_by_game_version;
 use crate::resource::helpers::loader_meta::quilt::get_quilt_meta_by_game_version;
 use crate::resource::helpers::misc::get_source_priority_list;
+use crate::resource::helpers::mod_update::{MOD_BACKUP_SUFFIX, fetch_mod_update_candidate};
 use crate::resource::helpers::modrinth::{
-  fetch_remote_resource_by_id_modrinth, fetch_remote_resource_by_local_modrinth,
-  fetch_resource_list_by_name_modrinth, fetch_resource_version_packs_modrinth,
+  fetch_remote_resource_by_id_modrinth, fetch_resource_list_by_name_modrinth,
+  fetch_resource_version_packs_modrinth,
+};
+use crate::resource::helpers::version_manifest::{
+  get_extra_game_version_manifests, get_game_version_manifest,
 };
-use crate::resource::helpers::version_manifest::get_game_version_manifest;
 use crate::resource::models::{
-  GameClientResourceInfo, ModLoaderResourceInfo, ModUpdateQuery, OptiFineResourceInfo,
-  OtherResourceFileInfo, OtherResourceInfo, OtherResourceSearchQuery, OtherResourceSearchRes,
-  OtherResourceSource, OtherResourceVersionPack, OtherResourceVersionPackQuery, ResourceError,
+  GameClientResourceInfo, ModDependencyResolution, ModLoaderResourceInfo, ModUpdateCandidate,
+  ModUpdateQuery, OptiFineResourceInfo, OtherResourceFileInfo, OtherResourceInfo,
+  OtherResourceSearchQuery, OtherResourceSearchRes, OtherResourceSource, OtherResourceVersionPack,
+  OtherResourceVersionPackQuery, ResourceError,
 };
 use crate::tasks::PTaskParam;
 use crate::tasks::commands::schedule_progressive_task_group;
 use crate::tasks::download::DownloadParam;
+use crate::utils::fs::get_files_with_regex;
 
 #[tauri::command]
 pub async fn fetch_game_version_list(app: AppHandle) -> SJMCLResult<Vec<GameClientResourceInfo>> {
-  let priority_list = {
+  let (priority_list, extra_manifests) = {
     let launcher_config_state = app.state::<Mutex<LauncherConfig>>();
     let launcher_config = launcher_config_state.lock()?;
-    get_source_priority_list(&launcher_config)
+    (
+      get_source_priority_list(&launcher_config),
+      launcher_config
+        .download
+        .source
+        .extra_version_manifests
+        .clone(),
+    )
   };
-  get_game_version_manifest(&app, &priority_list).await
+  let mut game_info_list = get_game_version_manifest(&app, &priority_list).await?;
+
+  // official versions take precedence over unofficial feeds with the same id
+  let known_ids = game_info_list
+    .iter()
+    .map(|info| info.id.clone())
+    .collect::<HashSet<_>>();
+  game_info_list.extend(
+    get_extra_game_version_manifests(&app, &extra_manifests)
+      .await
+      .into_iter()
+      .filter(|info| !known_ids.contains(&info.id)),
+  );
+  Ok(game_info_list)
 }
 
 #[tauri::command]
@@ -78,6 +118,9 @@ pub async fn fetch_mod_loader_version_list(
     ModLoaderType::Quilt => {
       Ok(get_quilt_meta_by_game_version(&app, &priority_list, &game_version).await?)
     }
+    ModLoaderType::LiteLoader => {
+      Ok(get_liteloader_meta_by_game_version(&app, &priority_list, &game_version).await?)
+    }
     // TODO here
     _ => Err(ResourceError::NoDownloadApi.into()),
   }
@@ -169,15 +212,17 @@ pub async fn fetch_remote_resource_by_local(
   download_source: OtherResourceSource,
   file_path: String,
 ) -> SJMCLResult<OtherResourceFileInfo> {
-  match download_source {
-    OtherResourceSource::CurseForge => {
-      Ok(fetch_remote_resource_by_local_curseforge(&app, &file_path).await?)
-    }
-    OtherResourceSource::Modrinth => {
-      Ok(fetch_remote_resource_by_local_modrinth(&app, &file_path).await?)
-    }
-    _ => Err(ResourceError::NoDownloadApi.into()),
+  fetch_remote_resource_by_local_cached(&app, &file_path, &download_source).await
+}
+
+// names from the frontend or remote APIs must stay directly inside the mods folder
+fn join_mod_file_name(mods_dir: &Path, file_name: &str) -> SJMCLResult<PathBuf> {
+  if file_name.contains(['/', '\\'])
+    || Path::new(file_name).file_name() != Some(OsStr::new(file_name))
+  {
+    return Err(ResourceError::FileOperationError.into());
   }
+  Ok(mods_dir.join(file_name))
 }
 
 #[tauri::command]
@@ -186,9 +231,14 @@ pub async fn update_mods(
   instance_id: String,
   queries: Vec<ModUpdateQuery>,
 ) -> SJMCLResult<()> {
-  if queries.is_empty() {
-    return Ok(());
-  }
+  let pinned_mods = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance.pinned_mods.clone()
+  };
 
   let mods_dir = match get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
   {
@@ -196,12 +246,39 @@ pub async fn update_mods(
     None => return Ok(()),
   };
 
-  let mut download_tasks = Vec::new();
+  // (old file, new file) of every mod to update, pinned mods are skipped
+  let mut updates = Vec::new();
   for query in &queries {
-    let file_path = mods_dir.join(&query.file_name);
+    let old_file_path = PathBuf::from(&query.old_file_path);
+    let Some(old_file_name) = old_file_path.file_name().and_then(OsStr::to_str) else {
+      return Err(ResourceError::FileOperationError.into());
+    };
+    let old_file_path = join_mod_file_name(&mods_dir, old_file_name)?;
+    if pinned_mods.iter().any(|name| name == old_file_name) {
+      continue;
+    }
+    updates.push((
+      query,
+      old_file_path,
+      join_mod_file_name(&mods_dir, &query.file_name)?,
+    ));
+  }
+  if updates.is_empty() {
+    return Ok(());
+  }
+
+  let mut download_tasks = Vec::new();
+  for (query, old_file_path, file_path) in &updates {
+    // keep a backup for rollback when the new file overwrites the old one
+    if old_file_path == file_path
+      && let Err(e) = std::fs::copy(file_path, get_mod_backup_path(old_file_path))
+    {
+      log::error!("Failed to back up old mod file: {}", e);
+      return Err(ResourceError::FileOperationError.into());
+    }
     let download_param = DownloadParam {
       src: url::Url::parse(&query.url).map_err(|_| ResourceError::ParseError)?,
-      dest: file_path,
+      dest: file_path.clone(),
       filename: None,
       sha1: Some(query.sha1.clone()),
     };
@@ -210,19 +287,169 @@ pub async fn update_mods(
 
   schedule_progressive_task_group(app, "mod-update".to_string(), download_tasks, true).await?;
 
-  for query in &queries {
-    let old_file_path = &query.old_file_path;
-    let new_file_path = mods_dir.join(&query.file_name);
-
-    if old_file_path != &new_file_path.to_string_lossy().to_string() {
-      let old_backup_path = format!("{}.old", old_file_path);
-      if let Err(e) = std::fs::rename(old_file_path, &old_backup_path) {
-        log::error!("Failed to rename old mod file: {}", e);
-        return Err(ResourceError::FileOperationError.into());
-      }
+  for (_, old_file_path, file_path) in &updates {
+    if old_file_path != file_path
+      && let Err(e) = std::fs::rename(old_file_path, get_mod_backup_path(old_file_path))
+    {
+      log::error!("Failed to rename old mod file: {}", e);
+      return Err(ResourceError::FileOperationError.into());
+    }
+  }
+
+  Ok(())
+}
+
+fn get_mod_backup_path(file_path: &Path) -> PathBuf {
+  let mut backup_path = file_path.as_os_str().to_owned();
+  backup_path.push(MOD_BACKUP_SUFFIX);
+  PathBuf::from(backup_path)
+}
+
+#[tauri::command]
+pub async fn check_mod_updates(
+  app: AppHandle,
+  instance_id: String,
+  download_source: OtherResourceSource,
+) -> SJMCLResult<Vec<ModUpdateCandidate>> {
+  let (loader_type, game_version, pinned_mods) = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    (
+      instance.mod_loader.loader_type,
+      instance.version.clone(),
+      instance.pinned_mods.clone(),
+    )
+  };
+
+  let mods_dir = match get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+  {
+    Some(path) => path,
+    None => return Ok(Vec::new()),
+  };
+
+  // disabled mods are skipped
+  let valid_extensions = RegexBuilder::new(r"\.(jar|zip)$")
+    .case_insensitive(true)
+    .build()
+    .unwrap();
+  let mod_paths = get_files_with_regex(&mods_dir, &valid_extensions).unwrap_or_default();
+
+  let semaphore = Arc::new(Semaphore::new(
+    std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get),
+  ));
+  let mut tasks = Vec::new();
+  for path in mod_paths {
+    let permit = semaphore
+      .clone()
+      .acquire_owned()
+      .await
+      .map_err(|_| InstanceError::SemaphoreAcquireFailed)?;
+    let app = app.clone();
+    let download_source = download_source.clone();
+    let game_version = game_version.clone();
+    tasks.push(tokio::spawn(async move {
+      let candidate =
+        fetch_mod_update_candidate(&app, &download_source, &path, loader_type, &game_version).await;
+      drop(permit);
+      candidate.ok().flatten()
+    }));
+  }
+
+  let mut candidates = Vec::new();
+  for task in tasks {
+    if let Ok(Some(mut candidate)) = task.await {
+      let file_name = PathBuf::from(&candidate.file_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+      candidate.pinned = pinned_mods.contains(&file_name);
+      candidates.push(candidate);
+    }
+  }
+  candidates.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+
+  Ok(candidates)
+}
+
+/// Restore `.old` backups left by `update_mods` and remove the files that replaced them.
+/// All backups in the mods folder are restored if `backup_file_names` is empty.
+/// Every backup is tried, returns the names of the ones that could not be restored.
+#[tauri::command]
+pub async fn rollback_mod_updates(
+  app: AppHandle,
+  instance_id: String,
+  backup_file_names: Vec<String>,
+) -> SJMCLResult<Vec<String>> {
+  let loader_type = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance.mod_loader.loader_type
+  };
+  let loader_type = (loader_type != ModLoaderType::Unknown).then_some(loader_type);
+
+  let mods_dir = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+
+  let backup_paths: Vec<PathBuf> = if backup_file_names.is_empty() {
+    let backup_regex = RegexBuilder::new(r"\.(jar|zip)\.old$")
+      .case_insensitive(true)
+      .build()
+      .unwrap();
+    get_files_with_regex(&mods_dir, &backup_regex).unwrap_or_default()
+  } else {
+    backup_file_names
+      .iter()
+      .map(|name| join_mod_file_name(&mods_dir, name))
+      .collect::<SJMCLResult<_>>()?
+  };
+
+  let current_mods = load_local_mod_infos(&mods_dir, loader_type).await;
+  let mut failed = Vec::new();
+  for backup_path in backup_paths {
+    let backup_name = backup_path
+      .file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default();
+    if let Err(e) = restore_mod_backup(&mods_dir, &backup_path, &current_mods, loader_type).await {
+      log::error!("Failed to restore old mod file {}: {:?}", backup_name, e);
+      failed.push(backup_name);
     }
   }
 
+  Ok(failed)
+}
+
+async fn restore_mod_backup(
+  mods_dir: &Path,
+  backup_path: &PathBuf,
+  current_mods: &[LocalModInfo],
+  loader_type: Option<ModLoaderType>,
+) -> SJMCLResult<()> {
+  let backup_name = backup_path
+    .file_name()
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or_default();
+  let Some(restored_name) = backup_name.strip_suffix(MOD_BACKUP_SUFFIX) else {
+    return Err(ResourceError::FileOperationError.into());
+  };
+  let restored_path = mods_dir.join(restored_name);
+  let backup_info = get_mod_info_from_jar(backup_path, loader_type).await?;
+
+  for newer in current_mods
+    .iter()
+    .filter(|m| m.mod_id == backup_info.mod_id && m.file_path != restored_path)
+  {
+    if newer.file_path.exists() {
+      std::fs::remove_file(&newer.file_path).map_err(|_| ResourceError::FileOperationError)?;
+    }
+  }
+  std::fs::rename(backup_path, &restored_path).map_err(|_| ResourceError::FileOperationError)?;
   Ok(())
 }
 
@@ -242,3 +469,81 @@ pub async fn fetch_remote_resource_by_id(
     _ => Err(ResourceError::NoDownloadApi.into()),
   }
 }
+
+#[tauri::command]
+pub async fn resolve_mod_dependencies(
+  app: AppHandle,
+  instance_id: String,
+  download_source: OtherResourceSource,
+  file_info: OtherResourceFileInfo,
+) -> SJMCLResult<ModDependencyResolution> {
+  let (loader_type, game_version) = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    let instance = state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    (instance.mod_loader.loader_type, instance.version.clone())
+  };
+
+  let (local_project_ids, local_mod_ids) =
+    match get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods) {
+      Some(mods_dir) => {
+        // disabled mods are not counted as installed
+        let valid_extensions = RegexBuilder::new(r"\.(jar|zip)$")
+          .case_insensitive(true)
+          .build()
+          .unwrap();
+        let mod_paths = get_files_with_regex(&mods_dir, &valid_extensions).unwrap_or_default();
+        (
+          get_cached_remote_project_ids(&app, &mod_paths, &download_source).await,
+          get_local_mod_ids(
+            &mods_dir,
+            (loader_type != ModLoaderType::Unknown).then_some(loader_type),
+          )
+          .await,
+        )
+      }
+      None => Default::default(),
+    };
+
+  build_mod_dependency_resolution(
+    &app,
+    &download_source,
+    file_info,
+    loader_type,
+    &game_version,
+    &local_project_ids,
+    &local_mod_ids,
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn install_mods(
+  app: AppHandle,
+  instance_id: String,
+  files: Vec<OtherResourceFileInfo>,
+) -> SJMCLResult<()> {
+  let Some(first_file) = files.first() else {
+    return Ok(());
+  };
+
+  let mods_dir = get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+
+  let task_group = format!("mod-install?{}", first_file.file_name);
+  let mut download_tasks = Vec::new();
+  for file in &files {
+    download_tasks.push(PTaskParam::Download(DownloadParam {
+      src: url::Url::parse(&file.download_url).map_err(|_| ResourceError::ParseError)?,
+      dest: join_mod_file_name(&mods_dir, &file.file_name)?,
+      filename: None,
+      sha1: (!file.sha1.is_empty()).then(|| file.sha1.clone()),
+    }));
+  }
+
+  schedule_progressive_task_group(app, task_group, download_tasks, true).await?;
+
+  Ok(())
+}
@@ -70,11 +70,14 @@ pub fn get_curseforge_api(
       let mod_id = id.ok_or(ResourceError::ParseError)?;
       format!("{}/mods/{}/files", base_url, mod_id)
     }
-    OtherResourceApiEndpoint::FromLocal => format!("{}/fingerprints/432", base_url),
+    OtherResourceApiEndpoint::FromLocal | OtherResourceApiEndpoint::FromLocalBatch => {
+      format!("{}/fingerprints/432", base_url)
+    }
     OtherResourceApiEndpoint::ById => {
       let mod_id = id.ok_or(ResourceError::ParseError)?;
       format!("{}/mods/{}", base_url, mod_id)
     }
+    OtherResourceApiEndpoint::ByIds => format!("{}/mods", base_url),
     OtherResourceApiEndpoint::TranslateDesc => {
       let mod_id = id.ok_or(ResourceError::ParseError)?;
       format!("https://mod.mcimirror.top/translate/curseforge/{}", mod_id)
@@ -148,6 +151,11 @@ structstruck::strike! {
   }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CurseForgeChangelogRes {
+  pub data: String,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CurseForgeVersionPackSearchRes {
   pub data: Vec<CurseForgeFileInfo>,
@@ -297,6 +305,8 @@ impl From<(&CurseForgeFileInfo, Option<String>)> for OtherResourceFileInfo {
         })
         .collect(),
       loader,
+      file_id: cf_file.id.to_string(),
+      changelog: None,
     }
   }
 }
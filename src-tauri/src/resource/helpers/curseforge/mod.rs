@@ -2,9 +2,9 @@ pub mod misc;
 
 use hex;
 use misc::{
-  CurseForgeFileInfo, CurseForgeFingerprintRes, CurseForgeGetProjectRes, CurseForgeSearchRes,
-  CurseForgeVersionPackSearchRes, cvt_category_to_id, cvt_mod_loader_to_id, cvt_sort_by_to_id,
-  cvt_type_to_class_id, get_curseforge_api, make_curseforge_request,
+  CurseForgeChangelogRes, CurseForgeFileInfo, CurseForgeFingerprintRes, CurseForgeGetProjectRes,
+  CurseForgeSearchRes, CurseForgeVersionPackSearchRes, cvt_category_to_id, cvt_mod_loader_to_id,
+  cvt_sort_by_to_id, cvt_type_to_class_id, get_curseforge_api, make_curseforge_request,
   map_curseforge_file_to_version_pack,
 };
 use murmur2::murmur2;
@@ -275,3 +275,25 @@ pub async fn fetch_remote_resource_by_id_curseforge(
 
   Ok(resource_info)
 }
+
+pub async fn fetch_file_changelog_curseforge(
+  app: &AppHandle,
+  resource_id: &str,
+  file_id: &str,
+) -> SJMCLResult<String> {
+  let url = format!(
+    "{}/files/{}/changelog",
+    get_curseforge_api(OtherResourceApiEndpoint::ById, Some(resource_id))?,
+    file_id
+  );
+  let client = app.state::<reqwest::Client>();
+
+  let results = make_curseforge_request::<CurseForgeChangelogRes, ()>(
+    &client,
+    &url,
+    OtherResourceRequestType::Get,
+  )
+  .await?;
+
+  Ok(results.data) // in HTML
+}
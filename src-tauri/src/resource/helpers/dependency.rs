@@ -0,0 +1,223 @@
+use sjmcl_types::error::SJMCLResult;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tauri::AppHandle;
+
+use crate::instance::models::misc::ModLoaderType;
+use crate::resource::helpers::curseforge::{
+  fetch_remote_resource_by_id_curseforge, fetch_resource_version_packs_curseforge,
+};
+use crate::resource::helpers::modrinth::{
+  fetch_remote_resource_by_id_modrinth, fetch_resource_version_packs_modrinth,
+};
+use crate::resource::models::{
+  ModDependencyInfo, ModDependencyResolution, OtherResourceFileInfo, OtherResourceInfo,
+  OtherResourceSource, OtherResourceVersionPackQuery, ResourceError,
+};
+
+const ALL_FILTER: &str = "All";
+
+fn get_loader_filter(mod_loader: ModLoaderType) -> String {
+  match mod_loader {
+    ModLoaderType::Unknown => ALL_FILTER.to_string(),
+    ModLoaderType::LegacyForge => ModLoaderType::Forge.to_string(),
+    _ => mod_loader.to_string(),
+  }
+}
+
+// local mod ids are not namespaced by the download source, the project slug is the closest match.
+fn normalize_mod_id(id: &str) -> String {
+  id.to_lowercase().replace('_', "-")
+}
+
+// matched by the remote project of the local files, the slug is only a fallback for files
+// not looked up on the platform yet
+fn is_installed(
+  resource: &OtherResourceInfo,
+  local_project_ids: &HashSet<String>,
+  local_mod_ids: &HashSet<String>,
+) -> bool {
+  if local_project_ids.contains(&resource.id) {
+    return true;
+  }
+  let slug = normalize_mod_id(&resource.slug);
+  !slug.is_empty() && local_mod_ids.iter().any(|id| normalize_mod_id(id) == slug)
+}
+
+fn release_type_rank(release_type: &str) -> u8 {
+  match release_type {
+    "release" => 0,
+    "beta" => 1,
+    _ => 2,
+  }
+}
+
+async fn fetch_resource_info(
+  app: &AppHandle,
+  download_source: &OtherResourceSource,
+  resource_id: &str,
+) -> SJMCLResult<OtherResourceInfo> {
+  match download_source {
+    OtherResourceSource::CurseForge => {
+      fetch_remote_resource_by_id_curseforge(app, resource_id).await
+    }
+    OtherResourceSource::Modrinth => fetch_remote_resource_by_id_modrinth(app, resource_id).await,
+    _ => Err(ResourceError::NoDownloadApi.into()),
+  }
+}
+
+/// Pick the newest file of the resource for the given loader and game version, preferring stable releases.
+pub async fn fetch_best_mod_file(
+  app: &AppHandle,
+  download_source: &OtherResourceSource,
+  resource_id: &str,
+  mod_loader: ModLoaderType,
+  game_version: &str,
+) -> SJMCLResult<Option<OtherResourceFileInfo>> {
+  let query = OtherResourceVersionPackQuery {
+    resource_id: resource_id.to_string(),
+    mod_loader: get_loader_filter(mod_loader),
+    game_versions: vec![game_version.to_string()],
+    resource_type: "mod".to_string(),
+  };
+  let version_packs = match download_source {
+    OtherResourceSource::CurseForge => fetch_resource_version_packs_curseforge(app, &query).await?,
+    OtherResourceSource::Modrinth => fetch_resource_version_packs_modrinth(app, &query).await?,
+    _ => return Err(ResourceError::NoDownloadApi.into()),
+  };
+
+  let loader_filter = query.mod_loader;
+  let mut candidates = version_packs
+    .into_iter()
+    .filter(|pack| pack.name == game_version)
+    .flat_map(|pack| pack.items)
+    .filter(|file| file.file_name.ends_with(".jar"))
+    .filter(|file| {
+      loader_filter == ALL_FILTER
+        || file
+          .loader
+          .as_ref()
+          .is_none_or(|loader| loader.eq_ignore_ascii_case(&loader_filter))
+    })
+    .collect::<Vec<_>>();
+  candidates.sort_by(|a, b| {
+    release_type_rank(&a.release_type)
+      .cmp(&release_type_rank(&b.release_type))
+      .then_with(|| b.file_date.cmp(&a.file_date))
+  });
+
+  Ok(candidates.into_iter().next())
+}
+
+/// Walk the dependencies of `file` breadth-first. Required ones are resolved transitively
+/// unless already installed, optional and incompatible ones are only reported.
+pub async fn build_mod_dependency_resolution(
+  app: &AppHandle,
+  download_source: &OtherResourceSource,
+  file: OtherResourceFileInfo,
+  mod_loader: ModLoaderType,
+  game_version: &str,
+  local_project_ids: &HashSet<String>,
+  local_mod_ids: &HashSet<String>,
+) -> SJMCLResult<ModDependencyResolution> {
+  let mut resolution = ModDependencyResolution::default();
+  let mut resource_infos: HashMap<String, OtherResourceInfo> = HashMap::new();
+  let mut visited = HashSet::from([file.resource_id.clone()]);
+  let mut optional_deps = Vec::new();
+  let mut incompatible_deps = Vec::new();
+  let mut queue = VecDeque::from([file]);
+
+  while let Some(current) = queue.pop_front() {
+    for dep in &current.dependencies {
+      if dep.resource_id.is_empty() || dep.resource_id == current.resource_id {
+        continue;
+      }
+      match dep.relation.as_str() {
+        "required" => {
+          if !visited.insert(dep.resource_id.clone()) {
+            continue;
+          }
+          let resource = fetch_resource_info(app, download_source, &dep.resource_id).await?;
+          resource_infos.insert(dep.resource_id.clone(), resource.clone());
+          if is_installed(&resource, local_project_ids, local_mod_ids) {
+            resolution.satisfied.push(ModDependencyInfo {
+              resource,
+              file: None,
+              required_by: current.resource_id.clone(),
+            });
+            continue;
+          }
+          let dep_file = fetch_best_mod_file(
+            app,
+            download_source,
+            &dep.resource_id,
+            mod_loader,
+            game_version,
+          )
+          .await?;
+          match dep_file {
+            Some(dep_file) => queue.push_back(dep_file),
+            None => resolution.unresolved.push(ModDependencyInfo {
+              resource,
+              file: None,
+              required_by: current.resource_id.clone(),
+            }),
+          }
+        }
+        "optional" => optional_deps.push((dep.resource_id.clone(), current.resource_id.clone())),
+        "incompatible" => {
+          incompatible_deps.push((dep.resource_id.clone(), current.resource_id.clone()))
+        }
+        _ => {} // embedded, tool and include dependencies need no separate download
+      }
+    }
+    resolution.files.push(current);
+  }
+
+  // report optional dependencies that end up neither installed nor scheduled
+  let mut reported = HashSet::new();
+  for (resource_id, required_by) in optional_deps {
+    if visited.contains(&resource_id) || !reported.insert(resource_id.clone()) {
+      continue;
+    }
+    let Ok(resource) = fetch_resource_info(app, download_source, &resource_id).await else {
+      continue;
+    };
+    if !is_installed(&resource, local_project_ids, local_mod_ids) {
+      resolution.optional.push(ModDependencyInfo {
+        resource,
+        file: None,
+        required_by,
+      });
+    }
+  }
+
+  let scheduled = resolution
+    .files
+    .iter()
+    .map(|file| file.resource_id.clone())
+    .collect::<HashSet<_>>();
+  for (resource_id, required_by) in incompatible_deps {
+    let resource = match resource_infos.get(&resource_id) {
+      Some(resource) => resource.clone(),
+      None => match fetch_resource_info(app, download_source, &resource_id).await {
+        Ok(resource) => resource,
+        Err(_) => continue,
+      },
+    };
+    if scheduled.contains(&resource_id) || is_installed(&resource, local_project_ids, local_mod_ids)
+    {
+      let file = resolution
+        .files
+        .iter()
+        .find(|file| file.resource_id == resource_id)
+        .cloned();
+      resolution.incompatible.push(ModDependencyInfo {
+        resource,
+        file,
+        required_by,
+      });
+    }
+  }
+
+  Ok(resolution)
+}
@@ -1,6 +1,8 @@
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sjmcl_types::error::SJMCLResult;
+use std::collections::HashMap;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_http::reqwest;
 
@@ -8,6 +10,9 @@ use crate::instance::models::misc::ModLoaderType;
 use crate::resource::helpers::misc::get_download_api;
 use crate::resource::models::{ModLoaderResourceInfo, ResourceError, ResourceType, SourceType};
 
+const FORGE_PROMOTIONS_URL: &str =
+  "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
+
 #[derive(Serialize, Deserialize, Default)]
 struct ForgeMetaItem {
   pub branch: Option<Value>,
@@ -55,12 +60,78 @@ async fn get_forge_meta_by_game_version_bmcl(
   }
 }
 
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ForgePromotions {
+  pub promos: HashMap<String, String>,
+}
+
+fn get_forge_version_sort_key(version: &str) -> Vec<u32> {
+  version
+    .split('.')
+    .map(|part| part.parse().unwrap_or(0))
+    .collect()
+}
+
+// ref: https://github.com/HMCL-dev/HMCL/blob/main/HMCLCore/src/main/java/org/jackhuang/hmcl/download/forge/ForgeNewVersionList.java
 async fn get_forge_meta_by_game_version_official(
   app: &AppHandle,
   game_version: &str,
 ) -> SJMCLResult<Vec<ModLoaderResourceInfo>> {
-  let _ = (app, game_version);
-  Err(ResourceError::NoDownloadApi.into()) // TODO
+  let client = app.state::<reqwest::Client>();
+  let url = get_download_api(SourceType::Official, ResourceType::ForgeInstall)?
+    .join("maven-metadata.xml")?;
+  let response = client
+    .get(url)
+    .send()
+    .await
+    .map_err(|_| ResourceError::NetworkError)?;
+  if !response.status().is_success() {
+    return Err(ResourceError::NetworkError.into());
+  }
+  let metadata = response
+    .text()
+    .await
+    .map_err(|_| ResourceError::ParseError)?;
+
+  // promotions only mark builds, the list is still usable without them
+  let promos = match client.get(FORGE_PROMOTIONS_URL).send().await {
+    Ok(response) if response.status().is_success() => response
+      .json::<ForgePromotions>()
+      .await
+      .map(|promotions| promotions.promos)
+      .unwrap_or_default(),
+    _ => HashMap::new(),
+  };
+  // only the recommended build is marked, "latest" says nothing about stability
+  let recommended = promos.get(&format!("{}-recommended", game_version));
+
+  // e.g. `1.12.2-14.23.5.2860`, `1.7.10-10.13.4.1614-1.7.10` (with branch)
+  let version_re =
+    Regex::new(r"<version>([^<]+)</version>").map_err(|_| ResourceError::ParseError)?;
+  let prefix = format!("{}-", game_version);
+  let mut manifest = version_re
+    .captures_iter(&metadata)
+    .filter_map(|caps| caps[1].trim().strip_prefix(&prefix).map(String::from))
+    .map(|version| match version.split_once('-') {
+      Some((version, branch)) => (version.to_string(), Some(branch.to_string())),
+      None => (version, None),
+    })
+    .collect::<Vec<_>>();
+  manifest.sort_by_key(|(version, _)| std::cmp::Reverse(get_forge_version_sort_key(version)));
+
+  Ok(
+    manifest
+      .into_iter()
+      .map(|(version, branch)| ModLoaderResourceInfo {
+        loader_type: ModLoaderType::Forge,
+        stable: (recommended == Some(&version)).then_some(true),
+        version,
+        description: String::new(), // no release date in the official metadata
+        branch,
+      })
+      .collect(),
+  )
 }
 
 pub async fn get_forge_meta_by_game_version(
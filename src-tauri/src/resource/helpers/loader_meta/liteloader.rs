@@ -0,0 +1,179 @@
+use serde::Deserialize;
+use sjmcl_types::error::SJMCLResult;
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use url::Url;
+
+use crate::instance::models::misc::ModLoaderType;
+use crate::resource::helpers::misc::get_download_api;
+use crate::resource::models::{ModLoaderResourceInfo, ResourceError, ResourceType, SourceType};
+
+// ref: https://github.com/HMCL-dev/HMCL/blob/main/HMCLCore/src/main/java/org/jackhuang/hmcl/download/liteloader/LiteLoaderVersionList.java
+#[derive(Deserialize, Default)]
+struct LiteLoaderVersionManifest {
+  #[serde(default)]
+  pub versions: HashMap<String, LiteLoaderGameVersion>,
+}
+
+#[derive(Deserialize, Default)]
+struct LiteLoaderGameVersion {
+  pub repo: Option<LiteLoaderRepository>,
+  pub artefacts: Option<LiteLoaderBranch>,
+  pub snapshots: Option<LiteLoaderBranch>,
+}
+
+#[derive(Deserialize, Default)]
+struct LiteLoaderRepository {
+  pub url: String,
+}
+
+#[derive(Deserialize, Default)]
+struct LiteLoaderBranch {
+  #[serde(default)]
+  pub libraries: Vec<LiteLoaderLibrary>,
+  #[serde(rename = "com.mumfrey:liteloader", default)]
+  pub builds: HashMap<String, LiteLoaderBuild>,
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+struct LiteLoaderBuild {
+  pub tweak_class: String,
+  pub libraries: Vec<LiteLoaderLibrary>,
+  pub file: String,
+  pub version: String,
+  pub timestamp: String,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+pub struct LiteLoaderLibrary {
+  pub name: String,
+  pub url: Option<String>,
+}
+
+/// A LiteLoader build resolved for installation.
+#[derive(Debug, Clone)]
+pub struct LiteLoaderRemoteInfo {
+  pub version: String,
+  pub tweak_class: String,
+  pub libraries: Vec<LiteLoaderLibrary>,
+  pub jar_url: Url,
+}
+
+async fn fetch_liteloader_manifest(
+  app: &AppHandle,
+  priority_list: &[SourceType],
+) -> SJMCLResult<(SourceType, LiteLoaderVersionManifest)> {
+  let client = app.state::<reqwest::Client>();
+  for source_type in priority_list.iter() {
+    let url = get_download_api(*source_type, ResourceType::Liteloader)?;
+    match client.get(url).send().await {
+      Ok(response) => {
+        if response.status().is_success() {
+          if let Ok(manifest) = response.json::<LiteLoaderVersionManifest>().await {
+            return Ok((*source_type, manifest));
+          } else {
+            return Err(ResourceError::ParseError.into());
+          }
+        } else {
+          continue;
+        }
+      }
+      Err(_) => continue,
+    }
+  }
+  Err(ResourceError::NetworkError.into())
+}
+
+// "latest" duplicates one of the versioned entries
+fn iter_liteloader_builds(
+  game_version: &LiteLoaderGameVersion,
+) -> impl Iterator<Item = (&LiteLoaderBranch, &LiteLoaderBuild, bool)> {
+  [
+    (&game_version.artefacts, false),
+    (&game_version.snapshots, true),
+  ]
+  .into_iter()
+  .filter_map(|(branch, is_snapshot)| branch.as_ref().map(|branch| (branch, is_snapshot)))
+  .flat_map(|(branch, is_snapshot)| {
+    branch
+      .builds
+      .iter()
+      .filter(|(key, _)| key.as_str() != "latest")
+      .map(move |(_, build)| (branch, build, is_snapshot))
+  })
+}
+
+pub async fn get_liteloader_meta_by_game_version(
+  app: &AppHandle,
+  priority_list: &[SourceType],
+  game_version: &str,
+) -> SJMCLResult<Vec<ModLoaderResourceInfo>> {
+  let (_, manifest) = fetch_liteloader_manifest(app, priority_list).await?;
+  let Some(game_version) = manifest.versions.get(game_version) else {
+    return Ok(Vec::new());
+  };
+
+  let mut builds = iter_liteloader_builds(game_version)
+    .map(|(_, build, is_snapshot)| (build, is_snapshot))
+    .collect::<Vec<_>>();
+  builds.sort_by_key(|(build, _)| std::cmp::Reverse(build.timestamp.parse::<u64>().unwrap_or(0)));
+
+  Ok(
+    builds
+      .into_iter()
+      .map(|(build, is_snapshot)| ModLoaderResourceInfo {
+        loader_type: ModLoaderType::LiteLoader,
+        version: build.version.clone(),
+        description: String::new(),
+        stable: Some(!is_snapshot),
+        branch: None,
+      })
+      .collect(),
+  )
+}
+
+pub async fn get_liteloader_remote_info(
+  app: &AppHandle,
+  priority_list: &[SourceType],
+  game_version: &str,
+  loader_version: &str,
+) -> SJMCLResult<LiteLoaderRemoteInfo> {
+  let (source_type, manifest) = fetch_liteloader_manifest(app, priority_list).await?;
+  let game_version_info = manifest
+    .versions
+    .get(game_version)
+    .ok_or(ResourceError::ClientVersionNotFound)?;
+  let (branch, build, is_snapshot) = iter_liteloader_builds(game_version_info)
+    .find(|(_, build, _)| build.version == loader_version)
+    .ok_or(ResourceError::ParseError)?;
+
+  let jar_url = if is_snapshot {
+    // snapshots are not mirrored, use the repository declared by the manifest
+    let repo = game_version_info
+      .repo
+      .as_ref()
+      .ok_or(ResourceError::NoDownloadApi)?;
+    Url::parse(&repo.url)?.join(&format!(
+      "com/mumfrey/liteloader/{}/{}",
+      build.version, build.file
+    ))?
+  } else {
+    get_download_api(source_type, ResourceType::LiteloaderMaven)?.join(&format!(
+      "com/mumfrey/liteloader/{}/{}",
+      game_version, build.file
+    ))?
+  };
+
+  Ok(LiteLoaderRemoteInfo {
+    version: build.version.clone(),
+    tweak_class: build.tweak_class.clone(),
+    libraries: if build.libraries.is_empty() {
+      branch.libraries.clone()
+    } else {
+      build.libraries.clone()
+    },
+    jar_url,
+  })
+}
@@ -47,6 +47,7 @@ pub fn get_download_api(source: SourceType, resource_type: ResourceType) -> SJMC
       ResourceType::Liteloader => Ok(Url::parse(
         "https://dl.liteloader.com/versions/versions.json",
       )?),
+      ResourceType::LiteloaderMaven => Ok(Url::parse("https://dl.liteloader.com/versions/")?),
       ResourceType::OptiFine => Err(ResourceError::NoDownloadApi.into()), //
       ResourceType::AuthlibInjector => Ok(Url::parse("https://authlib-injector.yushi.moe/")?),
       ResourceType::FabricMeta => Ok(Url::parse("https://meta.fabricmc.net/")?),
@@ -88,6 +89,7 @@ pub fn get_download_api(source: SourceType, resource_type: ResourceType) -> SJMC
       ResourceType::Liteloader => Ok(Url::parse(
         "https://bmclapi.bangbang93.com/maven/com/mumfrey/liteloader/versions.json",
       )?),
+      ResourceType::LiteloaderMaven => Ok(Url::parse("https://bmclapi2.bangbang93.com/maven/")?),
       ResourceType::AuthlibInjector => Ok(Url::parse(
         "https://bmclapi2.bangbang93.com/mirrors/authlib-injector/",
       )?),
@@ -1,7 +1,9 @@
 pub mod curseforge;
+pub mod dependency;
 pub mod loader_meta;
 pub mod misc;
 pub mod mod_db;
+pub mod mod_update;
 pub mod modrinth;
 pub mod translation;
 pub mod version_manifest;
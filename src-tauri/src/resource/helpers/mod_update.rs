@@ -0,0 +1,53 @@
+use sjmcl_types::error::SJMCLResult;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::instance::helpers::local_cache::fetch_remote_resource_by_local_cached;
+use crate::instance::models::misc::ModLoaderType;
+use crate::resource::helpers::curseforge::fetch_file_changelog_curseforge;
+use crate::resource::helpers::dependency::fetch_best_mod_file;
+use crate::resource::models::{ModUpdateCandidate, OtherResourceSource};
+
+pub const MOD_BACKUP_SUFFIX: &str = ".old";
+
+/// Look up a local mod file by its hash, and return the newest compatible remote file if it is newer.
+pub async fn fetch_mod_update_candidate(
+  app: &AppHandle,
+  download_source: &OtherResourceSource,
+  file_path: &Path,
+  mod_loader: ModLoaderType,
+  game_version: &str,
+) -> SJMCLResult<Option<ModUpdateCandidate>> {
+  let file_path = file_path.to_string_lossy().to_string();
+  // sha1 for Modrinth, murmur2 fingerprint for CurseForge
+  let current = fetch_remote_resource_by_local_cached(app, &file_path, download_source).await?;
+
+  let Some(mut latest) = fetch_best_mod_file(
+    app,
+    download_source,
+    &current.resource_id,
+    mod_loader,
+    game_version,
+  )
+  .await?
+  else {
+    return Ok(None);
+  };
+  if latest.sha1.eq_ignore_ascii_case(&current.sha1) || latest.file_date <= current.file_date {
+    return Ok(None);
+  }
+
+  // Modrinth returns changelogs along with the version list
+  if latest.changelog.is_none() && *download_source == OtherResourceSource::CurseForge {
+    latest.changelog = fetch_file_changelog_curseforge(app, &latest.resource_id, &latest.file_id)
+      .await
+      .ok();
+  }
+
+  Ok(Some(ModUpdateCandidate {
+    file_path,
+    current,
+    latest,
+    pinned: false,
+  }))
+}
@@ -56,10 +56,12 @@ pub fn get_modrinth_api(
       let hash = param.ok_or(ResourceError::ParseError)?;
       format!("{}/version_file/{}", base_url, hash)
     }
+    OtherResourceApiEndpoint::FromLocalBatch => format!("{}/version_files", base_url),
     OtherResourceApiEndpoint::ById => {
       let project_id = param.ok_or(ResourceError::ParseError)?;
       format!("{}/project/{}", base_url, project_id)
     }
+    OtherResourceApiEndpoint::ByIds => format!("{}/projects", base_url),
     OtherResourceApiEndpoint::TranslateDesc => {
       let project_id = param.ok_or(ResourceError::ParseError)?;
       format!(
@@ -87,6 +89,8 @@ pub struct ModrinthProject {
   #[serde(alias = "updated")]
   pub date_modified: String,
   pub author: Option<String>,
+  #[serde(default)]
+  pub server_side: String, // "required", "optional", "unsupported" or "unknown"
 }
 
 #[derive(Deserialize, Debug)]
@@ -113,6 +117,7 @@ structstruck::strike! {
 structstruck::strike! {
 #[strikethrough[derive(Deserialize, Debug)]]
   pub struct ModrinthVersionPack {
+    pub id: String,
     pub project_id: String,
     pub dependencies: Vec<pub struct {
       pub project_id: Option<String>,
@@ -125,6 +130,8 @@ structstruck::strike! {
     pub downloads: u64,
     pub version_type: String,
     pub files: Vec<ModrinthFileInfo>,
+    #[serde(default)]
+    pub changelog: Option<String>,
   }
 }
 
@@ -277,6 +284,8 @@ impl From<(&ModrinthVersionPack, &ModrinthFileInfo, Option<String>)> for OtherRe
         })
         .collect(),
       loader,
+      file_id: version.id.clone(),
+      changelog: version.changelog.clone(),
     }
   }
 }
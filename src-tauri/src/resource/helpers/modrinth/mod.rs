@@ -182,6 +182,62 @@ pub async fn fetch_remote_resource_by_local_modrinth(
   )
 }
 
+/// Look up the `server_side` support of the projects owning the given files, keyed by sha1.
+/// Files unknown to Modrinth are absent from the result.
+pub async fn fetch_server_side_support_modrinth(
+  app: &AppHandle,
+  hashes: &[String],
+) -> SJMCLResult<HashMap<String, String>> {
+  if hashes.is_empty() {
+    return Ok(HashMap::new());
+  }
+  let client = app.state::<reqwest::Client>();
+
+  let url = get_modrinth_api(OtherResourceApiEndpoint::FromLocalBatch, None)?;
+  let payload = serde_json::json!({ "hashes": hashes, "algorithm": "sha1" });
+  let version_packs = make_modrinth_request::<HashMap<String, ModrinthVersionPack>, _>(
+    &client,
+    &url,
+    OtherResourceRequestType::Post(&payload),
+  )
+  .await?;
+  if version_packs.is_empty() {
+    return Ok(HashMap::new());
+  }
+
+  let mut project_ids = version_packs
+    .values()
+    .map(|version_pack| version_pack.project_id.clone())
+    .collect::<Vec<_>>();
+  project_ids.sort();
+  project_ids.dedup();
+
+  let mut params = HashMap::new();
+  params.insert("ids".to_string(), serde_json::to_string(&project_ids)?);
+  let url = get_modrinth_api(OtherResourceApiEndpoint::ByIds, None)?;
+  let projects = make_modrinth_request::<Vec<ModrinthProject>, _>(
+    &client,
+    &url,
+    OtherResourceRequestType::GetWithParams(&params),
+  )
+  .await?;
+  let server_sides = projects
+    .into_iter()
+    .map(|project| (project.project_id, project.server_side))
+    .collect::<HashMap<_, _>>();
+
+  Ok(
+    version_packs
+      .into_iter()
+      .filter_map(|(hash, version_pack)| {
+        server_sides
+          .get(&version_pack.project_id)
+          .map(|server_side| (hash, server_side.clone()))
+      })
+      .collect(),
+  )
+}
+
 pub async fn fetch_remote_resource_by_id_modrinth(
   app: &AppHandle,
   resource_id: &str,
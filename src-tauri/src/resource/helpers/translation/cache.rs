@@ -23,6 +23,8 @@ impl ResourceTranslationsCache {
       OtherResourceSource::CurseForge => "curseforge",
       OtherResourceSource::Modrinth => "modrinth",
       OtherResourceSource::MultiMc => "multimc",
+      OtherResourceSource::Technic => "technic",
+      OtherResourceSource::ATLauncher => "atlauncher",
       OtherResourceSource::Unknown => "unknown",
     };
 
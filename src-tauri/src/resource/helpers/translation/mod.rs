@@ -9,17 +9,14 @@ use sjmcl_types::storage::Storage;
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 
+use crate::instance::helpers::local_cache::fetch_remote_resource_by_local_cached;
 use crate::instance::models::misc::LocalModInfo;
 use crate::launcher_config::models::LauncherConfig;
+use crate::resource::helpers::curseforge::fetch_remote_resource_by_id_curseforge;
 use crate::resource::helpers::curseforge::misc::translate_description_curseforge;
-use crate::resource::helpers::curseforge::{
-  fetch_remote_resource_by_id_curseforge, fetch_remote_resource_by_local_curseforge,
-};
 use crate::resource::helpers::mod_db::ModDataBase;
+use crate::resource::helpers::modrinth::fetch_remote_resource_by_id_modrinth;
 use crate::resource::helpers::modrinth::misc::translate_description_modrinth;
-use crate::resource::helpers::modrinth::{
-  fetch_remote_resource_by_id_modrinth, fetch_remote_resource_by_local_modrinth,
-};
 use crate::resource::models::{OtherResourceInfo, OtherResourceSource};
 use crate::utils::string::contains_chinese;
 
@@ -57,7 +54,12 @@ pub async fn add_local_mod_translations(
     let app_clone = app.clone();
     let file_path_clone = file_path.clone();
     tokio::spawn(async move {
-      let file_info = fetch_remote_resource_by_local_modrinth(&app_clone, &file_path_clone).await?;
+      let file_info = fetch_remote_resource_by_local_cached(
+        &app_clone,
+        &file_path_clone,
+        &OtherResourceSource::Modrinth,
+      )
+      .await?;
       let resource_info =
         fetch_remote_resource_by_id_modrinth(&app_clone, &file_info.resource_id).await?;
       Ok::<_, SJMCLError>(resource_info)
@@ -68,8 +70,12 @@ pub async fn add_local_mod_translations(
     let app_clone = app.clone();
     let file_path_clone = file_path.clone();
     tokio::spawn(async move {
-      let file_info =
-        fetch_remote_resource_by_local_curseforge(&app_clone, &file_path_clone).await?;
+      let file_info = fetch_remote_resource_by_local_cached(
+        &app_clone,
+        &file_path_clone,
+        &OtherResourceSource::CurseForge,
+      )
+      .await?;
       let resource_info =
         fetch_remote_resource_by_id_curseforge(&app_clone, &file_info.resource_id).await?;
       Ok::<_, SJMCLError>(resource_info)
@@ -30,6 +30,32 @@ struct LatestVersion {
   pub snapshot: String,
 }
 
+// unofficial feeds may omit fields or add their own
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct ExtraGameResource {
+  pub id: String,
+  #[serde(rename = "type")]
+  pub game_type: String,
+  pub release_time: String,
+  pub url: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ExtraVersionManifest {
+  pub versions: Vec<ExtraGameResource>,
+}
+
+fn get_game_type(id: &str, release_time: &str, game_type: String) -> String {
+  let april_fool = release_time.contains("04-01") && semver::Version::parse(id).is_err();
+  if april_fool {
+    "april_fools".to_string()
+  } else {
+    game_type
+  }
+}
+
 pub async fn get_game_version_manifest(
   app: &AppHandle,
   priority_list: &[SourceType],
@@ -54,19 +80,11 @@ pub async fn get_game_version_manifest(
     let game_info_list = manifest
       .versions
       .into_iter()
-      .map(|info| {
-        let april_fool =
-          info.release_time.contains("04-01") && semver::Version::parse(&info.id).is_err();
-        GameClientResourceInfo {
-          id: info.id,
-          game_type: if april_fool {
-            "april_fools".to_string()
-          } else {
-            info.game_type
-          },
-          release_time: info.release_time,
-          url: info.url,
-        }
+      .map(|info| GameClientResourceInfo {
+        game_type: get_game_type(&info.id, &info.release_time, info.game_type),
+        id: info.id,
+        release_time: info.release_time,
+        url: info.url,
       })
       .collect();
 
@@ -93,3 +111,50 @@ fn save_version_list_to_cache(app: &AppHandle, versions: &[GameResource]) {
   let content = ids.join("\n");
   let _ = fs::write(file_path, content);
 }
+
+/// Versions from user configured manifests, entries may point to a version JSON or a zip
+/// containing the client JAR and JSON. Unreachable feeds are skipped.
+pub async fn get_extra_game_version_manifests(
+  app: &AppHandle,
+  manifest_urls: &[String],
+) -> Vec<GameClientResourceInfo> {
+  let client = app.state::<reqwest::Client>();
+  let mut game_info_list = Vec::new();
+
+  for manifest_url in manifest_urls {
+    let manifest = match client.get(manifest_url).send().await {
+      Ok(resp) if resp.status().is_success() => resp.json::<ExtraVersionManifest>().await,
+      _ => {
+        log::warn!("Failed to fetch version manifest {}", manifest_url);
+        continue;
+      }
+    };
+    let Ok(manifest) = manifest else {
+      log::warn!("Failed to parse version manifest {}", manifest_url);
+      continue;
+    };
+
+    game_info_list.extend(
+      manifest
+        .versions
+        .into_iter()
+        .filter(|info| !info.id.is_empty() && !info.url.is_empty())
+        .map(|info| GameClientResourceInfo {
+          game_type: get_game_type(
+            &info.id,
+            &info.release_time,
+            if info.game_type.is_empty() {
+              "snapshot".to_string()
+            } else {
+              info.game_type
+            },
+          ),
+          id: info.id,
+          release_time: info.release_time,
+          url: info.url,
+        }),
+    );
+  }
+
+  game_info_list
+}
@@ -19,6 +19,7 @@ pub enum ResourceType {
   ForgeMavenNew,
   ForgeInstall,
   Liteloader,
+  LiteloaderMaven,
   OptiFine,
   AuthlibInjector,
   FabricMeta,
@@ -44,6 +45,8 @@ pub enum OtherResourceSource {
   CurseForge,
   Modrinth,
   MultiMc,
+  Technic,
+  ATLauncher,
 }
 
 impl FromStr for OtherResourceSource {
@@ -54,6 +57,8 @@ impl FromStr for OtherResourceSource {
       "curseforge" => Ok(OtherResourceSource::CurseForge),
       "modrinth" => Ok(OtherResourceSource::Modrinth),
       "multimc" => Ok(OtherResourceSource::MultiMc),
+      "technic" => Ok(OtherResourceSource::Technic),
+      "atlauncher" => Ok(OtherResourceSource::ATLauncher),
       _ => Err(format!("Unknown resource download type: {}", input)),
     }
   }
@@ -72,7 +77,9 @@ pub enum OtherResourceApiEndpoint {
   Search,
   VersionPack,
   FromLocal,
+  FromLocalBatch,
   ById,
+  ByIds,
   TranslateDesc,
 }
 
@@ -141,6 +148,10 @@ pub struct OtherResourceFileInfo {
   pub file_name: String,
   pub dependencies: Vec<OtherResourceDependency>,
   pub loader: Option<String>,
+  #[serde(default)]
+  pub file_id: String, // CurseForge file id or Modrinth version id
+  #[serde(default)]
+  pub changelog: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
@@ -166,6 +177,33 @@ pub struct ModUpdateQuery {
   pub old_file_path: String,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDependencyInfo {
+  pub resource: OtherResourceInfo,
+  pub file: Option<OtherResourceFileInfo>,
+  pub required_by: String, // resource id of the dependent
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModDependencyResolution {
+  pub files: Vec<OtherResourceFileInfo>, // the requested file first, then required dependencies
+  pub satisfied: Vec<ModDependencyInfo>, // required, already installed in the instance
+  pub unresolved: Vec<ModDependencyInfo>, // required, no file for the instance's loader and version
+  pub optional: Vec<ModDependencyInfo>,
+  pub incompatible: Vec<ModDependencyInfo>, // installed or to be installed
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModUpdateCandidate {
+  pub file_path: String,
+  pub current: OtherResourceFileInfo,
+  pub latest: OtherResourceFileInfo,
+  pub pinned: bool,
+}
+
 // game client itself
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -192,6 +230,8 @@ pub struct OptiFineResourceInfo {
   pub filename: String,
   pub patch: String,
   pub r#type: String,
+  #[serde(default)]
+  pub forge: Option<String>, // declared Forge compatibility, e.g. `Forge 14.23.5.2847`, `Forge N/A`
 }
 
 #[derive(Debug, Display)]
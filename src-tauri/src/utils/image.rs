@@ -83,6 +83,9 @@ impl<'de> Deserialize<'de> for ImageWrapper {
     D: Deserializer<'de>,
   {
     let base64_str = String::deserialize(deserializer)?;
+    if base64_str.is_empty() {
+      return Ok(ImageWrapper::default()); // serialized from an empty image
+    }
     let decoded_bytes = base64::engine::general_purpose::STANDARD
       .decode(&base64_str)
       .map_err(serde::de::Error::custom)?;